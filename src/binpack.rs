@@ -55,16 +55,33 @@
 //! println!("Occupancy of the bin: {:.1} %", bin.occupancy() * 100.0);
 //! ```
 
+use self::growth::GrowthPolicy;
 use self::guillotine::GuillotineBin;
 use self::maxrects::MaxRectsBin;
-use crate::dimension::Dimension;
+use self::shelf::ShelfBin;
+use self::skyline::SkylineBin;
+use self::validate::Violation;
+use crate::coord::Coord;
+use crate::dimension::{Alignment, Dimension};
 use crate::rectangle::Rectangle;
+use std::collections::HashMap;
 use std::error::Error;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::slice::Iter;
 
+pub mod binset;
+pub mod catalog;
+pub mod growth;
 pub mod guillotine;
+mod index;
+mod journal;
+pub mod keyed;
 pub mod maxrects;
+pub mod payload;
+pub mod shelf;
+pub mod sizing;
+pub mod skyline;
+pub mod validate;
 
 /// List of available bin packing algorithms.
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -73,18 +90,24 @@ pub enum BinType {
     MaxRects,
     /// Refers to the [`GuillotineBin`] packing algorithm.
     Guillotine,
+    /// Refers to the [`SkylineBin`] packing algorithm.
+    Skyline,
+    /// Refers to the [`ShelfBin`] packing algorithm.
+    Shelf,
 }
 
 /// Represents the core of a bin-packing algorithm.
 ///
 /// This trait provides a common set of methods for packing 2D rectangles into larger bins, which
 /// is implemented by all bin-packing algorithms provided by this package.
-pub trait BinPacker: Display {
+///
+/// Coordinates and extents are of the [`Coord`] type `C`, which is `i32` by default.
+pub trait BinPacker<C: Coord = i32>: Display {
     /// Returns the width of the bin.
-    fn width(&self) -> i32;
+    fn width(&self) -> C;
 
     /// Returns the height of the bin.
-    fn height(&self) -> i32;
+    fn height(&self) -> C;
 
     /// Removes all mapped rectangles from the bin.
     fn clear(&mut self) {
@@ -128,6 +151,49 @@ pub trait BinPacker: Display {
     /// ```
     fn shrink(&mut self, binary: bool);
 
+    /// Returns the margin which is kept free along the bin boundaries.
+    fn border(&self) -> C;
+
+    /// Specifies the margin which is kept free along the bin boundaries. Bins have no border by
+    /// default.
+    ///
+    /// A negative value is capped at 0. Changing the border removes all mapped rectangles from
+    /// the bin.
+    fn set_border(&mut self, border: C);
+
+    /// Returns the gap which is kept between neighbouring objects.
+    fn spacing(&self) -> C;
+
+    /// Specifies the gap which is kept between neighbouring objects. Unlike the [`Padding`] of
+    /// objects, spacing is not applied against the bin boundaries. Bins use no spacing by
+    /// default.
+    ///
+    /// A negative value is capped at 0. Changing the spacing removes all mapped rectangles from
+    /// the bin.
+    ///
+    /// [`Padding`]: crate::dimension::Padding
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{bin_new, BinType, Dimension};
+    ///
+    /// let mut bin = bin_new(BinType::MaxRects, 20, 8);
+    /// bin.set_spacing(4);
+    ///
+    /// // Only the gap between both objects is required
+    /// assert_eq!(8, bin.insert(&Dimension::new(8, 8)).unwrap().width());
+    /// assert_eq!(12, bin.insert(&Dimension::new(8, 8)).unwrap().x());
+    /// ```
+    fn set_spacing(&mut self, spacing: C);
+
+    /// Returns the [`GrowthPolicy`] of the bin, if available.
+    fn growth_policy(&self) -> Option<GrowthPolicy<C>>;
+
+    /// Specifies a [`GrowthPolicy`] for enlarging the bin automatically if objects do not fit.
+    /// Specify `None` to reject objects which do not fit into the bin. Bins do not grow by default.
+    fn set_growth_policy(&mut self, policy: Option<GrowthPolicy<C>>);
+
     /// Inserts a single [`Dimension`] object into the bin.
     ///
     /// `dim` refers to the object to be packed into the bin.
@@ -138,7 +204,7 @@ pub trait BinPacker: Display {
     /// **Note:** This trait method performs the operation with sane default values for
     /// packer-specific implementations. You can override them by the bin packer's own
     /// `set_default_*()` methods.
-    fn insert(&mut self, dim: &Dimension) -> Option<Rectangle>;
+    fn insert(&mut self, dim: &Dimension<C>) -> Option<Rectangle<C>>;
 
     /// Attempts to insert the given list of [`Dimension`] objects into the bin.
     ///
@@ -156,7 +222,90 @@ pub trait BinPacker: Display {
     /// **Note:** This trait method performs the operation with sane default values for
     /// packer-specific implementations. You can override them by the bin packer's own
     /// `set_default_*()` methods.
-    fn insert_list(&mut self, nodes: &[Dimension]) -> (Vec<Rectangle>, Vec<Dimension>);
+    fn insert_list(&mut self, nodes: &[Dimension<C>]) -> (Vec<Rectangle<C>>, Vec<Dimension<C>>);
+
+    /// Inserts a single [`Dimension`] object at the specified position of the bin.
+    ///
+    /// `x` and `y` specify the position of the object, excluding padding.
+    ///
+    /// `dim` refers to the object to be packed into the bin. The object is placed upright, unless
+    /// rotation is enforced by its [`Rotation`] policy.
+    ///
+    /// [`Rotation`]: crate::dimension::Rotation
+    ///
+    /// Returns a copy of the packed [`Rectangle`] if the object was inserted successful,
+    /// or `None` if the requested area is not located inside the bin or not available.
+    /// The bin is not enlarged by the [`GrowthPolicy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{bin_new, BinType, Dimension};
+    ///
+    /// let mut bin = bin_new(BinType::MaxRects, 16, 16);
+    /// assert!(bin.insert_at(4, 4, &Dimension::new(8, 8)).is_some());
+    /// assert!(bin.insert_at(8, 8, &Dimension::new(8, 8)).is_none());
+    ///
+    /// // Subsequently inserted objects are placed around the rectangle
+    /// let rect = bin.insert(&Dimension::new(16, 4));
+    /// assert!(rect.is_some_and(|r| r.y() == 0 || r.y() == 12));
+    /// ```
+    fn insert_at(&mut self, x: C, y: C, dim: &Dimension<C>) -> Option<Rectangle<C>>;
+
+    /// Determines where the given [`Dimension`] object would be placed by [`insert`], without
+    /// modifying the bin.
+    ///
+    /// [`insert`]: BinPacker::insert
+    ///
+    /// Returns the [`Rectangle`] which would be packed into the bin, or `None` if the object
+    /// does not fit. Objects which would only fit after enlarging the bin by the
+    /// [`GrowthPolicy`] are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{bin_new, BinType, Dimension};
+    ///
+    /// let mut bin = bin_new(BinType::MaxRects, 16, 16);
+    /// let node = Dimension::new(8, 8);
+    /// let preview = bin.preview_insert(&node);
+    /// assert!(bin.is_empty());
+    ///
+    /// assert_eq!(preview, bin.insert(&node));
+    /// ```
+    fn preview_insert(&self, dim: &Dimension<C>) -> Option<Rectangle<C>>;
+
+    /// Returns `true` if the given [`Dimension`] object can be inserted into the bin by [`insert`],
+    /// without modifying the bin.
+    ///
+    /// [`insert`]: BinPacker::insert
+    ///
+    /// Objects which would only fit after enlarging the bin by the [`GrowthPolicy`] are not
+    /// considered.
+    fn can_fit(&self, dim: &Dimension<C>) -> bool {
+        self.preview_insert(dim).is_some()
+    }
+
+    /// Returns how many copies of the given [`Dimension`] object can still be inserted into the
+    /// bin by subsequent calls of [`insert`], without modifying the bin.
+    ///
+    /// [`insert`]: BinPacker::insert
+    ///
+    /// The insertions are simulated on a copy of the free space of the bin, which is not enlarged
+    /// by the [`GrowthPolicy`]. Processing time is proportional to the number of copies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{bin_new, BinType, Dimension};
+    ///
+    /// let mut bin = bin_new(BinType::Guillotine, 16, 16);
+    /// bin.insert(&Dimension::new(16, 8));
+    ///
+    /// assert_eq!(2, bin.count_fit(&Dimension::new(8, 8)));
+    /// assert_eq!(1, bin.len());
+    /// ```
+    fn count_fit(&self, dim: &Dimension<C>) -> usize;
 
     /// Computes the ratio of used surface area to the total bin area and returns it as a
     /// normalized value in the range `[0.0, 1.0]`.
@@ -165,7 +314,7 @@ pub trait BinPacker: Display {
     /// Extracts a slice containing the entire list of mapped rectangles.
     ///
     /// Equivalent to `&bin[..]`.
-    fn as_slice(&self) -> &[Rectangle];
+    fn as_slice(&self) -> &[Rectangle<C>];
 
     /// Returns `true` if the list of mapped rectangles contains no entries.
     fn is_empty(&self) -> bool;
@@ -174,11 +323,125 @@ pub trait BinPacker: Display {
     fn len(&self) -> usize;
 
     /// Returns an iterator over the list of mapped rectangles.
-    fn iter(&self) -> Iter<'_, Rectangle>;
+    fn iter(&self) -> Iter<'_, Rectangle<C>>;
 
     /// Returns the first mapped rectangle with the specified identifier, if available.
     /// Returns `None` otherwise.
-    fn find_by_id(&self, id: isize) -> Option<Rectangle>;
+    ///
+    /// Mapped rectangles are indexed by their identifiers, so the lookup takes constant time.
+    fn find_by_id(&self, id: isize) -> Option<Rectangle<C>>;
+
+    /// Removes the first mapped rectangle with the specified identifier from the bin and makes
+    /// the occupied space available for subsequent insertions.
+    ///
+    /// Returns the removed [`Rectangle`] if available, and `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{bin_new, BinType, Dimension};
+    ///
+    /// let mut bin = bin_new(BinType::MaxRects, 16, 16);
+    /// bin.insert(&Dimension::with_id(1, 16, 16, 0));
+    /// assert!(bin.insert(&Dimension::with_id(2, 16, 16, 0)).is_none());
+    ///
+    /// // The space of the removed rectangle is available again
+    /// assert!(bin.remove(1).is_some());
+    /// assert!(bin.insert(&Dimension::with_id(2, 16, 16, 0)).is_some());
+    /// ```
+    fn remove(&mut self, id: isize) -> Option<Rectangle<C>> {
+        let rect = self.find_by_id(id)?;
+        if self.remove_rect(&rect) {
+            Some(rect)
+        } else {
+            None
+        }
+    }
+
+    /// Removes the specified mapped rectangle from the bin and makes the occupied space available
+    /// for subsequent insertions.
+    ///
+    /// Returns `true` if the rectangle was found and removed, and `false` otherwise.
+    ///
+    /// **Note:** Skyline and shelf bins can only reclaim the space of the topmost rectangles
+    /// or the last rectangle of the open shelf respectively, unless their waste map is enabled.
+    fn remove_rect(&mut self, rect: &Rectangle<C>) -> bool;
+
+    /// Creates a checkpoint of the current state of the bin.
+    ///
+    /// All subsequent modifications of the bin are recorded, until the checkpoint is either
+    /// discarded by [`rollback`] or accepted by [`commit`]. Checkpoints can be nested, in which
+    /// case [`rollback`] and [`commit`] refer to the most recent checkpoint.
+    ///
+    /// Modifications are recorded incrementally, which is usually much cheaper than a copy of the
+    /// whole bin. Only operations which rearrange the whole bin, such as [`grow`], [`shrink`] and
+    /// the removal of rectangles, may record the full content of internal lists.
+    ///
+    /// [`rollback`]: BinPacker::rollback
+    /// [`commit`]: BinPacker::commit
+    /// [`grow`]: BinPacker::grow
+    /// [`shrink`]: BinPacker::shrink
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{bin_new, BinType, Dimension};
+    ///
+    /// let mut bin = bin_new(BinType::MaxRects, 16, 16);
+    /// bin.insert(&Dimension::new(16, 8));
+    ///
+    /// // Either all objects of the group are packed or none of them
+    /// let group = vec![Dimension::new(8, 8); 3];
+    /// bin.checkpoint();
+    /// let (_, rejected) = bin.insert_list(&group);
+    /// if rejected.is_empty() {
+    ///     bin.commit();
+    /// } else {
+    ///     bin.rollback();
+    /// }
+    ///
+    /// assert_eq!(1, bin.len());
+    /// ```
+    fn checkpoint(&mut self);
+
+    /// Undoes all modifications of the bin since the most recent checkpoint, and removes the
+    /// checkpoint.
+    ///
+    /// Returns `false` if no checkpoint is active, and `true` otherwise.
+    fn rollback(&mut self) -> bool;
+
+    /// Keeps all modifications of the bin since the most recent checkpoint, and removes the
+    /// checkpoint.
+    ///
+    /// Returns `false` if no checkpoint is active, and `true` otherwise.
+    fn commit(&mut self) -> bool;
+
+    /// Validates the layout of the bin and returns all violations found, or an empty list if the
+    /// layout is valid.
+    ///
+    /// Verifies that no two placed rectangles overlap, padding and spacing included, and that
+    /// every rectangle lies within the bin. Bin packers which maintain lists of free rectangles
+    /// verify that the lists are consistent with the placed rectangles as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{bin_new, BinPacker, BinType, Dimension};
+    ///
+    /// let mut bin = bin_new(BinType::Guillotine, 64, 64);
+    /// bin.set_spacing(2);
+    /// bin.insert_list(&vec![Dimension::with_padding(12, 12, 1); 16]);
+    /// assert!(bin.validate().is_empty());
+    /// ```
+    fn validate(&self) -> Vec<Violation<C>> {
+        validate::check_layout(
+            self.width(),
+            self.height(),
+            self.border(),
+            self.as_slice(),
+            &[],
+        )
+    }
 
     /// Returns a visual representation of the bin as ascii graphics `String`.
     ///
@@ -195,14 +458,26 @@ pub trait BinPacker: Display {
 }
 
 /// This error is returned when items could not be placed into bins.
-#[derive(Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum BinError {
-    /// Item does not fit into the bin because item dimension is bigger than bin dimension.
+    /// Item does not fit into the bin because item dimension, including padding, is bigger than
+    /// bin dimension.
     ItemTooBig,
     /// Item has a dimension of 0 and can therefore not be meaningfully placed into the bin.
     ItemTooSmall,
-    /// A generic "catch-all" error, which is returned when the cause could not be determined.
-    Unspecified,
+    /// Item fits into the bin only in an orientation which is not permitted by its [`Rotation`]
+    /// policy or by the bin packer.
+    ///
+    /// [`Rotation`]: crate::dimension::Rotation
+    RotationForbidden,
+    /// Items could not be packed into a single bin without exceeding the maximum bin size.
+    MaxSizeExceeded,
+    /// Items could not be packed because all bin sizes which can hold them are out of stock.
+    OutOfStock,
+    /// Items of the same group could not be placed together into a single bin.
+    GroupTooBig,
+    /// Item fits into the empty bin, but not into the space which is left in the bin.
+    NoSpaceLeft,
 }
 
 impl Display for BinError {
@@ -210,7 +485,11 @@ impl Display for BinError {
         let s = match self {
             Self::ItemTooBig => "item is too big for the bin",
             Self::ItemTooSmall => "item with no space cannot be placed into the bin",
-            _ => "unspecified error",
+            Self::RotationForbidden => "item fits into the bin only in a forbidden orientation",
+            Self::MaxSizeExceeded => "items do not fit into a bin of the maximum size",
+            Self::OutOfStock => "no bins left in stock for the remaining items",
+            Self::GroupTooBig => "group of items does not fit into a single bin",
+            Self::NoSpaceLeft => "item does not fit into the space left in the bin",
         };
         f.write_str(s)
     }
@@ -218,11 +497,122 @@ impl Display for BinError {
 
 impl Error for BinError {}
 
+/// Describes a single item which could not be placed into any bin.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Rejection {
+    /// Identifier of the rejected [`Dimension`] object.
+    id: isize,
+    /// Reason why the item was rejected.
+    reason: BinError,
+    /// Tag of the rejected [`Dimension`] object, which is used by the payload API.
+    tag: usize,
+}
+
+impl Rejection {
+    /// Creates a new rejection of the item with the given identifier.
+    pub(crate) fn new(id: isize, reason: BinError) -> Self {
+        Self { id, reason, tag: 0 }
+    }
+
+    /// Creates a new rejection of the given item.
+    pub(crate) fn from_node(node: &Dimension, reason: BinError) -> Self {
+        Self {
+            id: node.id(),
+            reason,
+            tag: node.tag,
+        }
+    }
+
+    /// Returns the identifier of the rejected [`Dimension`] object.
+    pub fn id(&self) -> isize {
+        self.id
+    }
+
+    /// Returns the reason why the item was rejected.
+    pub fn reason(&self) -> BinError {
+        self.reason
+    }
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "item {}: {}", self.id, self.reason)
+    }
+}
+
+/// This error is returned by functions which pack items into multiple bins, if some of the
+/// items could not be placed into any bin.
+///
+/// All other items are still packed, and the resulting bins are available by [`bins`] or
+/// [`into_bins`].
+///
+/// [`bins`]: PackError::bins
+/// [`into_bins`]: PackError::into_bins
+pub struct PackError<B = Box<dyn BinPacker>> {
+    /// List of rejected items, in order of processing.
+    rejections: Vec<Rejection>,
+    /// List of bins with all items which could be packed.
+    bins: Vec<B>,
+}
+
+impl<B> PackError<B> {
+    /// Creates a new error from the given list of rejected items and packed bins.
+    pub(crate) fn new(rejections: Vec<Rejection>, bins: Vec<B>) -> Self {
+        Self { rejections, bins }
+    }
+
+    /// Returns the list of rejected items.
+    pub fn rejections(&self) -> &[Rejection] {
+        &self.rejections
+    }
+
+    /// Returns the list of bins with all items which could be packed.
+    pub fn bins(&self) -> &[B] {
+        &self.bins
+    }
+
+    /// Consumes the error and returns the list of bins with all items which could be packed.
+    pub fn into_bins(self) -> Vec<B> {
+        self.bins
+    }
+
+    /// Converts the bins of this error by the given function.
+    pub(crate) fn map_bins<C, F>(self, f: F) -> PackError<C>
+    where
+        F: FnMut(B) -> C,
+    {
+        PackError::new(self.rejections, self.bins.into_iter().map(f).collect())
+    }
+}
+
+impl<B> Debug for PackError<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PackError")
+            .field("rejections", &self.rejections)
+            .field("bins", &self.bins.len())
+            .finish()
+    }
+}
+
+impl<B> Display for PackError<B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} item(s) could not be packed", self.rejections.len())?;
+        if let Some(rejection) = self.rejections.first() {
+            write!(f, " ({rejection})")?;
+        }
+        Ok(())
+    }
+}
+
+impl<B> Error for PackError<B> {}
+
 /// Creates an empty bin of the given size, using the specified [`BinType`] implementation.
 pub fn bin_new(bin_type: BinType, width: i32, height: i32) -> Box<dyn BinPacker> {
     match bin_type {
         BinType::MaxRects => Box::new(MaxRectsBin::new(width, height)),
         BinType::Guillotine => Box::new(GuillotineBin::new(width, height)),
+        BinType::Skyline => Box::new(SkylineBin::new(width, height)),
+        BinType::Shelf => Box::new(ShelfBin::new(width, height)),
     }
 }
 
@@ -237,6 +627,8 @@ pub fn bin_with_capacity(
     match bin_type {
         BinType::MaxRects => Box::new(MaxRectsBin::with_capacity(width, height, capacity)),
         BinType::Guillotine => Box::new(GuillotineBin::with_capacity(width, height, capacity)),
+        BinType::Skyline => Box::new(SkylineBin::with_capacity(width, height, capacity)),
+        BinType::Shelf => Box::new(ShelfBin::with_capacity(width, height, capacity)),
     }
 }
 
@@ -250,11 +642,18 @@ pub fn bin_with_capacity(
 ///
 /// [`insert_list`]: BinPacker::insert_list
 ///
+/// Nodes with the same [`group`] key are always placed into the same bin.
+///
+/// [`group`]: Dimension::group
+///
 /// Returns a list of bins with the packed rectangle nodes as a [`Result`] value.
 ///
 /// # Errors
 ///
-/// A [`BinError`] is returned for nodes which are either empty or too big for the bin.
+/// A [`PackError`] is returned if any nodes could not be packed, listing each rejected node with
+/// the reason of rejection. Nodes are rejected if they are either empty or too big for the bin,
+/// or if they belong to a group of nodes which does not fit into a single bin. All other nodes
+/// are still packed into the bins provided by the error.
 ///
 /// # Examples
 /// ```
@@ -276,132 +675,396 @@ pub fn pack_bins(
     bin_width: i32,
     bin_height: i32,
     optimized: bool,
-) -> Result<Vec<Box<dyn BinPacker>>, BinError> {
-    if optimized {
-        pack_bins_list(bin_type, nodes, bin_width, bin_height)
-    } else {
-        pack_bins_single(bin_type, nodes, bin_width, bin_height)
-    }
+) -> Result<Vec<Box<dyn BinPacker>>, PackError> {
+    pack_bins_with(nodes, bin_width, bin_height, optimized, |width, height| {
+        bin_new(bin_type, width, height)
+    })
 }
 
-/// Inserts nodes via insert_list().
-fn pack_bins_list(
+/// A convenience function that packs a given list of `Dimension` objects into a strip of the
+/// given width, and reduces the height of the strip as much as possible.
+///
+/// The strip is packed with the default heuristic rules for the given [`BinType`]. The height
+/// of the returned bin is the final strip height.
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too wide for the strip.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{BinPacker, BinType, Dimension, pack_strip};
+///
+/// // Defining three items of different size
+/// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
+///
+/// // Returned bin contains all nodes
+/// let strip = pack_strip(BinType::MaxRects, &nodes, 8)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(8, strip.width());
+/// assert_eq!(12, strip.height());
+/// assert_eq!(3, strip.len());
+/// ```
+pub fn pack_strip(
     bin_type: BinType,
+    nodes: &[Dimension],
+    strip_width: i32,
+) -> Result<Box<dyn BinPacker>, BinError> {
+    sizing::pack_strip_with(nodes, strip_width, |width, height| {
+        bin_new(bin_type, width, height)
+    })
+}
+
+/// Packs a given list of `Dimension` objects into a variable number of bins, which are created
+/// on demand by the function `create`.
+///
+/// Nodes are packed by [`insert_list`] if `optimized` is `true`, and by [`insert`] otherwise.
+/// Nodes with the same group key are always placed into the same bin. Groups containing a node
+/// which cannot be packed are rejected as a whole.
+///
+/// [`insert_list`]: BinPacker::insert_list
+/// [`insert`]: BinPacker::insert
+pub(crate) fn pack_bins_with<B, F>(
     nodes: &[Dimension],
     bin_width: i32,
     bin_height: i32,
-) -> Result<Vec<Box<dyn BinPacker>>, BinError> {
+    optimized: bool,
+    mut create: F,
+) -> Result<Vec<Box<B>>, PackError<Box<B>>>
+where
+    B: BinPacker + ?Sized,
+    F: FnMut(i32, i32) -> Box<B>,
+{
     let mut bins = Vec::new();
     if nodes.is_empty() || bin_width == 0 || bin_height == 0 {
         return Ok(bins);
     }
 
-    // first pass is done separately to avoid a (potentially) costly clone operation
-    let mut bin = bin_new(bin_type, bin_width, bin_height);
-    let (inserted, mut rejected) = bin.insert_list(nodes);
+    let mut empty_bin = create(bin_width, bin_height);
+    let mut rejections = Vec::new();
+    let mut groups = Vec::new();
+    for group in group_nodes(nodes) {
+        let reasons: Vec<Option<BinError>> = group
+            .iter()
+            .map(|node| {
+                (node.is_empty() || !empty_bin.can_fit(node))
+                    .then(|| rejection_reason(empty_bin.as_ref(), node))
+            })
+            .collect();
 
-    if inserted.is_empty() && !rejected.is_empty() {
-        // remaining nodes are too big and will be silently skipped
-        rejected.clear();
+        if reasons.iter().any(Option::is_some) {
+            reject_group(&mut rejections, &group, &reasons);
+        } else if group.len() > 1 && !fits_group(empty_bin.as_mut(), &group, optimized) {
+            reject_group(&mut rejections, &group, &[]);
+        } else {
+            groups.push(group);
+        }
     }
 
-    if !inserted.is_empty() {
-        bins.push(bin);
+    if optimized {
+        let mut nodes_left: Vec<Dimension> = groups.concat();
+        while !nodes_left.is_empty() {
+            // every group fits into an empty bin, which guarantees progress
+            let mut bin = create(bin_width, bin_height);
+            let (_, rejected) = insert_list_grouped(bin.as_mut(), &nodes_left);
+            bins.push(bin);
+            nodes_left = rejected;
+        }
+    } else {
+        for group in &groups {
+            // try inserting group into existing bins
+            if !bins
+                .iter_mut()
+                .any(|bin| insert_group(bin.as_mut(), group, false).is_some())
+            {
+                let mut bin = create(bin_width, bin_height);
+                insert_group(bin.as_mut(), group, false);
+                bins.push(bin);
+            }
+        }
     }
 
-    // subsequent passes are done in a loop
-    let mut nodes_left = rejected;
-    while !nodes_left.is_empty() {
-        let mut bin = bin_new(bin_type, bin_width, bin_height);
-        let (inserted, mut rejected) = bin.insert_list(&nodes_left);
+    if rejections.is_empty() {
+        Ok(bins)
+    } else {
+        Err(PackError::new(rejections, bins))
+    }
+}
 
-        if inserted.is_empty() && !rejected.is_empty() {
-            // remaining nodes are too big or too small
-            let result = rejected
-                .iter()
-                .map(|r| {
-                    if r.width_total() == 0 || r.height_total() == 0 {
-                        BinError::ItemTooSmall
-                    } else if r.width_total() > bin_width || r.height_total() > bin_height {
-                        BinError::ItemTooBig
-                    } else {
-                        BinError::Unspecified
-                    }
-                })
-                .next();
-            if let Some(result) = result {
-                return Err(result);
-            } else {
-                // Should not happen
-                eprintln!("pack_bins(): Could not insert remaining items");
-                rejected.clear();
-            }
-        }
+/// Determines why the given `Dimension` object could not be inserted into the given bin.
+///
+/// The object is checked against the packing area of the bin, which considers border and
+/// spacing. Objects which fit into the packing area, but not into the empty bin, are too big for
+/// the bin as well, e.g. due to alignment.
+pub(crate) fn rejection_reason<C, B>(bin: &B, node: &Dimension<C>) -> BinError
+where
+    C: Coord,
+    B: BinPacker<C> + ?Sized,
+{
+    let area = packing_area(bin.width(), bin.height(), bin.border(), bin.spacing());
+    let spaced = node.to_spaced(bin.spacing());
+    if node.is_empty() {
+        BinError::ItemTooSmall
+    } else if !spaced.fits_into(area.width(), area.height(), true)
+        && !spaced
+            .to_flipped()
+            .fits_into(area.width(), area.height(), true)
+    {
+        // object fits in none of the orientations, regardless of its rotation policy
+        BinError::ItemTooBig
+    } else if bin.can_fit(&node.to_flipped()) {
+        // object fits only if rotated, but the bin does not rotate objects
+        BinError::RotationForbidden
+    } else if bin.is_empty() {
+        BinError::ItemTooBig
+    } else {
+        BinError::NoSpaceLeft
+    }
+}
 
-        if !inserted.is_empty() {
-            bins.push(bin);
+/// Adds a rejection for each node of the given group to `rejections`. Nodes without a specific
+/// reason in `reasons` are rejected with [`BinError::GroupTooBig`].
+pub(crate) fn reject_group(
+    rejections: &mut Vec<Rejection>,
+    group: &[Dimension],
+    reasons: &[Option<BinError>],
+) {
+    rejections.extend(group.iter().enumerate().map(|(idx, node)| {
+        let reason = reasons.get(idx).copied().flatten();
+        Rejection::from_node(node, reason.unwrap_or(BinError::GroupTooBig))
+    }));
+}
+
+/// Splits the given list of `Dimension` objects into groups of the same group key, in order of
+/// their first occurrence. Each object without group key forms a group of its own.
+pub(crate) fn group_nodes(nodes: &[Dimension]) -> Vec<Vec<Dimension>> {
+    let mut groups: Vec<Vec<Dimension>> = Vec::new();
+    let mut indices: HashMap<isize, usize> = HashMap::new();
+    for node in nodes {
+        match node.group() {
+            Some(key) => match indices.get(&key) {
+                Some(&index) => groups[index].push(*node),
+                None => {
+                    indices.insert(key, groups.len());
+                    groups.push(vec![*node]);
+                }
+            },
+            None => groups.push(vec![*node]),
         }
+    }
+    groups
+}
 
-        // preparing for next iteration
-        nodes_left.clear();
-        nodes_left.append(&mut rejected);
+/// Inserts either all of the given `Dimension` objects into the bin, or none of them.
+///
+/// Objects are inserted by [`insert_list`] if `optimized` is `true`, and one after the other by
+/// [`insert`] otherwise.
+///
+/// [`insert_list`]: BinPacker::insert_list
+/// [`insert`]: BinPacker::insert
+///
+/// Returns the list of inserted [`Rectangle`] objects, or `None` if the objects do not fit.
+pub(crate) fn insert_group<B>(
+    bin: &mut B,
+    nodes: &[Dimension],
+    optimized: bool,
+) -> Option<Vec<Rectangle>>
+where
+    B: BinPacker + ?Sized,
+{
+    bin.checkpoint();
+    let result = if optimized {
+        let (inserted, rejected) = bin.insert_list(nodes);
+        rejected.is_empty().then_some(inserted)
+    } else {
+        nodes.iter().map(|node| bin.insert(node)).collect()
+    };
+
+    if result.is_some() {
+        bin.commit();
+    } else {
+        bin.rollback();
     }
+    result
+}
 
-    Ok(bins)
+/// Returns whether all of the given `Dimension` objects can be inserted together into the bin,
+/// without modifying the bin.
+fn fits_group<B>(bin: &mut B, nodes: &[Dimension], optimized: bool) -> bool
+where
+    B: BinPacker + ?Sized,
+{
+    bin.checkpoint();
+    let result = insert_group(bin, nodes, optimized).is_some();
+    bin.rollback();
+    result
 }
 
-/// Inserts nodes via insert().
-fn pack_bins_single(
-    bin_type: BinType,
+/// Inserts the given list of `Dimension` objects into the bin by [`insert_list`], without
+/// splitting groups of objects with the same group key.
+///
+/// [`insert_list`]: BinPacker::insert_list
+///
+/// Groups which would be split are retried one by one after all other objects have been
+/// inserted.
+///
+/// Returns a tuple consisting of the list with all successfully inserted [`Rectangle`] objects
+/// and a list of rejected [`Dimension`] objects.
+pub(crate) fn insert_list_grouped<B>(
+    bin: &mut B,
     nodes: &[Dimension],
-    bin_width: i32,
-    bin_height: i32,
-) -> Result<Vec<Box<dyn BinPacker>>, BinError> {
-    let mut bins = Vec::new();
-    if nodes.is_empty() || bin_width == 0 || bin_height == 0 {
-        return Ok(bins);
+) -> (Vec<Rectangle>, Vec<Dimension>)
+where
+    B: BinPacker + ?Sized,
+{
+    if nodes.iter().all(|node| node.group().is_none()) {
+        return bin.insert_list(nodes);
     }
 
-    for node in nodes {
-        if node.is_empty() {
-            return Err(BinError::ItemTooSmall);
-        } else if node.width_total() > bin_width || node.height_total() > bin_height {
-            return Err(BinError::ItemTooBig);
+    let mut candidates = nodes.to_vec();
+    let mut deferred = Vec::new();
+    let (mut inserted, mut rejected) = loop {
+        bin.checkpoint();
+        let (inserted, rejected) = bin.insert_list(&candidates);
+        let split: Vec<isize> = rejected
+            .iter()
+            .filter_map(|node| node.group())
+            .filter(|&key| inserted.iter().any(|r| r.dim().group() == Some(key)))
+            .collect();
+
+        if split.is_empty() {
+            bin.commit();
+            break (inserted, rejected);
         }
 
-        // try inserting node into existing bins
-        let mut inserted = false;
-        for bin in &mut bins {
-            if bin.insert(node).is_some() {
-                inserted = true;
-                break;
-            }
+        bin.rollback();
+        let (split, kept): (Vec<Dimension>, Vec<Dimension>) = candidates
+            .into_iter()
+            .partition(|node| node.group().is_some_and(|key| split.contains(&key)));
+        deferred.extend(group_nodes(&split));
+        candidates = kept;
+    };
+
+    for group in deferred {
+        match insert_group(bin, &group, true) {
+            Some(mut rects) => inserted.append(&mut rects),
+            None => rejected.extend(group),
         }
+    }
 
-        // create new bin if needed
-        if !inserted {
-            bins.push(bin_new(bin_type, bin_width, bin_height));
-            if let Some(bin) = bins.last_mut() {
-                bin.insert(node).expect("Object should fit into the bin");
-            }
+    (inserted, rejected)
+}
+
+/// Returns the area of a bin of the given size which is available for packing objects.
+///
+/// The area is reduced by `border` on each side of the bin. Objects keep a gap of `spacing` to
+/// their neighbours on the right and bottom side, which is not needed at the bin boundary, so the
+/// area is extended by `spacing` to the right and bottom, so that the right and bottom edges
+/// saturate at the greatest value of the coordinate type.
+pub(crate) fn packing_area<C: Coord>(width: C, height: C, border: C, spacing: C) -> Rectangle<C> {
+    let extent = |side: C| match side.checked_sub(border).and_then(|s| s.checked_sub(border)) {
+        Some(inner) if inner > C::ZERO => {
+            border.saturating_add(inner.saturating_add(spacing)) - border
+        }
+        _ => C::ZERO,
+    };
+    Rectangle::new(
+        border,
+        border,
+        Dimension::with_id(0, extent(width), extent(height), C::ZERO),
+    )
+}
+
+/// Returns the `Rectangle` for the given `Dimension` placed at the specified position, if it is
+/// located inside the given packing area. The `Dimension` is rotated only if enforced by its
+/// [`Rotation`] policy.
+///
+/// The position must match the given [`Alignment`], if specified.
+///
+/// [`Rotation`]: crate::dimension::Rotation
+pub(crate) fn rect_at<C: Coord>(
+    x: C,
+    y: C,
+    dim: &Dimension<C>,
+    area: &Rectangle<C>,
+    alignment: Option<Alignment<C>>,
+) -> Option<Rectangle<C>> {
+    if dim.is_empty() {
+        return None;
+    }
+
+    let (mut dim, rotated) = dim.orientations(false).next()?;
+    if let Some(alignment) = alignment {
+        if !alignment.is_aligned(x, y) {
+            return None;
         }
+        dim = dim.to_aligned(&alignment);
+    }
+
+    let mut rect = Rectangle::new(x, y, dim);
+    rect.set_rotated(rotated);
+    if !rect.is_total_valid() || !area.contains_total(&rect) {
+        return None;
+    }
+
+    Some(rect)
+}
+
+/// Determines the aligned placement of the given oriented `Dimension` within the free rectangle.
+///
+/// Returns the part of the free rectangle which starts at the first aligned position of the
+/// `Dimension`, together with the `Dimension` rounded up to the alignment. Returns `None` if no
+/// aligned position is located within the free rectangle.
+pub(crate) fn align_placement<C: Coord>(
+    free: &Rectangle<C>,
+    dim: &Dimension<C>,
+    alignment: Option<Alignment<C>>,
+) -> Option<(Rectangle<C>, Dimension<C>)> {
+    let alignment = match alignment {
+        Some(alignment) => alignment,
+        None => return Some((*free, *dim)),
+    };
+
+    let padding = dim.padding();
+    let x = alignment.align_x(free.x_total().checked_add(padding.left())?)? - padding.left();
+    let y = alignment.align_y(free.y_total().checked_add(padding.top())?)? - padding.top();
+    if x >= free.right_total() || y >= free.bottom_total() {
+        return None;
+    }
+
+    let dim = dim.to_aligned(&alignment);
+    if !dim.is_total_valid() {
+        return None;
     }
 
-    Ok(bins)
+    let width = free.right_total() - x;
+    let height = free.bottom_total() - y;
+    let rect = Rectangle::new(x, y, Dimension::with_id(0, width, height, C::ZERO));
+    Some((rect, dim))
 }
 
 /// A helper method for visualizing bin content.
-fn visualize_bin(width: i32, height: i32, rects: &Vec<Rectangle>) -> Option<String> {
+fn visualize_bin<C: Coord>(width: C, height: C, rects: &[Rectangle<C>]) -> Option<String> {
+    let (width, height) = (width.to_u32(), height.to_u32());
     if width > 0 && height > 0 && rects.len() <= 62 {
         // initializing grid
-        let size = (width * height) as usize;
+        let size = width as usize * height as usize;
         let mut grid = vec![0u32; size];
 
         for y in 0..height {
+            let cy = C::from_u32(y);
             for x in 0..width {
+                let cx = C::from_u32(x);
                 for (i, r) in rects.iter().enumerate() {
-                    if r.y() <= y && r.y() + r.height() > y && r.x() <= x && r.x() + r.width() > x {
-                        let pos = (y * width + x) as usize;
+                    if r.y() <= cy
+                        && r.y().saturating_add(r.height()) > cy
+                        && r.x() <= cx
+                        && r.x().saturating_add(r.width()) > cx
+                    {
+                        let pos = y as usize * width as usize + x as usize;
                         grid[pos] = (i + 1) as u32;
                     }
                 }
@@ -411,7 +1074,7 @@ fn visualize_bin(width: i32, height: i32, rects: &Vec<Rectangle>) -> Option<Stri
         // converting grid to string
         let mut output = String::with_capacity(grid.len() + height as usize);
         for y in 0..height {
-            let pos = (y * width) as usize;
+            let pos = y as usize * width as usize;
             let line: String = grid[pos..(pos + width as usize)]
                 .iter()
                 .map(|v| match v {
@@ -0,0 +1,314 @@
+//! Provides a manager for packing items into a growing number of bins, one item at a time.
+//!
+//! # Quick Start
+//!
+//! This example demonstrates how items can be streamed into a set of bins. New bins are opened
+//! on demand, and the target bin for each item is chosen by a configurable [`SelectionPolicy`].
+//!
+//! ```rust
+//! use binpack2d::{BinType, Dimension};
+//! use binpack2d::binset::{BinSet, SelectionPolicy};
+//!
+//! // Create a set of 64x64 bins, using the "MaxRects" bin type.
+//! let mut bins = BinSet::new(BinType::MaxRects, 64, 64);
+//! bins.set_policy(SelectionPolicy::BestFit);
+//!
+//! // Items can be inserted at any time.
+//! let (idx, rect) = bins.insert(&Dimension::with_id(1, 48, 48, 0)).expect("Item should fit");
+//! println!("Item {} was placed into bin {idx} at (x: {}, y: {})", rect.id(), rect.x(), rect.y());
+//!
+//! // Bins can be closed to prevent further insertions.
+//! bins.close(idx);
+//! let (idx, _) = bins.insert(&Dimension::with_id(2, 16, 16, 0)).expect("Item should fit");
+//! assert_eq!(1, idx);
+//!
+//! // Items can be looked up across all bins.
+//! assert_eq!(Some(0), bins.find_by_id(1).map(|(idx, _)| idx));
+//! println!("Number of bins: {}", bins.len());
+//! ```
+
+use super::{bin_new, group_nodes, insert_group, rejection_reason, BinError, BinPacker, BinType};
+use crate::dimension::Dimension;
+use crate::rectangle::Rectangle;
+use std::fmt::{Display, Formatter};
+use std::ops::Index;
+use std::slice::{Iter, SliceIndex};
+
+/// List of supported policies for choosing the bin to place an item into.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SelectionPolicy {
+    /// Places items into the first open bin that can hold them.
+    FirstFit,
+    /// Places items into the open bin with the highest occupancy after insertion.
+    BestFit,
+    /// Places items into the open bin with the lowest occupancy after insertion.
+    WorstFit,
+    /// Places items into the last open bin that can hold them.
+    LastFit,
+}
+
+/// A set of bins of the same size, which accepts items one at a time and opens new bins on demand.
+pub struct BinSet {
+    /// Bin packing algorithm of new bins.
+    bin_type: BinType,
+    /// Horizontal dimension of new bins.
+    bin_width: i32,
+    /// Vertical dimension of new bins.
+    bin_height: i32,
+    /// Policy for choosing the target bin of inserted items.
+    policy: SelectionPolicy,
+    /// List of managed bins.
+    bins: Vec<Box<dyn BinPacker>>,
+    /// Indicates for each bin whether it has been closed for insertions.
+    closed: Vec<bool>,
+}
+
+impl BinSet {
+    /// Creates an empty set of bins. New bins are created with the specified [`BinType`] and
+    /// size. Items are placed by the [`SelectionPolicy::FirstFit`] policy by default.
+    pub fn new(bin_type: BinType, width: i32, height: i32) -> Self {
+        Self {
+            bin_type,
+            bin_width: width.max(0),
+            bin_height: height.max(0),
+            policy: SelectionPolicy::FirstFit,
+            bins: Vec::new(),
+            closed: Vec::new(),
+        }
+    }
+
+    /// Returns the [`BinType`] of new bins.
+    pub fn bin_type(&self) -> BinType {
+        self.bin_type
+    }
+
+    /// Returns the width of new bins.
+    pub fn bin_width(&self) -> i32 {
+        self.bin_width
+    }
+
+    /// Returns the height of new bins.
+    pub fn bin_height(&self) -> i32 {
+        self.bin_height
+    }
+
+    /// Returns the policy for choosing the target bin of inserted items.
+    pub fn policy(&self) -> SelectionPolicy {
+        self.policy
+    }
+
+    /// Sets the policy for choosing the target bin of inserted items.
+    pub fn set_policy(&mut self, policy: SelectionPolicy) {
+        self.policy = policy;
+    }
+
+    /// Inserts a single [`Dimension`] object into one of the open bins, as determined by the
+    /// selection policy. A new bin is opened if none of the open bins can hold the object.
+    ///
+    /// Objects with a [`group`] key are always placed into the bin which already holds objects of
+    /// the same group.
+    ///
+    /// [`group`]: Dimension::group
+    ///
+    /// Returns the index of the target bin and a copy of the packed [`Rectangle`] as a
+    /// [`Result`] value.
+    ///
+    /// # Errors
+    ///
+    /// A [`BinError`] is returned if the object is either empty or does not fit into a new bin,
+    /// or if the bin holding the object's group cannot hold the object.
+    pub fn insert(&mut self, dim: &Dimension) -> Result<(usize, Rectangle), BinError> {
+        let (idx, rects) = self.insert_group(std::slice::from_ref(dim))?;
+        Ok((idx, rects[0]))
+    }
+
+    /// Inserts the given list of [`Dimension`] objects one after the other, as if [`insert`] was
+    /// called for each of them. Objects with the same [`group`] key are inserted together, so
+    /// that either all of them are placed into the same bin, or all of them are rejected.
+    ///
+    /// [`insert`]: BinSet::insert
+    /// [`group`]: Dimension::group
+    ///
+    /// Returns a tuple consisting of the list with all successfully inserted [`Rectangle`] objects,
+    /// paired with the index of their bin, and a list of rejected [`Dimension`] objects.
+    pub fn insert_list(
+        &mut self,
+        nodes: &[Dimension],
+    ) -> (Vec<(usize, Rectangle)>, Vec<Dimension>) {
+        let mut inserted = Vec::with_capacity(nodes.len());
+        let mut rejected = Vec::new();
+        for group in group_nodes(nodes) {
+            match self.insert_group(&group) {
+                Ok((idx, rects)) => inserted.extend(rects.into_iter().map(|rect| (idx, rect))),
+                Err(_) => rejected.extend(group),
+            }
+        }
+        (inserted, rejected)
+    }
+
+    /// Closes the bin at the specified index. Closed bins do not accept any more objects.
+    ///
+    /// Returns `true` if the bin was open, and `false` otherwise.
+    pub fn close(&mut self, index: usize) -> bool {
+        match self.closed.get_mut(index) {
+            Some(closed) if !*closed => {
+                *closed = true;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the bin at the specified index is closed or does not exist.
+    pub fn is_closed(&self, index: usize) -> bool {
+        self.closed.get(index).copied().unwrap_or(true)
+    }
+
+    /// Removes all bins from the set.
+    pub fn clear(&mut self) {
+        self.bins.clear();
+        self.closed.clear();
+    }
+
+    /// Extracts a slice containing the entire list of bins.
+    ///
+    /// Equivalent to `&set[..]`.
+    pub fn as_slice(&self) -> &[Box<dyn BinPacker>] {
+        &self.bins
+    }
+
+    /// Returns `true` if the set contains no bins.
+    pub fn is_empty(&self) -> bool {
+        self.bins.is_empty()
+    }
+
+    /// Returns the number of bins in this set.
+    pub fn len(&self) -> usize {
+        self.bins.len()
+    }
+
+    /// Returns an iterator over the list of bins.
+    pub fn iter(&self) -> Iter<'_, Box<dyn BinPacker>> {
+        self.bins.iter()
+    }
+
+    /// Returns the first mapped rectangle with the specified identifier in any of the bins,
+    /// paired with the index of the bin, if available. Returns `None` otherwise.
+    pub fn find_by_id(&self, id: isize) -> Option<(usize, Rectangle)> {
+        self.bins
+            .iter()
+            .enumerate()
+            .find_map(|(idx, bin)| bin.find_by_id(id).map(|rect| (idx, rect)))
+    }
+
+    /// Removes the first mapped rectangle with the specified identifier from any of the bins.
+    ///
+    /// Returns the removed [`Rectangle`], paired with the index of the bin, if available.
+    /// Returns `None` otherwise.
+    pub fn remove(&mut self, id: isize) -> Option<(usize, Rectangle)> {
+        self.bins
+            .iter_mut()
+            .enumerate()
+            .find_map(|(idx, bin)| bin.remove(id).map(|rect| (idx, rect)))
+    }
+
+    /// Inserts the given list of [`Dimension`] objects, which share the same group key, together
+    /// into a single bin.
+    fn insert_group(&mut self, nodes: &[Dimension]) -> Result<(usize, Vec<Rectangle>), BinError> {
+        for node in nodes {
+            if node.is_empty() {
+                return Err(BinError::ItemTooSmall);
+            } else if !node.fits_into(self.bin_width, self.bin_height, true) {
+                return Err(BinError::ItemTooBig);
+            }
+        }
+
+        // objects of the same group must be placed into the same bin
+        let key = nodes[0].group();
+        let group_bin = key.and_then(|key| {
+            self.bins
+                .iter()
+                .position(|bin| bin.iter().any(|r| r.dim().group() == Some(key)))
+        });
+        if let Some(idx) = group_bin {
+            if !self.closed[idx] {
+                if let Some(rects) = insert_group(self.bins[idx].as_mut(), nodes, false) {
+                    return Ok((idx, rects));
+                }
+            }
+            return Err(BinError::GroupTooBig);
+        }
+
+        let area = nodes.iter().map(|node| node.area()).sum();
+        for idx in self.candidates(area) {
+            if let Some(rects) = insert_group(self.bins[idx].as_mut(), nodes, false) {
+                return Ok((idx, rects));
+            }
+        }
+
+        let mut bin = bin_new(self.bin_type, self.bin_width, self.bin_height);
+        match insert_group(bin.as_mut(), nodes, false) {
+            Some(rects) => {
+                self.bins.push(bin);
+                self.closed.push(false);
+                Ok((self.bins.len() - 1, rects))
+            }
+            None if nodes.len() > 1 => Err(BinError::GroupTooBig),
+            None => Err(rejection_reason(bin.as_ref(), &nodes[0])),
+        }
+    }
+
+    /// Returns the indices of all open bins in the order they should be tried for objects of the
+    /// given total area, according to the selection policy.
+    fn candidates(&self, area: i64) -> Vec<usize> {
+        let mut result: Vec<usize> = (0..self.bins.len())
+            .filter(|&idx| !self.closed[idx])
+            .collect();
+
+        match self.policy {
+            SelectionPolicy::FirstFit => {}
+            SelectionPolicy::LastFit => result.reverse(),
+            SelectionPolicy::BestFit | SelectionPolicy::WorstFit => {
+                // occupancy after insertion does not depend on the placement of the object
+                let occupancy = |idx: usize| {
+                    let bin = &self.bins[idx];
+                    let bin_area = bin.width() as f32 * bin.height() as f32;
+                    bin.occupancy() + area as f32 / bin_area
+                };
+                if self.policy == SelectionPolicy::BestFit {
+                    result.sort_by(|&a, &b| occupancy(b).total_cmp(&occupancy(a)));
+                } else {
+                    result.sort_by(|&a, &b| occupancy(a).total_cmp(&occupancy(b)));
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl<Idx> Index<Idx> for BinSet
+where
+    Idx: SliceIndex<[Box<dyn BinPacker>]>,
+{
+    type Output = Idx::Output;
+
+    fn index(&self, index: Idx) -> &Self::Output {
+        &self.bins[index]
+    }
+}
+
+impl Display for BinSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "BinSet(width: {}, height: {}, bins: {})",
+            self.bin_width,
+            self.bin_height,
+            self.bins.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,118 @@
+use super::*;
+
+const BIN_TYPES: [BinType; 4] = [
+    BinType::MaxRects,
+    BinType::Guillotine,
+    BinType::Skyline,
+    BinType::Shelf,
+];
+
+/// Returns a set with two bins, the first one being more occupied than the second one.
+fn prepare_set(bin_type: BinType, policy: SelectionPolicy) -> BinSet {
+    let mut set = BinSet::new(bin_type, 16, 16);
+    set.set_policy(policy);
+    assert_eq!(0, set.insert(&Dimension::new(16, 12)).unwrap().0);
+    assert_eq!(1, set.insert(&Dimension::new(16, 8)).unwrap().0);
+    set
+}
+
+#[test]
+fn set_policy() {
+    let node = Dimension::new(4, 4);
+    for bin_type in BIN_TYPES {
+        let mut set = prepare_set(bin_type, SelectionPolicy::FirstFit);
+        assert_eq!(0, set.insert(&node).unwrap().0);
+
+        let mut set = prepare_set(bin_type, SelectionPolicy::LastFit);
+        assert_eq!(1, set.insert(&node).unwrap().0);
+
+        let mut set = prepare_set(bin_type, SelectionPolicy::BestFit);
+        assert_eq!(0, set.insert(&node).unwrap().0);
+
+        let mut set = prepare_set(bin_type, SelectionPolicy::WorstFit);
+        assert_eq!(1, set.insert(&node).unwrap().0);
+
+        // best fitting bin cannot hold the object
+        let mut set = prepare_set(bin_type, SelectionPolicy::BestFit);
+        assert_eq!(1, set.insert(&Dimension::new(16, 6)).unwrap().0);
+        assert_eq!(2, set.len());
+    }
+}
+
+#[test]
+fn set_close() {
+    for bin_type in BIN_TYPES {
+        let mut set = BinSet::new(bin_type, 16, 16);
+        assert!(set.is_closed(0));
+        assert_eq!(0, set.insert(&Dimension::new(8, 8)).unwrap().0);
+        assert!(!set.is_closed(0));
+
+        assert!(set.close(0));
+        assert!(!set.close(0));
+        assert!(set.is_closed(0));
+        assert_eq!(1, set.insert(&Dimension::new(8, 8)).unwrap().0);
+        assert_eq!(1, set[0].len());
+        assert_eq!(1, set[1].len());
+    }
+}
+
+#[test]
+fn set_find_remove() {
+    for bin_type in BIN_TYPES {
+        let mut set = BinSet::new(bin_type, 8, 8);
+        let nodes: Vec<_> = (1..=3).map(|id| Dimension::with_id(id, 8, 8, 0)).collect();
+        let (inserted, rejected) = set.insert_list(&nodes);
+        assert_eq!(3, inserted.len());
+        assert!(rejected.is_empty());
+        assert_eq!(3, set.len());
+
+        assert_eq!(Some(2), set.find_by_id(3).map(|(idx, _)| idx));
+        assert!(set.find_by_id(4).is_none());
+
+        assert_eq!(Some(1), set.remove(2).map(|(idx, _)| idx));
+        assert!(set.find_by_id(2).is_none());
+        assert_eq!(1, set.insert(&Dimension::with_id(4, 8, 8, 0)).unwrap().0);
+    }
+}
+
+#[test]
+fn set_groups() {
+    let grouped = |width: i32, height: i32, group: isize| {
+        let mut dim = Dimension::new(width, height);
+        dim.set_group(Some(group));
+        dim
+    };
+
+    for bin_type in BIN_TYPES {
+        let mut set = BinSet::new(bin_type, 16, 16);
+        set.insert(&Dimension::new(16, 8)).unwrap();
+
+        // group does not fit into the remaining space of the first bin
+        let nodes = vec![grouped(8, 8, 1), grouped(8, 8, 1), grouped(8, 8, 1)];
+        let (inserted, rejected) = set.insert_list(&nodes);
+        assert!(rejected.is_empty());
+        assert!(inserted.iter().all(|(idx, _)| *idx == 1));
+
+        // later members are placed into the bin of their group
+        assert_eq!(1, set.insert(&grouped(8, 8, 1)).unwrap().0);
+        let result = set.insert(&grouped(8, 8, 1));
+        assert_eq!(BinError::GroupTooBig, result.err().unwrap());
+        assert_eq!(2, set.len());
+
+        let nodes = vec![grouped(16, 8, 2), grouped(16, 16, 2)];
+        let (inserted, rejected) = set.insert_list(&nodes);
+        assert!(inserted.is_empty());
+        assert_eq!(2, rejected.len());
+    }
+}
+
+#[test]
+fn set_failure() {
+    let mut set = BinSet::new(BinType::MaxRects, 8, 8);
+    let result = set.insert(&Dimension::new(0, 4));
+    assert_eq!(BinError::ItemTooSmall, result.err().unwrap());
+
+    let result = set.insert(&Dimension::new(9, 4));
+    assert_eq!(BinError::ItemTooBig, result.err().unwrap());
+    assert!(set.is_empty());
+}
@@ -0,0 +1,368 @@
+//! Provides functions for packing items into bins of different sizes, chosen from a catalog of
+//! available stock sizes.
+//!
+//! # Quick Start
+//!
+//! This example demonstrates how to pack items into a combination of stock sheets, each sheet
+//! size having its own cost and an optional limited quantity.
+//!
+//! ```rust
+//! use binpack2d::{BinPacker, BinType, Dimension};
+//! use binpack2d::catalog::{pack_bins_catalog, BinSpec};
+//!
+//! // Create a number of items to be placed into the bins.
+//! let items_to_place = vec![
+//!     Dimension::new(40, 30),
+//!     Dimension::new(40, 30),
+//!     Dimension::new(20, 20),
+//!     Dimension::new(10, 60),
+//! ];
+//!
+//! // Available stock sizes: a cheap small sheet and a more expensive large sheet,
+//! // of which only one is in stock.
+//! let catalog = vec![
+//!     BinSpec::new(64, 64, 1.0),
+//!     BinSpec::with_quantity(128, 128, 3.0, 1),
+//! ];
+//!
+//! let bins = pack_bins_catalog(BinType::MaxRects, &items_to_place, &catalog)
+//!     .expect("Items should not be rejected");
+//!
+//! let total_cost: f64 = bins.iter().map(|(spec, _)| catalog[*spec].cost()).sum();
+//! for (spec, bin) in &bins {
+//!     println!("Bin {}x{} (catalog entry {spec}) holds {} items", bin.width(), bin.height(), bin.len());
+//! }
+//! println!("Total cost: {total_cost}");
+//! ```
+
+use super::{
+    bin_new, group_nodes, insert_group, insert_list_grouped, reject_group, rejection_reason,
+    BinError, BinPacker, BinType, PackError, Rejection,
+};
+use crate::dimension::Dimension;
+
+/// A packed bin, paired with the index of its [`BinSpec`] in the catalog.
+pub type CatalogBin<B = dyn BinPacker> = (usize, Box<B>);
+
+/// Describes a bin size available in a catalog of stock sizes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BinSpec {
+    /// Horizontal dimension of the bin.
+    width: i32,
+    /// Vertical dimension of the bin.
+    height: i32,
+    /// Cost of a single bin of this size.
+    cost: f64,
+    /// Number of available bins of this size. `None` indicates an unlimited quantity.
+    quantity: Option<u32>,
+}
+
+impl BinSpec {
+    /// Creates a new bin specification of the given size and cost, with an unlimited quantity.
+    pub fn new(width: i32, height: i32, cost: f64) -> Self {
+        Self {
+            width: width.max(0),
+            height: height.max(0),
+            cost: cost.max(0.0),
+            quantity: None,
+        }
+    }
+
+    /// Creates a new bin specification of the given size and cost, with a limited quantity.
+    pub fn with_quantity(width: i32, height: i32, cost: f64, quantity: u32) -> Self {
+        let mut result = Self::new(width, height, cost);
+        result.quantity = Some(quantity);
+        result
+    }
+
+    /// Returns the width of the bin.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Returns the height of the bin.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// Returns the cost of a single bin.
+    pub fn cost(&self) -> f64 {
+        self.cost
+    }
+
+    /// Returns the number of available bins, or `None` if the quantity is unlimited.
+    pub fn quantity(&self) -> Option<u32> {
+        self.quantity
+    }
+
+    /// Sets the width of the bin. Negative values are set to 0.
+    pub fn set_width(&mut self, value: i32) {
+        self.width = value.max(0);
+    }
+
+    /// Sets the height of the bin. Negative values are set to 0.
+    pub fn set_height(&mut self, value: i32) {
+        self.height = value.max(0);
+    }
+
+    /// Sets the cost of a single bin. Negative values are set to 0.
+    pub fn set_cost(&mut self, value: f64) {
+        self.cost = value.max(0.0);
+    }
+
+    /// Sets the number of available bins. Specify `None` for an unlimited quantity.
+    pub fn set_quantity(&mut self, value: Option<u32>) {
+        self.quantity = value;
+    }
+
+    /// Returns whether the bin has no space for any items.
+    fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+}
+
+/// A convenience function that packs a given list of `Dimension` objects into bins of the sizes
+/// defined by `catalog`, using the default heuristic rules for the given [`BinType`].
+///
+/// See [`pack_bins_catalog_with`] for a description of how bin sizes are selected.
+///
+/// Returns a list of bins with the packed rectangle nodes as a [`Result`] value. Each bin is
+/// paired with the index of its specification in `catalog`.
+///
+/// # Errors
+///
+/// A [`PackError`] is returned if any nodes could not be packed, listing each rejected node with
+/// the reason of rejection. Nodes are rejected if they are either empty or too big for all bin
+/// sizes, if they belong to a group of nodes which does not fit into a single bin of any size, or
+/// if all fitting bin sizes are out of stock. All other nodes are still packed into the bins
+/// provided by the error.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{BinPacker, BinType, Dimension};
+/// use binpack2d::catalog::{pack_bins_catalog, BinSpec};
+///
+/// let nodes = vec![Dimension::new(8, 8); 3];
+///
+/// // Large bins are cheaper per unit of area, but only one is available
+/// let catalog = vec![BinSpec::new(8, 8, 1.0), BinSpec::with_quantity(16, 8, 1.5, 1)];
+///
+/// let bins = pack_bins_catalog(BinType::MaxRects, &nodes, &catalog)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(2, bins.len());
+/// assert_eq!(1, bins[0].0);
+/// assert_eq!(0, bins[1].0);
+/// ```
+pub fn pack_bins_catalog(
+    bin_type: BinType,
+    nodes: &[Dimension],
+    catalog: &[BinSpec],
+) -> Result<Vec<CatalogBin>, PackError<CatalogBin>> {
+    pack_bins_catalog_with(nodes, catalog, |width, height| {
+        bin_new(bin_type, width, height)
+    })
+}
+
+/// Packs a given list of `Dimension` objects into bins of the sizes defined by `catalog`.
+///
+/// Bins are created on demand by the function `create`, which is called with the width and
+/// height of the requested bin. Nodes are packed by the bin's [`insert_list`] method, which
+/// allows to choose arbitrary bin types and heuristic rules.
+///
+/// [`insert_list`]: BinPacker::insert_list
+///
+/// Bin sizes are selected greedily: whenever a new bin is needed, the remaining nodes are packed
+/// into a bin of each size that is still in stock, and the bin with the lowest cost per packed
+/// area is kept. Ties are resolved by choosing the bin with the bigger packed area, and then
+/// the bin which comes first in `catalog`.
+///
+/// This is a heuristic, which does not guarantee the lowest total cost. A bin with the lowest
+/// cost per packed area may leave a few nodes for another expensive bin, where a single bigger
+/// bin would have been cheaper in total. Each new bin requires a trial packing of the remaining
+/// nodes for every bin size in stock.
+///
+/// Nodes with the same [`group`] key are always placed into the same bin.
+///
+/// [`group`]: Dimension::group
+///
+/// Returns a list of bins with the packed rectangle nodes as a [`Result`] value. Each bin is
+/// paired with the index of its specification in `catalog`.
+///
+/// # Errors
+///
+/// A [`PackError`] is returned if any nodes could not be packed, listing each rejected node with
+/// the reason of rejection. Nodes are rejected if they are either empty or too big for all bin
+/// sizes, if they belong to a group of nodes which does not fit into a single bin of any size, or
+/// if all fitting bin sizes are out of stock. All other nodes are still packed into the bins
+/// provided by the error.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{BinError, BinPacker, Dimension};
+/// use binpack2d::catalog::{pack_bins_catalog_with, BinSpec};
+/// use binpack2d::maxrects::{Heuristic, MaxRectsBin};
+///
+/// let nodes = vec![Dimension::new(8, 8); 3];
+///
+/// // Only two bins are in stock
+/// let catalog = vec![BinSpec::with_quantity(8, 8, 1.0, 2)];
+///
+/// let result = pack_bins_catalog_with(&nodes, &catalog, |width, height| {
+///     let mut bin = MaxRectsBin::new(width, height);
+///     bin.set_default_rule(Heuristic::BottomLeftRule);
+///     Box::new(bin)
+/// });
+///
+/// // Two of the nodes are packed, and the third node is rejected
+/// let err = result.err().unwrap();
+/// assert_eq!(2, err.bins().len());
+/// assert_eq!(nodes[2].id(), err.rejections()[0].id());
+/// assert_eq!(BinError::OutOfStock, err.rejections()[0].reason());
+/// ```
+pub fn pack_bins_catalog_with<B, F>(
+    nodes: &[Dimension],
+    catalog: &[BinSpec],
+    mut create: F,
+) -> Result<Vec<CatalogBin<B>>, PackError<CatalogBin<B>>>
+where
+    B: BinPacker + ?Sized,
+    F: FnMut(i32, i32) -> Box<B>,
+{
+    let mut bins = Vec::new();
+    if nodes.is_empty() {
+        return Ok(bins);
+    }
+
+    // empty bins of all catalog entries, to check which objects fit at all
+    let empty_bins: Vec<Option<Box<B>>> = catalog
+        .iter()
+        .map(|spec| (!spec.is_empty()).then(|| create(spec.width, spec.height)))
+        .collect();
+
+    let mut rejections = Vec::new();
+    let mut nodes_left = Vec::with_capacity(nodes.len());
+    for group in group_nodes(nodes) {
+        let reasons: Vec<Option<BinError>> = group
+            .iter()
+            .map(|node| {
+                let fits = empty_bins.iter().flatten().any(|bin| bin.can_fit(node));
+                (node.is_empty() || !fits).then(|| catalog_reason(node, &empty_bins))
+            })
+            .collect();
+
+        if reasons.iter().any(Option::is_some) {
+            reject_group(&mut rejections, &group, &reasons);
+            continue;
+        }
+
+        let fits = group.len() == 1
+            || catalog.iter().filter(|spec| !spec.is_empty()).any(|spec| {
+                let mut bin = create(spec.width, spec.height);
+                insert_group(bin.as_mut(), &group, true).is_some()
+            });
+        if fits {
+            nodes_left.extend(group);
+        } else {
+            reject_group(&mut rejections, &group, &[]);
+        }
+    }
+
+    let mut stock: Vec<Option<u32>> = catalog.iter().map(|spec| spec.quantity).collect();
+    while !nodes_left.is_empty() {
+        let mut best: Option<Candidate<B>> = None;
+        for (idx, spec) in catalog.iter().enumerate() {
+            if spec.is_empty() || stock[idx] == Some(0) {
+                continue;
+            }
+
+            let mut bin = create(spec.width, spec.height);
+            let (inserted, rejected) = insert_list_grouped(bin.as_mut(), &nodes_left);
+            if inserted.is_empty() {
+                continue;
+            }
+
+            let area: i64 = inserted.iter().map(|r| r.dim().area_total()).sum();
+            let ratio = spec.cost / area as f64;
+            let is_better = match &best {
+                Some(c) => ratio < c.ratio || (ratio == c.ratio && area > c.area),
+                None => true,
+            };
+            if is_better {
+                best = Some(Candidate {
+                    index: idx,
+                    ratio,
+                    area,
+                    bin,
+                    rejected,
+                });
+            }
+        }
+
+        match best {
+            Some(c) => {
+                if let Some(quantity) = &mut stock[c.index] {
+                    *quantity -= 1;
+                }
+                bins.push((c.index, c.bin));
+                nodes_left = c.rejected;
+            }
+            None => {
+                // none of the remaining nodes fits into a bin which is still in stock
+                rejections.extend(nodes_left.drain(..).map(|node| {
+                    let reason = if empty_bins.iter().enumerate().any(|(idx, bin)| {
+                        stock[idx] == Some(0) && bin.as_ref().is_some_and(|bin| bin.can_fit(&node))
+                    }) {
+                        BinError::OutOfStock
+                    } else if node.group().is_some() {
+                        BinError::GroupTooBig
+                    } else {
+                        catalog_reason(&node, &empty_bins)
+                    };
+                    Rejection::new(node.id(), reason)
+                }));
+            }
+        }
+    }
+
+    if rejections.is_empty() {
+        Ok(bins)
+    } else {
+        Err(PackError::new(rejections, bins))
+    }
+}
+
+/// Determines why the given `Dimension` object cannot be placed into any of the given empty
+/// bins, which are created for the catalog entries.
+fn catalog_reason<B>(node: &Dimension, empty_bins: &[Option<Box<B>>]) -> BinError
+where
+    B: BinPacker + ?Sized,
+{
+    let mut bins = empty_bins.iter().flatten();
+    if node.is_empty() {
+        BinError::ItemTooSmall
+    } else if bins.clone().any(|bin| bin.can_fit(node)) {
+        BinError::NoSpaceLeft
+    } else if bins.any(|bin| rejection_reason(bin.as_ref(), node) == BinError::RotationForbidden) {
+        BinError::RotationForbidden
+    } else {
+        BinError::ItemTooBig
+    }
+}
+
+/// A trial packing of the remaining nodes into a bin of a specific catalog entry.
+struct Candidate<B: BinPacker + ?Sized> {
+    /// Index of the bin specification in the catalog.
+    index: usize,
+    /// Cost of the bin per packed area.
+    ratio: f64,
+    /// Total area of the packed nodes.
+    area: i64,
+    /// The bin with the packed nodes.
+    bin: Box<B>,
+    /// Nodes which could not be packed into the bin.
+    rejected: Vec<Dimension>,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,182 @@
+use super::*;
+use crate::dimension::Rotation;
+
+const BIN_TYPES: [BinType; 4] = [
+    BinType::MaxRects,
+    BinType::Guillotine,
+    BinType::Skyline,
+    BinType::Shelf,
+];
+
+fn assert_layout(bin: &dyn BinPacker) {
+    for rect1 in bin.iter() {
+        assert!(rect1.x() >= 0 && rect1.x() + rect1.width() <= bin.width());
+        assert!(rect1.y() >= 0 && rect1.y() + rect1.height() <= bin.height());
+        for rect2 in bin.iter() {
+            if rect1 != rect2 {
+                assert!(!rect1.intersects(rect2));
+            }
+        }
+    }
+}
+
+#[test]
+fn catalog_lowest_cost() {
+    let nodes = vec![Dimension::new(8, 8); 4];
+
+    // a single 16x16 bin is cheaper than four 8x8 bins
+    let catalog = vec![BinSpec::new(8, 8, 1.0), BinSpec::new(16, 16, 3.0)];
+    for bin_type in BIN_TYPES {
+        let bins =
+            pack_bins_catalog(bin_type, &nodes, &catalog).expect("Items should not be rejected");
+        assert_eq!(1, bins.len());
+        assert_eq!(1, bins[0].0);
+        assert_eq!(nodes.len(), bins[0].1.len());
+        assert_layout(bins[0].1.as_ref());
+    }
+
+    // four 8x8 bins are cheaper than a single 16x16 bin
+    let catalog = vec![BinSpec::new(8, 8, 1.0), BinSpec::new(16, 16, 5.0)];
+    for bin_type in BIN_TYPES {
+        let bins =
+            pack_bins_catalog(bin_type, &nodes, &catalog).expect("Items should not be rejected");
+        assert_eq!(4, bins.len());
+        assert!(bins.iter().all(|(spec, bin)| *spec == 0 && bin.len() == 1));
+    }
+}
+
+#[test]
+fn catalog_greedy() {
+    let nodes = vec![Dimension::new(8, 8); 5];
+
+    // the bin with the lowest cost per packed area is chosen first, although a single 16x24 bin
+    // for all nodes would be cheaper than a 16x16 bin and an 8x8 bin
+    let catalog = vec![
+        BinSpec::new(8, 8, 1.0),
+        BinSpec::new(16, 16, 3.0),
+        BinSpec::new(16, 24, 3.9),
+    ];
+    for bin_type in BIN_TYPES {
+        let bins =
+            pack_bins_catalog(bin_type, &nodes, &catalog).expect("Items should not be rejected");
+        let specs: Vec<usize> = bins.iter().map(|(spec, _)| *spec).collect();
+        assert_eq!(vec![1, 0], specs);
+        let cost: f64 = specs.iter().map(|&spec| catalog[spec].cost()).sum();
+        assert!(cost > catalog[2].cost());
+    }
+}
+
+#[test]
+fn catalog_quantity() {
+    let nodes = vec![Dimension::new(8, 8); 6];
+
+    let catalog = vec![
+        BinSpec::new(8, 8, 1.0),
+        BinSpec::with_quantity(16, 16, 2.0, 1),
+    ];
+    for bin_type in BIN_TYPES {
+        let bins =
+            pack_bins_catalog(bin_type, &nodes, &catalog).expect("Items should not be rejected");
+        assert_eq!(3, bins.len());
+        assert_eq!(1, bins[0].0);
+        assert_eq!(4, bins[0].1.len());
+        assert_eq!(nodes.len(), bins.iter().map(|(_, bin)| bin.len()).sum());
+    }
+
+    // nodes which do not fit into the only bin in stock are rejected
+    let catalog = vec![BinSpec::with_quantity(16, 16, 2.0, 1)];
+    for bin_type in BIN_TYPES {
+        let err = pack_bins_catalog(bin_type, &nodes, &catalog).err().unwrap();
+        assert_eq!(2, err.rejections().len());
+        assert!(err
+            .rejections()
+            .iter()
+            .all(|r| r.reason() == BinError::OutOfStock));
+        assert_eq!(1, err.bins().len());
+        assert_eq!(4, err.bins()[0].1.len());
+    }
+}
+
+#[test]
+fn catalog_failure() {
+    let catalog = vec![
+        BinSpec::new(8, 8, 1.0),
+        BinSpec::with_quantity(16, 4, 1.0, 0),
+        BinSpec::new(0, 32, 0.0),
+    ];
+
+    let result = pack_bins_catalog(BinType::MaxRects, &[Dimension::new(0, 4)], &catalog);
+    assert_eq!(
+        BinError::ItemTooSmall,
+        result.err().unwrap().rejections()[0].reason()
+    );
+
+    let result = pack_bins_catalog(BinType::MaxRects, &[Dimension::new(4, 20)], &catalog);
+    assert_eq!(
+        BinError::ItemTooBig,
+        result.err().unwrap().rejections()[0].reason()
+    );
+
+    let result = pack_bins_catalog(BinType::MaxRects, &[Dimension::new(12, 4)], &catalog);
+    assert_eq!(
+        BinError::OutOfStock,
+        result.err().unwrap().rejections()[0].reason()
+    );
+
+    let result = pack_bins_catalog(BinType::MaxRects, &[], &catalog);
+    assert!(result.unwrap().is_empty());
+
+    // reasons consider the border of the bins
+    let catalog = vec![BinSpec::new(16, 24, 1.0)];
+    let mut nodes = [
+        Dimension::with_id(1, 14, 14, 0),
+        Dimension::with_id(2, 14, 8, 0),
+        Dimension::with_id(3, 8, 14, 0),
+    ];
+    nodes[1].set_rotation(Rotation::Forbidden);
+    for bin_type in BIN_TYPES {
+        let result = pack_bins_catalog_with(&nodes, &catalog, |width, height| {
+            let mut bin = bin_new(bin_type, width, height);
+            bin.set_border(2);
+            bin
+        });
+        let err = result.err().unwrap();
+        let expected = vec![
+            Rejection::new(1, BinError::ItemTooBig),
+            Rejection::new(2, BinError::RotationForbidden),
+        ];
+        assert_eq!(expected, err.rejections());
+        assert_eq!(1, err.bins().len());
+    }
+}
+
+#[test]
+fn catalog_groups() {
+    let mut nodes = vec![Dimension::new(8, 8); 3];
+    for _ in 0..3 {
+        let mut node = Dimension::new(8, 8);
+        node.set_group(Some(1));
+        nodes.push(node);
+    }
+
+    // two small bins are cheaper than a large bin, but cannot hold the whole group
+    let catalog = vec![BinSpec::new(16, 8, 1.0), BinSpec::new(16, 16, 2.5)];
+    for bin_type in BIN_TYPES {
+        let bins =
+            pack_bins_catalog(bin_type, &nodes, &catalog).expect("Items should not be rejected");
+        let group_bins: Vec<_> = bins
+            .iter()
+            .filter(|(_, bin)| bin.iter().any(|r| r.dim().group() == Some(1)))
+            .collect();
+        assert_eq!(1, group_bins.len());
+        assert_eq!(1, group_bins[0].0);
+        assert_eq!(nodes.len(), bins.iter().map(|(_, bin)| bin.len()).sum());
+    }
+
+    let catalog = vec![BinSpec::new(16, 8, 1.0)];
+    let result = pack_bins_catalog(BinType::MaxRects, &nodes, &catalog);
+    assert_eq!(
+        BinError::GroupTooBig,
+        result.err().unwrap().rejections()[0].reason()
+    );
+}
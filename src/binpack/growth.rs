@@ -0,0 +1,159 @@
+//! Provides policies for enlarging bins automatically if objects do not fit into them.
+//!
+//! # Quick Start
+//!
+//! ```rust
+//! use binpack2d::{bin_new, BinPacker, BinType, Dimension};
+//! use binpack2d::growth::{GrowthPolicy, GrowthStrategy};
+//!
+//! // Create a small bin which grows up to a size of 1024x1024 on demand.
+//! let mut bin = bin_new(BinType::MaxRects, 64, 64);
+//! bin.set_growth_policy(Some(GrowthPolicy::new(GrowthStrategy::NextPowerOfTwo, 1024, 1024)));
+//!
+//! // The bin is enlarged instead of rejecting the item.
+//! assert!(bin.insert(&Dimension::new(100, 50)).is_some());
+//! assert_eq!(128, bin.width());
+//! assert_eq!(64, bin.height());
+//! ```
+
+use super::{packing_area, BinPacker};
+use crate::coord::{self, Coord};
+use crate::dimension::Dimension;
+
+/// List of supported strategies for enlarging a bin.
+///
+/// Only the shorter side of the bin is enlarged by each growth step, which keeps the bin roughly
+/// square. The longer side is enlarged instead if the shorter side has already reached the
+/// maximum size.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GrowthStrategy {
+    /// Doubles the shorter side of the bin.
+    DoubleShorterSide,
+    /// Enlarges the shorter side of the bin to the next higher power of two.
+    NextPowerOfTwo,
+    /// Enlarges the shorter side of the bin by the given amount.
+    FixedStep(u32),
+}
+
+/// Defines how a bin is enlarged if objects do not fit into it, and up to which size.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GrowthPolicy<C: Coord = i32> {
+    /// The strategy for enlarging the bin.
+    strategy: GrowthStrategy,
+    /// Maximum horizontal dimension of the bin.
+    max_width: C,
+    /// Maximum vertical dimension of the bin.
+    max_height: C,
+}
+
+impl<C: Coord> GrowthPolicy<C> {
+    /// Creates a new growth policy with the given strategy and maximum bin size.
+    ///
+    /// Minimum width and height of the maximum bin size is 1.
+    pub fn new(strategy: GrowthStrategy, max_width: C, max_height: C) -> Self {
+        Self {
+            strategy,
+            max_width: coord::max(max_width, C::ONE),
+            max_height: coord::max(max_height, C::ONE),
+        }
+    }
+
+    /// Returns the strategy for enlarging the bin.
+    pub fn strategy(&self) -> GrowthStrategy {
+        self.strategy
+    }
+
+    /// Returns the maximum width of the bin.
+    pub fn max_width(&self) -> C {
+        self.max_width
+    }
+
+    /// Returns the maximum height of the bin.
+    pub fn max_height(&self) -> C {
+        self.max_height
+    }
+
+    /// Computes the next growth step for a bin of the given size.
+    ///
+    /// Returns the horizontal and vertical growth as a tuple, or `None` if the bin has already
+    /// reached the maximum size.
+    pub(crate) fn next_growth(&self, width: C, height: C) -> Option<(u32, u32)> {
+        let grow_side = |side: C, max: C| {
+            let new_side = match self.strategy {
+                GrowthStrategy::DoubleShorterSide => side.saturating_add(side),
+                GrowthStrategy::NextPowerOfTwo => next_power_of_two(side),
+                GrowthStrategy::FixedStep(step) => side.saturating_add(C::from_u32(step)),
+            };
+            let new_side = coord::min(new_side, max);
+            if new_side > side {
+                Some((new_side - side).to_u32())
+            } else {
+                None
+            }
+        };
+
+        let grow_width = || grow_side(width, self.max_width).map(|dw| (dw, 0));
+        let grow_height = || grow_side(height, self.max_height).map(|dh| (0, dh));
+        if height < width {
+            grow_height().or_else(grow_width)
+        } else {
+            grow_width().or_else(grow_height)
+        }
+    }
+}
+
+/// Returns the smallest power of two which is greater than `side`, saturating at the greatest
+/// value of the coordinate type.
+fn next_power_of_two<C: Coord>(side: C) -> C {
+    let mut result = C::ONE;
+    while result <= side {
+        result = match result.checked_add(result) {
+            Some(value) => value,
+            None => return C::MAX,
+        };
+    }
+    result
+}
+
+/// Enlarges the given bin by a single step of the growth policy, as long as at least one of the
+/// given `Dimension` objects would fit into a bin of the maximum size.
+///
+/// Returns whether the bin was enlarged.
+pub(crate) fn grow_bin<C, B>(
+    bin: &mut B,
+    policy: Option<GrowthPolicy<C>>,
+    nodes: &[Dimension<C>],
+    rotate: bool,
+) -> bool
+where
+    C: Coord,
+    B: BinPacker<C> + ?Sized,
+{
+    let policy = match policy {
+        Some(policy) => policy,
+        None => return false,
+    };
+
+    let area = packing_area(
+        policy.max_width,
+        policy.max_height,
+        bin.border(),
+        bin.spacing(),
+    );
+    let fits = nodes
+        .iter()
+        .any(|dim| !dim.is_empty() && dim.fits_into(area.width(), area.height(), rotate));
+    if !fits {
+        return false;
+    }
+
+    if let Some((dw, dh)) = policy.next_growth(bin.width(), bin.height()) {
+        bin.grow(dw, dh);
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests;
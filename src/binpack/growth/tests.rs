@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn growth_double_shorter_side() {
+    let policy = GrowthPolicy::new(GrowthStrategy::DoubleShorterSide, 32, 24);
+    assert_eq!(Some((8, 0)), policy.next_growth(8, 8));
+    assert_eq!(Some((0, 8)), policy.next_growth(16, 8));
+    assert_eq!(Some((16, 0)), policy.next_growth(16, 16));
+    assert_eq!(Some((16, 0)), policy.next_growth(16, 24));
+    assert_eq!(None, policy.next_growth(32, 24));
+}
+
+#[test]
+fn growth_next_power_of_two() {
+    let policy = GrowthPolicy::new(GrowthStrategy::NextPowerOfTwo, 64, 64);
+    assert_eq!(Some((3, 0)), policy.next_growth(5, 5));
+    assert_eq!(Some((0, 3)), policy.next_growth(8, 5));
+    assert_eq!(Some((8, 0)), policy.next_growth(8, 8));
+    assert_eq!(Some((0, 32)), policy.next_growth(64, 32));
+    assert_eq!(None, policy.next_growth(64, 64));
+}
+
+#[test]
+fn growth_fixed_step() {
+    let policy = GrowthPolicy::new(GrowthStrategy::FixedStep(10), 25, 100);
+    assert_eq!(Some((10, 0)), policy.next_growth(10, 10));
+    assert_eq!(Some((5, 0)), policy.next_growth(20, 30));
+    assert_eq!(Some((0, 10)), policy.next_growth(25, 20));
+    assert_eq!(None, policy.next_growth(25, 100));
+}
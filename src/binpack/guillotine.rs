@@ -76,13 +76,20 @@
 //! println!("Occupancy of the bin: {:.1} %", bin.occupancy() * 100.0);
 //! ```
 
-use crate::binpack::BinError;
+use crate::binpack::{BinError, PackError};
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
-use std::mem;
+use std::slice;
 use std::slice::Iter;
 
-use super::{visualize_bin, BinPacker};
-use crate::dimension::Dimension;
+use super::growth::{grow_bin, GrowthPolicy};
+use super::index::IndexedRects;
+use super::journal::TrackedVec;
+use super::sizing::pack_strip_with;
+use super::validate::{self, Violation};
+use super::{align_placement, pack_bins_with, packing_area, rect_at, visualize_bin, BinPacker};
+use crate::coord::{self, Coord};
+use crate::dimension::{Alignment, Dimension, Rotation};
 use crate::rectangle::Rectangle;
 
 /// List of supported heuristic rules for *GUILLOTINE* data structures that can be used when deciding
@@ -120,16 +127,31 @@ pub enum SplitHeuristic {
 ///
 /// It can be used to pack multiple rectangles of arbitrary size into a "bin" of rectangular shape
 /// with the goal to add as many rectangles as possible into the bin.
+///
+/// Coordinates are of the [`Coord`] type `C`, which is `i32` by default.
 #[derive(Clone, Debug, PartialEq)]
-pub struct GuillotineBin {
+pub struct GuillotineBin<C: Coord = i32> {
     /// Horizontal dimension of the bin.
-    bin_width: i32,
+    bin_width: C,
     /// Vertical dimension of the bin.
-    bin_height: i32,
+    bin_height: C,
     /// Keeps track of used areas within the bin.
-    rects_used: Vec<Rectangle>,
+    rects_used: IndexedRects<C>,
     /// Keeps track of free areas within the bin.
-    rects_free: Vec<Rectangle>,
+    rects_free: TrackedVec<Rectangle<C>>,
+    /// Keeps track of areas within the bin which are not available for packing.
+    obstacles: TrackedVec<Rectangle<C>>,
+    /// Margin along the bin boundaries.
+    border: C,
+    /// Gap between neighbouring rectangles.
+    spacing: C,
+    /// Optional alignment of rectangle positions.
+    alignment: Option<Alignment<C>>,
+    /// Bin size at the time of each active checkpoint.
+    checkpoints: Vec<(C, C)>,
+
+    /// Optionally enlarges the bin if objects do not fit.
+    growth_policy: Option<GrowthPolicy<C>>,
 
     /// Implicitly used for the methods defined by the `BinPacker` trait.
     default_rect_choice: RectHeuristic,
@@ -139,12 +161,12 @@ pub struct GuillotineBin {
     default_merge: bool,
 }
 
-impl BinPacker for GuillotineBin {
-    fn width(&self) -> i32 {
+impl<C: Coord> BinPacker<C> for GuillotineBin<C> {
+    fn width(&self) -> C {
         self.bin_width
     }
 
-    fn height(&self) -> i32 {
+    fn height(&self) -> C {
         self.bin_height
     }
 
@@ -153,30 +175,81 @@ impl BinPacker for GuillotineBin {
         self.rects_used.shrink_to(capacity.max(4));
         self.rects_free.clear();
         self.rects_free.shrink_to((capacity * 4).max(16));
-        self.rects_free.push(Rectangle::new(
-            0,
-            0,
-            Dimension::with_id(0, self.bin_width, self.bin_height, 0),
-        ));
+        let area = self.area();
+        if !area.is_empty() {
+            self.rects_free.push(area);
+        }
+
+        // obstacles are preserved
+        for i in 0..self.obstacles.len() {
+            let rect = self.obstacles[i];
+            self.carve_free_list(&rect);
+        }
     }
 
     fn grow(&mut self, dw: u32, dh: u32) {
         if dw > 0 {
-            self.rects_free.push(Rectangle::new(
-                self.bin_width,
-                0,
-                Dimension::with_id(0, dw as i32, self.bin_height, 0)
-            ));
-            self.bin_width += dw as i32;
+            let old_right = self.area().right_total();
+            self.bin_width = self.bin_width.saturating_add(C::from_u32(dw));
+            let area = self.area();
+            let dw = area.right_total() - old_right;
+
+            // free rectangles at the right border are extended into the new area
+            let mut covered = Vec::new();
+            for rect in self.rects_free.as_mut_vec() {
+                if coord::approx_eq(rect.right_total(), old_right) {
+                    let width = rect.width() + (area.right_total() - rect.right_total());
+                    rect.dim_mut().set_width(width);
+                    covered.push((rect.y_total(), rect.bottom_total()));
+                }
+            }
+
+            // remaining parts of the new area are added as separate free rectangles
+            covered.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let mut y = area.y_total();
+            let end = (area.bottom_total(), C::ZERO);
+            for (top, bottom) in covered.into_iter().chain([end]) {
+                if coord::approx_lt(y, top) && dw > C::ZERO {
+                    self.rects_free.push(Rectangle::new(
+                        old_right,
+                        y,
+                        Dimension::with_id(0, dw, top - y, C::ZERO),
+                    ));
+                }
+                y = coord::max(y, bottom);
+            }
         }
 
         if dh > 0 {
-            self.rects_free.push(Rectangle::new(
-                0,
-                self.bin_height,
-                Dimension::with_id(0, self.bin_width, dh as i32, 0)
-            ));
-            self.bin_height += dh as i32;
+            let old_bottom = self.area().bottom_total();
+            self.bin_height = self.bin_height.saturating_add(C::from_u32(dh));
+            let area = self.area();
+            let dh = area.bottom_total() - old_bottom;
+
+            // free rectangles at the bottom border are extended into the new area
+            let mut covered = Vec::new();
+            for rect in self.rects_free.as_mut_vec() {
+                if coord::approx_eq(rect.bottom_total(), old_bottom) {
+                    let height = rect.height() + (area.bottom_total() - rect.bottom_total());
+                    rect.dim_mut().set_height(height);
+                    covered.push((rect.x_total(), rect.right_total()));
+                }
+            }
+
+            // remaining parts of the new area are added as separate free rectangles
+            covered.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+            let mut x = area.x_total();
+            let end = (area.right_total(), C::ZERO);
+            for (left, right) in covered.into_iter().chain([end]) {
+                if coord::approx_lt(x, left) && dh > C::ZERO {
+                    self.rects_free.push(Rectangle::new(
+                        x,
+                        old_bottom,
+                        Dimension::with_id(0, left - x, dh, C::ZERO),
+                    ));
+                }
+                x = coord::max(x, right);
+            }
         }
     }
 
@@ -185,56 +258,112 @@ impl BinPacker for GuillotineBin {
             return;
         }
 
-        let mut min_x = i32::MAX;
-        let mut min_y = i32::MAX;
-        let mut max_x = i32::MIN;
-        let mut max_y = i32::MIN;
-
-        // finding borders
-        for rect in &self.rects_used {
-            min_x = min_x.min(rect.x_total());
-            min_y = min_y.min(rect.y_total());
-            max_x = max_x.max(rect.x_total() + rect.width_total());
-            max_y = max_y.max(rect.y_total() + rect.height_total());
+        let mut min_x = C::MAX;
+        let mut min_y = C::MAX;
+        let mut max_x = C::MIN;
+        let mut max_y = C::MIN;
+
+        // finding borders, the spacing of rectangles is not needed at the bin boundary
+        for rect in self.rects_used.iter().chain(&self.obstacles) {
+            min_x = coord::min(min_x, rect.x_total());
+            min_y = coord::min(min_y, rect.y_total());
+            max_x = coord::max(max_x, rect.right_total() - rect.dim().spacing);
+            max_y = coord::max(max_y, rect.bottom_total() - rect.dim().spacing);
         }
 
-        let mut new_width = max_x - min_x;
-        let mut new_height = max_y - min_y;
+        // obstacles may be located within the bin border
+        let shift_x = coord::max(min_x.saturating_sub(self.border), C::ZERO);
+        let shift_y = coord::max(min_y.saturating_sub(self.border), C::ZERO);
+        let mut new_width = coord::min(
+            (max_x - shift_x).saturating_add(self.border),
+            self.bin_width,
+        );
+        let mut new_height = coord::min(
+            (max_y - shift_y).saturating_add(self.border),
+            self.bin_height,
+        );
 
         if binary {
             // attempt to shrink to the next lower power of two
+            let two = C::ONE + C::ONE;
             let mut cur_width = self.bin_width;
-            while new_width <= (cur_width >> 1) {
-                cur_width >>= 1;
+            while new_width <= cur_width / two {
+                cur_width = cur_width / two;
             }
             new_width = cur_width;
 
             let mut cur_height = self.bin_height;
-            while new_height <= (cur_height >> 1) {
-                cur_height >>= 1;
+            while new_height <= cur_height / two {
+                cur_height = cur_height / two;
             }
             new_height = cur_height;
         }
 
         // adjusting rectangle positions
         if new_width != self.bin_width || new_height != self.bin_height {
-            if min_x > 0 || min_y > 0 {
-                for rect in &mut self.rects_used {
-                    rect.set_x_total(rect.x_total() - min_x);
-                    rect.set_y_total(rect.y_total() - min_y);
+            if shift_x > C::ZERO || shift_y > C::ZERO {
+                self.rects_used.update_all(|rect| {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
+                });
+                for rect in self.obstacles.as_mut_vec() {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
                 }
-                for rect in &mut self.rects_free {
-                    rect.set_x_total(rect.x_total() - min_x);
-                    rect.set_y_total(rect.y_total() - min_y);
+                for rect in self.rects_free.as_mut_vec() {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
                 }
             }
 
             self.bin_width = new_width;
             self.bin_height = new_height;
+
+            // free areas outside of the new packing area are no longer available
+            let area = self.area();
+            self.rects_free.as_mut_vec().retain_mut(|rect| {
+                let left = coord::max(rect.x_total(), area.x_total());
+                let top = coord::max(rect.y_total(), area.y_total());
+                let right = coord::min(rect.right_total(), area.right_total());
+                let bottom = coord::min(rect.bottom_total(), area.bottom_total());
+                if right > left && bottom > top {
+                    rect.set_location_total(left, top);
+                    rect.dim_mut().set_dimension(right - left, bottom - top);
+                    true
+                } else {
+                    false
+                }
+            });
         }
     }
 
-    fn insert(&mut self, dim: &Dimension) -> Option<Rectangle> {
+    fn border(&self) -> C {
+        self.border
+    }
+
+    fn set_border(&mut self, border: C) {
+        self.border = coord::max(border, C::ZERO);
+        self.clear();
+    }
+
+    fn spacing(&self) -> C {
+        self.spacing
+    }
+
+    fn set_spacing(&mut self, spacing: C) {
+        self.spacing = coord::max(spacing, C::ZERO);
+        self.clear();
+    }
+
+    fn growth_policy(&self) -> Option<GrowthPolicy<C>> {
+        self.growth_policy
+    }
+
+    fn set_growth_policy(&mut self, policy: Option<GrowthPolicy<C>>) {
+        self.growth_policy = policy;
+    }
+
+    fn insert(&mut self, dim: &Dimension<C>) -> Option<Rectangle<C>> {
         self.insert(
             dim,
             self.default_merge,
@@ -243,7 +372,7 @@ impl BinPacker for GuillotineBin {
         )
     }
 
-    fn insert_list(&mut self, nodes: &[Dimension]) -> (Vec<Rectangle>, Vec<Dimension>) {
+    fn insert_list(&mut self, nodes: &[Dimension<C>]) -> (Vec<Rectangle<C>>, Vec<Dimension<C>>) {
         self.insert_list(
             nodes,
             self.default_merge,
@@ -252,17 +381,57 @@ impl BinPacker for GuillotineBin {
         )
     }
 
+    fn insert_at(&mut self, x: C, y: C, dim: &Dimension<C>) -> Option<Rectangle<C>> {
+        let alignment = dim.alignment().or(self.alignment);
+        let rect = rect_at(x, y, &dim.to_spaced(self.spacing), &self.area(), alignment)?;
+        if !self.is_free_area(&rect) {
+            return None;
+        }
+
+        self.carve_free_list(&rect);
+        self.rects_used.push(rect);
+        Some(rect)
+    }
+
+    fn preview_insert(&self, dim: &Dimension<C>) -> Option<Rectangle<C>> {
+        let dim = &dim.to_spaced(self.spacing);
+        if dim.is_empty() || !self.fits_area(dim) {
+            return None;
+        }
+
+        let (_, result) = self.find_position_for_new_node(dim, self.default_rect_choice);
+        result
+    }
+
+    fn count_fit(&self, dim: &Dimension<C>) -> usize {
+        let dim = &dim.to_spaced(self.spacing);
+        let mut bin = self.scratch_bin();
+        let mut count = 0;
+        while bin
+            .try_insert(
+                dim,
+                self.default_merge,
+                self.default_rect_choice,
+                self.default_split_method,
+            )
+            .is_some()
+        {
+            count += 1;
+        }
+        count
+    }
+
     fn occupancy(&self) -> f32 {
-        if self.bin_width == 0 || self.bin_height == 0 {
+        if self.bin_width == C::ZERO || self.bin_height == C::ZERO {
             return 0.0;
         }
 
-        let area: i64 = self.rects_used.iter().map(|r| r.dim().area()).sum();
+        let area: C::Wide = self.rects_used.iter().map(|r| r.dim().area()).sum();
 
-        area as f32 / (self.bin_width * self.bin_height) as f32
+        (C::wide_to_f64(area) / C::wide_to_f64(self.bin_width.mul_wide(self.bin_height))) as f32
     }
 
-    fn as_slice(&self) -> &[Rectangle] {
+    fn as_slice(&self) -> &[Rectangle<C>] {
         &self.rects_used
     }
 
@@ -274,15 +443,71 @@ impl BinPacker for GuillotineBin {
         self.rects_used.len()
     }
 
-    fn iter(&self) -> Iter<'_, Rectangle> {
+    fn iter(&self) -> Iter<'_, Rectangle<C>> {
         self.rects_used.iter()
     }
 
-    fn find_by_id(&self, id: isize) -> Option<Rectangle> {
-        self.rects_used
-            .iter()
-            .find(|&n| n.dim().id() == id)
-            .map(|r| r.to_owned())
+    fn find_by_id(&self, id: isize) -> Option<Rectangle<C>> {
+        self.rects_used.find_by_id(id).copied()
+    }
+
+    fn remove_rect(&mut self, rect: &Rectangle<C>) -> bool {
+        if let Some(index) = self.rects_used.position(rect) {
+            let rect = self.rects_used.remove(index);
+            self.reclaim_rect(&rect);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        self.checkpoints.push((self.bin_width, self.bin_height));
+        self.rects_used.checkpoint();
+        self.rects_free.checkpoint();
+        self.obstacles.checkpoint();
+    }
+
+    fn rollback(&mut self) -> bool {
+        if let Some((width, height)) = self.checkpoints.pop() {
+            self.bin_width = width;
+            self.bin_height = height;
+            self.rects_used.rollback();
+            self.rects_free.rollback();
+            self.obstacles.rollback();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn commit(&mut self) -> bool {
+        if self.checkpoints.pop().is_some() {
+            self.rects_used.commit();
+            self.rects_free.commit();
+            self.obstacles.commit();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn validate(&self) -> Vec<Violation<C>> {
+        let mut violations = validate::check_layout(
+            self.bin_width,
+            self.bin_height,
+            self.border,
+            &self.rects_used,
+            &self.obstacles,
+        );
+        violations.extend(validate::check_free_bounds(
+            &self.area(),
+            &self.rects_free,
+            &self.rects_used,
+            &self.obstacles,
+        ));
+        violations.extend(validate::check_free_disjoint(&self.rects_free));
+        violations
     }
 
     fn visualize(&self) -> String {
@@ -294,11 +519,11 @@ impl BinPacker for GuillotineBin {
     }
 }
 
-impl GuillotineBin {
+impl<C: Coord> GuillotineBin<C> {
     /// Creates an empty bin of the given size.
     ///
     /// Minimum width and height of a bin is 1.
-    pub fn new(width: i32, height: i32) -> Self {
+    pub fn new(width: C, height: C) -> Self {
         Self::with_capacity(width, height, 4)
     }
 
@@ -306,21 +531,23 @@ impl GuillotineBin {
     /// of mapped rectangle to improve performance.
     ///
     /// Minimum width and height of a bin is 1.
-    pub fn with_capacity(width: i32, height: i32, capacity: usize) -> Self {
+    pub fn with_capacity(width: C, height: C, capacity: usize) -> Self {
         let mut result = Self {
-            bin_width: width.max(1),
-            bin_height: height.max(1),
-            rects_used: Vec::with_capacity(capacity.max(4)),
-            rects_free: Vec::with_capacity((capacity * 4).max(4 * 4)),
+            bin_width: coord::max(width, C::ONE),
+            bin_height: coord::max(height, C::ONE),
+            rects_used: IndexedRects::with_capacity(capacity.max(4)),
+            rects_free: TrackedVec::with_capacity((capacity * 4).max(4 * 4)),
+            obstacles: TrackedVec::new(),
+            border: C::ZERO,
+            spacing: C::ZERO,
+            alignment: None,
+            checkpoints: Vec::new(),
             default_rect_choice: RectHeuristic::BestShortSideFit,
             default_split_method: SplitHeuristic::ShorterLeftoverAxis,
             default_merge: true,
+            growth_policy: None,
         };
-        result.rects_free.push(Rectangle::new(
-            0,
-            0,
-            Dimension::with_id(0, result.bin_width, result.bin_height, 0),
-        ));
+        result.rects_free.push(result.area());
 
         result
     }
@@ -379,6 +606,92 @@ impl GuillotineBin {
         self.default_merge = merge;
     }
 
+    /// Returns the [`Alignment`] of rectangle positions, if available.
+    pub fn alignment(&self) -> Option<Alignment<C>> {
+        self.alignment
+    }
+
+    /// Specifies the [`Alignment`] of rectangle positions. The alignment of individual objects
+    /// takes precedence over this setting. Specify `None` to place objects at arbitrary
+    /// positions. Positions are not aligned by default.
+    pub fn set_alignment(&mut self, alignment: Option<Alignment<C>>) {
+        self.alignment = alignment;
+    }
+
+    /// Returns the list of obstacles, which mark areas of the bin that are not available for
+    /// packing.
+    pub fn obstacles(&self) -> &[Rectangle<C>] {
+        &self.obstacles
+    }
+
+    /// Marks the area of the given rectangle as not available for packing.
+    ///
+    /// Obstacles are not considered mapped rectangles and are therefore not included in the
+    /// results of [`len`], [`as_slice`] or [`occupancy`]. They are preserved when the bin is
+    /// cleared.
+    ///
+    /// [`len`]: BinPacker::len
+    /// [`as_slice`]: BinPacker::as_slice
+    /// [`occupancy`]: BinPacker::occupancy
+    ///
+    /// Returns `true` if the obstacle was added, and `false` if it is empty, exceeds the
+    /// bin boundaries or intersects mapped rectangles.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{BinPacker, Dimension, Rectangle};
+    /// use binpack2d::guillotine::{GuillotineBin, RectHeuristic, SplitHeuristic};
+    ///
+    /// let mut bin = GuillotineBin::new(16, 16);
+    /// assert!(bin.add_obstacle(&Rectangle::new(0, 0, Dimension::new(16, 4))));
+    ///
+    /// // The top area of the bin is not available
+    /// let choice = RectHeuristic::BestAreaFit;
+    /// let method = SplitHeuristic::ShorterLeftoverAxis;
+    /// assert!(bin.insert(&Dimension::new(16, 16), true, choice, method).is_none());
+    /// let rect = bin.insert(&Dimension::new(16, 12), true, choice, method);
+    /// assert_eq!(Some(4), rect.map(|r| r.y()));
+    /// assert_eq!(1, bin.len());
+    /// ```
+    pub fn add_obstacle(&mut self, rect: &Rectangle<C>) -> bool {
+        if rect.is_empty()
+            || !rect.is_total_valid()
+            || rect.x_total() < C::ZERO
+            || rect.y_total() < C::ZERO
+            || rect.right_total() > self.bin_width
+            || rect.bottom_total() > self.bin_height
+            || self.rects_used.iter().any(|r| r.intersects_total(rect))
+        {
+            return false;
+        }
+
+        self.carve_free_list(rect);
+        self.obstacles.push(rect.to_owned());
+        true
+    }
+
+    /// Removes the specified obstacle from the bin and makes its area available for packing.
+    ///
+    /// Returns `true` if the obstacle was found and removed, and `false` otherwise.
+    pub fn remove_obstacle(&mut self, rect: &Rectangle<C>) -> bool {
+        if let Some(index) = self.obstacles.iter().position(|r| r == rect) {
+            self.obstacles.remove(index);
+            self.reclaim_rect(rect);
+
+            // overlapping obstacles must not lose their area
+            for i in 0..self.obstacles.len() {
+                let obstacle = self.obstacles[i];
+                if obstacle.intersects_total(rect) {
+                    self.carve_free_list(&obstacle);
+                }
+            }
+            true
+        } else {
+            false
+        }
+    }
+
     /// Inserts a single [`Dimension`] object into the bin.
     ///
     /// `dim` refers to the object to be packed into the bin.
@@ -391,20 +704,39 @@ impl GuillotineBin {
     ///
     /// `method` specifies the rectangle split heuristic to use.
     ///
+    /// The object is rotated by 90 degrees if it results in a better placement, unless
+    /// forbidden by the [`Rotation`] policy of the object.
+    ///
+    /// [`Rotation`]: crate::dimension::Rotation
+    ///
     /// Returns a copy of the packed [`Rectangle`] if the object was inserted successful,
     /// or `None` otherwise.
     pub fn insert(
         &mut self,
-        dim: &Dimension,
+        dim: &Dimension<C>,
+        merge: bool,
+        choice: RectHeuristic,
+        method: SplitHeuristic,
+    ) -> Option<Rectangle<C>> {
+        let dim = &dim.to_spaced(self.spacing);
+        loop {
+            let result = self.try_insert(dim, merge, choice, method);
+            if result.is_some() || !grow_bin(self, self.growth_policy, slice::from_ref(dim), true) {
+                return result;
+            }
+        }
+    }
+
+    /// Inserts a single `Dimension` object into the bin without enlarging the bin.
+    fn try_insert(
+        &mut self,
+        dim: &Dimension<C>,
         merge: bool,
         choice: RectHeuristic,
         method: SplitHeuristic,
-    ) -> Option<Rectangle> {
+    ) -> Option<Rectangle<C>> {
         // Empty or too big dimension objects are always rejected
-        if dim.is_empty()
-            || dim.width_total() > self.bin_width
-            || dim.height_total() > self.bin_height
-        {
+        if dim.is_empty() || !self.fits_area(dim) {
             return None;
         }
 
@@ -445,6 +777,11 @@ impl GuillotineBin {
     ///
     /// `method` specifies the rectangle split heuristic to use.
     ///
+    /// Objects are rotated by 90 degrees if it results in a better placement, unless
+    /// forbidden by the [`Rotation`] policy of the objects.
+    ///
+    /// [`Rotation`]: crate::dimension::Rotation
+    ///
     /// Returns a list with all successfully inserted [`Rectangle`] objects.
     ///
     /// This method performs slower than [`insert`], but may result in more tightly
@@ -453,102 +790,95 @@ impl GuillotineBin {
     /// [`insert`]: GuillotineBin::insert
     pub fn insert_list(
         &mut self,
-        nodes: &[Dimension],
+        nodes: &[Dimension<C>],
+        merge: bool,
+        choice: RectHeuristic,
+        method: SplitHeuristic,
+    ) -> (Vec<Rectangle<C>>, Vec<Dimension<C>>) {
+        let nodes: Vec<_> = nodes.iter().map(|n| n.to_spaced(self.spacing)).collect();
+        let (mut inserted, mut rejected) = self.try_insert_list(&nodes, merge, choice, method);
+        while !rejected.is_empty() && grow_bin(self, self.growth_policy, &rejected, true) {
+            let (mut new_nodes, remaining) = self.try_insert_list(&rejected, merge, choice, method);
+            inserted.append(&mut new_nodes);
+            rejected = remaining;
+        }
+
+        let rejected = rejected.into_iter().map(|n| n.to_spaced(C::ZERO)).collect();
+        (inserted, rejected)
+    }
+
+    /// Attempts to insert the given list of `Dimension` objects into the bin without enlarging
+    /// the bin.
+    fn try_insert_list(
+        &mut self,
+        nodes: &[Dimension<C>],
         merge: bool,
         choice: RectHeuristic,
         method: SplitHeuristic,
-    ) -> (Vec<Rectangle>, Vec<Dimension>) {
+    ) -> (Vec<Rectangle<C>>, Vec<Dimension<C>>) {
         let mut inserted = Vec::with_capacity(nodes.len().max(1));
-        let mut rejected = nodes.to_vec();
+        let (mut rejected, mut invalid): (Vec<Dimension<C>>, Vec<_>) =
+            nodes.iter().partition(|node| node.is_total_valid());
 
         // Remember variables about the best packing choice we have made so far during the
         // iteration process
         let mut best_free_rect = 0usize;
         let mut best_node = 0usize;
-        let mut best_flipped = false;
+        let mut best_rect = None;
 
         // Pack rectangles one at a time until we have cleared the `nodes` array of all rectangles.
         // `nodes` will get destroyed in the process.
         while !rejected.is_empty() {
-            let mut best_score = i32::MAX;
+            let mut best_score = C::WIDE_MAX;
 
             let mut i = 0usize;
             let free_size = self.rects_free.len();
             'free_loop: while i < free_size {
                 let free_rect = &self.rects_free[i];
 
-                let mut j = 0usize;
-                let nodes_size = rejected.len();
-                while j < nodes_size {
-                    let node = &rejected[j];
-
-                    if node.width_total() == free_rect.width_total()
-                        && node.height_total() == free_rect.height_total()
-                    {
-                        // If this rectangle is a perfect match, we pick it instantly
-                        best_free_rect = i;
-                        best_node = j;
-                        best_flipped = false;
-                        best_score = i32::MIN;
-                        break 'free_loop;
-                    } else if node.height_total() == free_rect.width_total()
-                        && node.width_total() == free_rect.height_total()
-                    {
-                        // If flipping this rectangle is a perfect match, pick that then
-                        best_free_rect = i;
-                        best_node = j;
-                        best_flipped = true;
-                        best_score = i32::MIN;
-                        break 'free_loop;
-                    } else if node.width_total() <= free_rect.width_total()
-                        && node.height_total() <= free_rect.height_total()
-                    {
-                        // Try if we can fit the rectangle upright
-                        let score = self.score_by_heuristic(node, free_rect, choice);
-                        if score < best_score {
-                            best_free_rect = i;
-                            best_node = j;
-                            best_flipped = false;
-                            best_score = score;
-                        }
-                    } else if node.height_total() <= free_rect.width_total()
-                        && node.width_total() <= free_rect.height_total()
-                    {
-                        // If not, then perhaps flipping sideways will make it fit?
-                        let score = self.score_by_heuristic(
-                            &Dimension::with_id(0, node.width_total(), node.height_total(), 0),
-                            free_rect,
-                            choice,
-                        );
-                        if score < best_score {
+                for (j, node) in rejected.iter().enumerate() {
+                    let alignment = node.alignment().or(self.alignment);
+                    for (dim, rotated) in node.orientations(true) {
+                        let (free_rect, dim) = match align_placement(free_rect, &dim, alignment) {
+                            Some(placement) => placement,
+                            None => continue,
+                        };
+                        let mut new_node = Rectangle::new(C::ZERO, C::ZERO, dim);
+                        new_node.set_location_total(free_rect.x_total(), free_rect.y_total());
+                        new_node.set_rotated(rotated);
+
+                        if coord::approx_eq(dim.width_total(), free_rect.width_total())
+                            && coord::approx_eq(dim.height_total(), free_rect.height_total())
+                        {
+                            // If this rectangle is a perfect match, we pick it instantly
                             best_free_rect = i;
                             best_node = j;
-                            best_flipped = true;
-                            best_score = score;
+                            best_rect = Some(new_node);
+                            best_score = C::WIDE_MIN;
+                            break 'free_loop;
+                        } else if coord::approx_le(dim.width_total(), free_rect.width_total())
+                            && coord::approx_le(dim.height_total(), free_rect.height_total())
+                        {
+                            // Try if we can fit the rectangle in the current orientation
+                            let score = self.score_by_heuristic(&dim, &free_rect, choice);
+                            if score < best_score {
+                                best_free_rect = i;
+                                best_node = j;
+                                best_rect = Some(new_node);
+                                best_score = score;
+                            }
                         }
                     }
-
-                    j += 1;
                 }
 
                 i += 1;
             }
 
             // If we didn't manage to find any rectangle to pack, abort
-            if best_score == i32::MAX {
-                break;
-            }
-
-            // Otherwise, we're good to go and do the actual packing
-            let mut new_node = Rectangle::new(
-                self.rects_free[best_free_rect].x() + rejected[best_node].padding(),
-                self.rects_free[best_free_rect].y() + rejected[best_node].padding(),
-                rejected[best_node].to_owned(),
-            );
-
-            if best_flipped {
-                new_node.dim_mut().flip();
-            }
+            let new_node = match best_rect {
+                Some(rect) if best_score != C::WIDE_MAX => rect,
+                _ => break,
+            };
 
             // Remove the free space we lost in the bin
             self.split_free_rect_by_heuristic(
@@ -571,30 +901,111 @@ impl GuillotineBin {
             inserted.push(new_node);
         }
 
+        rejected.append(&mut invalid);
         (inserted, rejected)
     }
 
-    /// Returns a mutable reference to the internal list of free rectangles.
+    /// Returns the area of the bin which is available for packing rectangles.
+    fn area(&self) -> Rectangle<C> {
+        packing_area(self.bin_width, self.bin_height, self.border, self.spacing)
+    }
+
+    /// Returns whether the given `Dimension` fits into the empty bin in any orientation.
+    fn fits_area(&self, dim: &Dimension<C>) -> bool {
+        let area = self.area();
+        dim.fits_into(area.width(), area.height(), true)
+    }
+
+    /// Creates a bin for simulating insertions, which shares the configuration and the free
+    /// rectangles of this bin, but holds no mapped rectangles or obstacles.
+    pub(crate) fn scratch_bin(&self) -> Self {
+        let mut rects_free = self.rects_free.to_vec();
+        let mut result = Self {
+            bin_width: self.bin_width,
+            bin_height: self.bin_height,
+            rects_used: IndexedRects::with_capacity(4),
+            rects_free: TrackedVec::with_capacity(rects_free.len()),
+            obstacles: TrackedVec::new(),
+            border: self.border,
+            spacing: self.spacing,
+            alignment: self.alignment,
+            checkpoints: Vec::new(),
+            default_rect_choice: self.default_rect_choice,
+            default_split_method: self.default_split_method,
+            default_merge: self.default_merge,
+            growth_policy: None,
+        };
+        result.rects_free.append(&mut rects_free);
+
+        result
+    }
+
+    /// Places the given `Dimension` into a free area, if the bin serves as the waste map of
+    /// another bin packer. Placed rectangles are not kept, as the waste map only keeps track of
+    /// free areas.
     ///
-    /// You may alter this vector any way desired, as long as the end result still is a list of
-    /// disjoint rectangles.
+    /// `rotate` indicates whether the object may be rotated by 90 degrees.
+    pub(crate) fn insert_waste(
+        &mut self,
+        dim: &Dimension<C>,
+        rotate: bool,
+    ) -> Option<Rectangle<C>> {
+        let mut result = self.insert(
+            &waste_node(dim, rotate),
+            true,
+            RectHeuristic::BestShortSideFit,
+            SplitHeuristic::MaximizeArea,
+        )?;
+        result.dim_mut().set_rotation(dim.rotation());
+        self.rects_used.clear();
+
+        Some(result)
+    }
+
+    /// Determines where the given `Dimension` would be placed by [`insert_waste`], without
+    /// modifying the bin.
     ///
-    /// (Added for potential future use.)
-    #[allow(dead_code)]
-    pub(crate) fn get_free_rects(&mut self) -> &mut Vec<Rectangle> {
-        &mut self.rects_free
+    /// [`insert_waste`]: Self::insert_waste
+    pub(crate) fn preview_waste(&self, dim: &Dimension<C>, rotate: bool) -> Option<Rectangle<C>> {
+        let (_, result) = self
+            .find_position_for_new_node(&waste_node(dim, rotate), RectHeuristic::BestShortSideFit);
+        let mut result = result?;
+        result.dim_mut().set_rotation(dim.rotation());
+
+        Some(result)
     }
 
-    /// Returns a mutable reference to the internal list of used rectangles.
+    /// Changes the size of the bin without modifying the list of free rectangles.
     ///
-    /// You may alter this vector at will, for example, you can move a `Rectangle` from this list
-    /// to the Free Rectangles list to free up space on-the-fly, but notice that this causes
-    /// fragmentation.
+    /// Used by bin packers which manage the free space of a `GuillotineBin` on their own.
+    pub(crate) fn set_bin_size(&mut self, width: C, height: C) {
+        self.bin_width = coord::max(width, C::ONE);
+        self.bin_height = coord::max(height, C::ONE);
+    }
+
+    /// Adds the area of the given rectangle to the list of free rectangles and merges it with
+    /// adjacent free rectangles.
+    pub(crate) fn reclaim_rect(&mut self, rect: &Rectangle<C>) {
+        self.rects_free.push(Rectangle::new(
+            rect.x_total(),
+            rect.y_total(),
+            Dimension::with_id(0, rect.width_total(), rect.height_total(), C::ZERO),
+        ));
+
+        // Free rectangles may have to be merged in several passes
+        let mut free_size = 0usize;
+        while free_size != self.rects_free.len() {
+            free_size = self.rects_free.len();
+            self.merge_free_list();
+        }
+    }
+
+    /// Returns a mutable reference to the internal list of free rectangles.
     ///
-    /// (Added for potential future use.)
-    #[allow(dead_code)]
-    pub(crate) fn get_used_rects(&mut self) -> &mut Vec<Rectangle> {
-        &mut self.rects_used
+    /// You may alter this list any way desired, as long as the end result still is a list of
+    /// disjoint rectangles.
+    pub(crate) fn get_free_rects(&mut self) -> &mut TrackedVec<Rectangle<C>> {
+        &mut self.rects_free
     }
 
     /// Goes through the list of free rectangles and finds the best one to place a rectangle of
@@ -605,57 +1016,51 @@ impl GuillotineBin {
     /// represents the placement of the new rectangle into the best free rectangle.
     ///
     /// Running time is Theta(|freeRectangles|).
-    fn find_position_for_new_node(
+    pub(crate) fn find_position_for_new_node(
         &self,
-        dim: &Dimension,
+        dim: &Dimension<C>,
         choice: RectHeuristic,
-    ) -> (usize, Option<Rectangle>) {
+    ) -> (usize, Option<Rectangle<C>>) {
         let mut node_index = 0usize;
         let mut best_node = None;
-        let mut best_score = i32::MAX;
+        let mut best_score = C::WIDE_MAX;
+
+        // Objects exceeding the range of the coordinate type can never be placed
+        if !dim.is_total_valid() {
+            return (node_index, best_node);
+        }
 
         // Try each free rectangle to find the best one for placement
-        for (i, rect) in self.rects_free.iter().enumerate() {
-            if dim.width_total() == rect.width_total() && dim.height_total() == rect.height_total()
-            {
-                // If this is a perfect fit upright, choose it immediately
-                let node = best_node.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                node.set_location_total(rect.x_total(), rect.y_total());
-                node.dim_mut().set_dimension(dim.width(), dim.height());
-                node_index = i;
-                break;
-            } else if dim.height_total() == rect.width_total()
-                && dim.width_total() == rect.height_total()
-            {
-                // If this is a perfect fit sideways, choose it
-                let node = best_node.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                node.set_location_total(rect.x_total(), rect.y_total());
-                node.dim_mut().set_dimension(dim.height(), dim.width());
-                node_index = i;
-                break;
-            } else if dim.width_total() <= rect.width_total()
-                && dim.height_total() <= rect.height_total()
-            {
-                // Does the rectangle fit upright?
-                let score = self.score_by_heuristic(dim, rect, choice);
-                if score < best_score {
-                    let node = best_node.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                    node.set_location_total(rect.x_total(), rect.y_total());
-                    node.dim_mut().set_dimension(dim.width(), dim.height());
-                    best_score = score;
-                    node_index = i;
-                }
-            } else if dim.height_total() <= rect.width_total()
-                && dim.width_total() <= rect.height_total()
-            {
-                // Does the rectangle fit sideways?
-                let score = self.score_by_heuristic(dim, rect, choice);
-                if score < best_score {
-                    let node = best_node.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
+        let alignment = dim.alignment().or(self.alignment);
+        'free_loop: for (i, free) in self.rects_free.iter().enumerate() {
+            for (dim, rotated) in dim.orientations(true) {
+                let (rect, dim) = match align_placement(free, &dim, alignment) {
+                    Some(placement) => placement,
+                    None => continue,
+                };
+                if coord::approx_eq(dim.width_total(), rect.width_total())
+                    && coord::approx_eq(dim.height_total(), rect.height_total())
+                {
+                    // If this is a perfect fit, choose it immediately
+                    let mut node = Rectangle::new(C::ZERO, C::ZERO, dim);
                     node.set_location_total(rect.x_total(), rect.y_total());
-                    node.dim_mut().set_dimension(dim.height(), dim.width());
-                    best_score = score;
+                    node.set_rotated(rotated);
+                    best_node = Some(node);
                     node_index = i;
+                    break 'free_loop;
+                } else if coord::approx_le(dim.width_total(), rect.width_total())
+                    && coord::approx_le(dim.height_total(), rect.height_total())
+                {
+                    // Does the rectangle fit in the current orientation?
+                    let score = self.score_by_heuristic(&dim, &rect, choice);
+                    if score < best_score {
+                        let mut node = Rectangle::new(C::ZERO, C::ZERO, dim);
+                        node.set_location_total(rect.x_total(), rect.y_total());
+                        node.set_rotated(rotated);
+                        best_node = Some(node);
+                        best_score = score;
+                        node_index = i;
+                    }
                 }
             }
         }
@@ -667,10 +1072,10 @@ impl GuillotineBin {
     /// Does not try to rotate.
     fn score_by_heuristic(
         &self,
-        dim: &Dimension,
-        free_rect: &Rectangle,
+        dim: &Dimension<C>,
+        free_rect: &Rectangle<C>,
         choice: RectHeuristic,
-    ) -> i32 {
+    ) -> C::Wide {
         match choice {
             RectHeuristic::BestAreaFit => self.score_baf(dim, free_rect),
             RectHeuristic::BestShortSideFit => self.score_bssf(dim, free_rect),
@@ -683,41 +1088,41 @@ impl GuillotineBin {
 
     /// Computes score value if a rect of the given size was placed into the given free rectangle.
     /// In these score values, smaller is better.
-    fn score_baf(&self, dim: &Dimension, free_rect: &Rectangle) -> i32 {
-        (free_rect.dim().area_total() - dim.area_total()) as i32
+    fn score_baf(&self, dim: &Dimension<C>, free_rect: &Rectangle<C>) -> C::Wide {
+        free_rect.dim().area_total() - dim.area_total()
     }
 
     /// Computes score value if a rect of the given size was placed into the given free rectangle.
     /// In these score values, smaller is better.
-    fn score_bssf(&self, dim: &Dimension, free_rect: &Rectangle) -> i32 {
-        let leftover_h = free_rect.width_total().abs_diff(dim.width_total());
-        let leftover_v = free_rect.height_total().abs_diff(dim.height_total());
-        leftover_v.min(leftover_h) as i32
+    fn score_bssf(&self, dim: &Dimension<C>, free_rect: &Rectangle<C>) -> C::Wide {
+        let leftover_h = coord::abs_diff(free_rect.width_total(), dim.width_total());
+        let leftover_v = coord::abs_diff(free_rect.height_total(), dim.height_total());
+        coord::min(leftover_v, leftover_h)
     }
 
     /// Computes score value if a rect of the given size was placed into the given free rectangle.
     /// In these score values, smaller is better.
-    fn score_blsf(&self, dim: &Dimension, free_rect: &Rectangle) -> i32 {
-        let leftover_h = free_rect.width_total().abs_diff(dim.width_total());
-        let leftover_v = free_rect.height_total().abs_diff(dim.height_total());
-        leftover_v.max(leftover_h) as i32
+    fn score_blsf(&self, dim: &Dimension<C>, free_rect: &Rectangle<C>) -> C::Wide {
+        let leftover_h = coord::abs_diff(free_rect.width_total(), dim.width_total());
+        let leftover_v = coord::abs_diff(free_rect.height_total(), dim.height_total());
+        coord::max(leftover_v, leftover_h)
     }
 
     /// Computes (penalty) score value if a rect of the given size was placed into the given free
     /// rectangle. In these score values, smaller is better.
-    fn score_waf(&self, dim: &Dimension, free_rect: &Rectangle) -> i32 {
+    fn score_waf(&self, dim: &Dimension<C>, free_rect: &Rectangle<C>) -> C::Wide {
         -self.score_baf(dim, free_rect)
     }
 
     /// Computes (penalty) score value if a rect of the given size was placed into the given free
     /// rectangle. In these score values, smaller is better.
-    fn score_wssf(&self, dim: &Dimension, free_rect: &Rectangle) -> i32 {
+    fn score_wssf(&self, dim: &Dimension<C>, free_rect: &Rectangle<C>) -> C::Wide {
         -self.score_bssf(dim, free_rect)
     }
 
     /// Computes (penalty) score value if a rect of the given size was placed into the given free
     /// rectangle. In these score values, smaller is better.
-    fn score_wlsf(&self, dim: &Dimension, free_rect: &Rectangle) -> i32 {
+    fn score_wlsf(&self, dim: &Dimension<C>, free_rect: &Rectangle<C>) -> C::Wide {
         -self.score_blsf(dim, free_rect)
     }
 
@@ -725,10 +1130,44 @@ impl GuillotineBin {
     /// has been placed into it. Determines the split axis by using the given heuristic.
     fn split_free_rect_by_heuristic(
         &mut self,
-        free_rect: &Rectangle,
-        placed_rect: &Rectangle,
+        free_rect: &Rectangle<C>,
+        placed_rect: &Rectangle<C>,
         method: SplitHeuristic,
     ) {
+        // Aligned rectangles may be placed at an offset, which leaves free strips above and to
+        // the left of the placed rectangle
+        let dx = placed_rect.x_total() - free_rect.x_total();
+        let dy = placed_rect.y_total() - free_rect.y_total();
+        let (offset_x, offset_y) = (coord::approx_lt(C::ZERO, dx), coord::approx_lt(C::ZERO, dy));
+        let free_rect = &if offset_x || offset_y {
+            if offset_y {
+                self.rects_free.push(Rectangle::new(
+                    free_rect.x_total(),
+                    free_rect.y_total(),
+                    Dimension::with_id(0, free_rect.width_total(), dy, C::ZERO),
+                ));
+            }
+            if offset_x {
+                self.rects_free.push(Rectangle::new(
+                    free_rect.x_total(),
+                    placed_rect.y_total(),
+                    Dimension::with_id(0, dx, free_rect.height_total() - dy, C::ZERO),
+                ));
+            }
+            Rectangle::new(
+                placed_rect.x_total(),
+                placed_rect.y_total(),
+                Dimension::with_id(
+                    0,
+                    free_rect.width_total() - dx,
+                    free_rect.height_total() - dy,
+                    C::ZERO,
+                ),
+            )
+        } else {
+            *free_rect
+        };
+
         // Compute the lengths of the leftover area
         let w = free_rect.width_total() - placed_rect.width_total();
         let h = free_rect.height_total() - placed_rect.height_total();
@@ -746,12 +1185,12 @@ impl GuillotineBin {
             // Maximize the larger area == minimize the smaller area.
             // Tries to make the single bigger rectangle.
             SplitHeuristic::MinimizeArea => {
-                placed_rect.width_total() * h > w * placed_rect.height_total()
+                placed_rect.width_total().mul_wide(h) > w.mul_wide(placed_rect.height_total())
             }
             // Maximize the smaller area == minimize the larger area.
             // Tries to make the rectangles more even-sized.
             SplitHeuristic::MaximizeArea => {
-                placed_rect.width_total() * h <= w * placed_rect.height_total()
+                placed_rect.width_total().mul_wide(h) <= w.mul_wide(placed_rect.height_total())
             }
             // Split along the shorter total axis
             SplitHeuristic::ShorterAxis => free_rect.width_total() <= free_rect.height_total(),
@@ -768,8 +1207,8 @@ impl GuillotineBin {
     /// The caller is expected to remove the original rectangle from the rects_free array after that.
     fn split_free_rect_along_axis(
         &mut self,
-        free_rect: &Rectangle,
-        placed_rect: &Rectangle,
+        free_rect: &Rectangle<C>,
+        placed_rect: &Rectangle<C>,
         split_horizontal: bool,
     ) {
         // Form the two new rectangles
@@ -778,26 +1217,31 @@ impl GuillotineBin {
             free_rect.y_total() + placed_rect.height_total(),
             Dimension::with_id(
                 0,
-                0,
+                C::ZERO,
                 free_rect.height_total() - placed_rect.height_total(),
-                0,
+                C::ZERO,
             ),
         );
 
         let mut right = Rectangle::new(
             free_rect.x_total() + placed_rect.width_total(),
             free_rect.y_total(),
-            Dimension::with_id(0, free_rect.width_total() - placed_rect.width_total(), 0, 0),
+            Dimension::with_id(
+                0,
+                free_rect.width_total() - placed_rect.width_total(),
+                C::ZERO,
+                C::ZERO,
+            ),
         );
 
         if split_horizontal {
             // split horizontally
-            bottom.dim_mut().set_width(free_rect.width());
-            right.dim_mut().set_height(placed_rect.height());
+            bottom.dim_mut().set_width(free_rect.width_total());
+            right.dim_mut().set_height(placed_rect.height_total());
         } else {
             // split vertically
-            bottom.dim_mut().set_width(placed_rect.width());
-            right.dim_mut().set_height(free_rect.height());
+            bottom.dim_mut().set_width(placed_rect.width_total());
+            right.dim_mut().set_height(free_rect.height_total());
         }
 
         // Add the new rectangles into the free rectangle pool if they weren't degenerate
@@ -809,11 +1253,105 @@ impl GuillotineBin {
         }
     }
 
+    /// Returns whether the area of the given rectangle is entirely covered by free rectangles.
+    pub(crate) fn is_free_area(&self, rect: &Rectangle<C>) -> bool {
+        let left = rect.x_total();
+        let top = rect.y_total();
+        let right = rect.right_total();
+        let bottom = rect.bottom_total();
+
+        // free rectangles are disjoint, which allows to simply sum up the covered areas
+        let area: C::Wide = self
+            .rects_free
+            .iter()
+            .map(|r| {
+                let width = coord::min(r.right_total(), right)
+                    .saturating_sub(coord::max(r.x_total(), left));
+                let height = coord::min(r.bottom_total(), bottom)
+                    .saturating_sub(coord::max(r.y_total(), top));
+                coord::max(width, C::ZERO).mul_wide(coord::max(height, C::ZERO))
+            })
+            .sum();
+
+        // rounding errors of floating-point coordinates are tolerated along the perimeter
+        let tolerance =
+            C::EPSILON.widen() * (rect.width_total().widen() + rect.height_total().widen());
+        area >= rect.dim().area_total() - tolerance
+    }
+
+    /// Removes the area of the given rectangle from all intersecting free rectangles.
+    ///
+    /// Each intersecting free rectangle is replaced by up to four disjoint rectangles: the areas
+    /// left and right of `rect` spanning the full height, and the areas above and below `rect`.
+    pub(crate) fn carve_free_list(&mut self, rect: &Rectangle<C>) {
+        let left = rect.x_total();
+        let top = rect.y_total();
+        let right = rect.right_total();
+        let bottom = rect.bottom_total();
+
+        let mut idx = 0usize;
+        while idx < self.rects_free.len() {
+            let free = self.rects_free[idx];
+            if !free.intersects_total(rect) {
+                idx += 1;
+                continue;
+            }
+            self.rects_free.swap_remove(idx);
+
+            let free_left = free.x_total();
+            let free_top = free.y_total();
+            let free_right = free.right_total();
+            let free_bottom = free.bottom_total();
+            let mid_left = coord::max(free_left, left);
+            let mid_right = coord::min(free_right, right);
+            let mid_width = mid_right - mid_left;
+            // extents of pieces outside of the free rectangle are not positive
+            let pieces = [
+                (
+                    free_left,
+                    free_top,
+                    left.saturating_sub(free_left),
+                    free.height_total(),
+                ),
+                (
+                    right,
+                    free_top,
+                    free_right.saturating_sub(right),
+                    free.height_total(),
+                ),
+                (mid_left, free_top, mid_width, top.saturating_sub(free_top)),
+                (
+                    mid_left,
+                    bottom,
+                    mid_width,
+                    free_bottom.saturating_sub(bottom),
+                ),
+            ];
+            for (x, y, width, height) in pieces {
+                if coord::approx_lt(C::ZERO, width) && coord::approx_lt(C::ZERO, height) {
+                    // new pieces do not intersect rect and are skipped by subsequent iterations
+                    self.rects_free.push(Rectangle::new(
+                        x,
+                        y,
+                        Dimension::with_id(0, width, height, C::ZERO),
+                    ));
+                }
+            }
+        }
+
+        // Free rectangles may have to be merged in several passes
+        let mut free_size = 0usize;
+        while free_size != self.rects_free.len() {
+            free_size = self.rects_free.len();
+            self.merge_free_list();
+        }
+    }
+
     /// Performs a Rectangle Merge operation.
     ///
     /// This procedure looks for adjacent free rectangles and merges them if they can be represented
     /// with a single rectangle. Takes up Theta(|freeRectangles|^2) time.
-    fn merge_free_list(&mut self) {
+    pub(crate) fn merge_free_list(&mut self) {
         // Do a Theta(n^2) loop to see if any pair of free rectangles could me merged into one.
         // Note that we miss any opportunities to merge three rectangles into one.
         // (should call this function again to detect that)
@@ -825,38 +1363,39 @@ impl GuillotineBin {
             let mut j = i + 1;
             while j < free_size {
                 let rect2 = &self.rects_free[j];
-                if rect1.width_total() == rect2.width_total() && rect1.x_total() == rect2.x_total()
+                if coord::approx_eq(rect1.width_total(), rect2.width_total())
+                    && coord::approx_eq(rect1.x_total(), rect2.x_total())
                 {
-                    if rect1.y_total() == rect2.y_total() + rect2.height_total() {
+                    if coord::approx_eq(rect1.y_total(), rect2.bottom_total()) {
                         rect1.set_y_total(rect1.y_total() - rect2.height_total());
                         let rect1_height = rect1.height();
                         rect1.dim_mut().set_height(rect1_height + rect2.height());
-                        let _ = mem::replace(&mut self.rects_free[i], rect1);
+                        self.rects_free.set(i, rect1);
                         self.rects_free.swap_remove(j);
                         free_size -= 1;
-                    } else if rect1.y_total() + rect1.height_total() == rect2.y_total() {
+                    } else if coord::approx_eq(rect1.bottom_total(), rect2.y_total()) {
                         let rect1_height = rect1.height();
                         rect1.dim_mut().set_height(rect1_height + rect2.height());
-                        let _ = mem::replace(&mut self.rects_free[i], rect1);
+                        self.rects_free.set(i, rect1);
                         self.rects_free.swap_remove(j);
                         free_size -= 1;
                     } else {
                         j += 1;
                     }
-                } else if rect1.height_total() == rect2.height_total()
-                    && rect1.y_total() == rect2.y_total()
+                } else if coord::approx_eq(rect1.height_total(), rect2.height_total())
+                    && coord::approx_eq(rect1.y_total(), rect2.y_total())
                 {
-                    if rect1.x_total() == rect2.x_total() + rect2.width_total() {
+                    if coord::approx_eq(rect1.x_total(), rect2.right_total()) {
                         rect1.set_x_total(rect1.x_total() - rect2.width_total());
                         let rect1_width = rect1.width();
                         rect1.dim_mut().set_width(rect1_width + rect2.width());
-                        let _ = mem::replace(&mut self.rects_free[i], rect1);
+                        self.rects_free.set(i, rect1);
                         self.rects_free.swap_remove(j);
                         free_size -= 1;
-                    } else if rect1.x_total() + rect1.width_total() == rect2.x_total() {
+                    } else if coord::approx_eq(rect1.right_total(), rect2.x_total()) {
                         let rect1_width = rect1.width();
                         rect1.dim_mut().set_width(rect1_width + rect2.width());
-                        let _ = mem::replace(&mut self.rects_free[i], rect1);
+                        self.rects_free.set(i, rect1);
                         self.rects_free.swap_remove(j);
                         free_size -= 1;
                     } else {
@@ -872,9 +1411,20 @@ impl GuillotineBin {
     }
 }
 
-impl<Idx> std::ops::Index<Idx> for GuillotineBin
+/// Returns a copy of the given `Dimension` for placing it into a waste map, which may only rotate
+/// the object if `rotate` is `true`.
+fn waste_node<C: Coord>(dim: &Dimension<C>, rotate: bool) -> Dimension<C> {
+    // The guillotine bin packer always rotates objects unless explicitly forbidden
+    let mut node = *dim;
+    if !rotate && node.rotation() == Rotation::Allowed {
+        node.set_rotation(Rotation::Forbidden);
+    }
+    node
+}
+
+impl<C: Coord, Idx> std::ops::Index<Idx> for GuillotineBin<C>
 where
-    Idx: std::slice::SliceIndex<[Rectangle]>,
+    Idx: std::slice::SliceIndex<[Rectangle<C>]>,
 {
     type Output = Idx::Output;
 
@@ -883,7 +1433,7 @@ where
     }
 }
 
-impl Display for GuillotineBin {
+impl<C: Coord> Display for GuillotineBin<C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -906,11 +1456,16 @@ impl Display for GuillotineBin {
 ///
 /// [`insert_list`]: GuillotineBin::insert_list
 ///
+/// Nodes with the same [`group`] key are always placed into the same bin.
+///
+/// [`group`]: Dimension::group
+///
 /// Returns a list of bins with the packed rectangle nodes as a [`Result`] value.
 ///
 /// # Errors
 ///
-/// A [`BinError`] is returned for nodes which are either empty or too big for the bin.
+/// A [`PackError`] is returned if any nodes could not be packed, listing each rejected node with
+/// the reason of rejection. All other nodes are still packed into the bins provided by the error.
 ///
 /// # Examples
 /// ```
@@ -941,123 +1496,71 @@ pub fn pack_bins(
     choice: RectHeuristic,
     method: SplitHeuristic,
     optimized: bool,
-) -> Result<Vec<GuillotineBin>, BinError> {
-    if optimized {
-        pack_bins_list(nodes, bin_width, bin_height, merge, choice, method)
-    } else {
-        pack_bins_single(nodes, bin_width, bin_height, merge, choice, method)
-    }
-}
-
-/// Inserts nodes via insert_list().
-fn pack_bins_list(
-    nodes: &[Dimension],
-    bin_width: i32,
-    bin_height: i32,
-    merge: bool,
-    choice: RectHeuristic,
-    method: SplitHeuristic,
-) -> Result<Vec<GuillotineBin>, BinError> {
-    let mut bins = Vec::new();
-    if nodes.is_empty() || bin_width == 0 || bin_height == 0 {
-        return Ok(bins);
-    }
-
-    // first pass is done separately to avoid a (potentially) costly clone operation
-    let mut bin = GuillotineBin::new(bin_width, bin_height);
-    let (inserted, mut rejected) = bin.insert_list(nodes, merge, choice, method);
-
-    if inserted.is_empty() && !rejected.is_empty() {
-        // remaining nodes are too big and will be silently skipped
-        rejected.clear();
-    }
-
-    if !inserted.is_empty() {
-        bins.push(bin);
-    }
-
-    // subsequent passes are done in a loop
-    let mut nodes_left = rejected;
-    while !nodes_left.is_empty() {
-        let mut bin = GuillotineBin::new(bin_width, bin_height);
-        let (inserted, mut rejected) = bin.insert_list(&nodes_left, merge, choice, method);
-
-        if inserted.is_empty() && !rejected.is_empty() {
-            // remaining nodes are too big or too small
-            let result = rejected
-                .iter()
-                .map(|r| {
-                    if r.width_total() == 0 || r.height_total() == 0 {
-                        BinError::ItemTooSmall
-                    } else if r.width_total() > bin_width || r.height_total() > bin_height {
-                        BinError::ItemTooBig
-                    } else {
-                        BinError::Unspecified
-                    }
-                })
-                .next();
-            if let Some(result) = result {
-                return Err(result);
-            } else {
-                // Should not happen
-                eprintln!("pack_bins(): Could not insert remaining items");
-                rejected.clear();
-            }
-        }
-
-        if !inserted.is_empty() {
-            bins.push(bin);
-        }
-
-        // preparing for next iteration
-        nodes_left.clear();
-        nodes_left.append(&mut rejected);
-    }
-
-    Ok(bins)
+) -> Result<Vec<GuillotineBin>, PackError<GuillotineBin>> {
+    let result = pack_bins_with(nodes, bin_width, bin_height, optimized, |width, height| {
+        let mut bin = GuillotineBin::new(width, height);
+        bin.set_default_merge(merge);
+        bin.set_default_choice(choice);
+        bin.set_default_method(method);
+        Box::new(bin)
+    });
+
+    result
+        .map(|bins| bins.into_iter().map(|bin| *bin).collect())
+        .map_err(|err| err.map_bins(|bin| *bin))
 }
 
-/// Inserts nodes via insert().
-fn pack_bins_single(
+/// A convenience function that packs a given list of `Dimension` objects into a strip of the
+/// given width, and reduces the height of the strip as much as possible.
+///
+/// The strip is packed via [`insert_list`], using the given heuristic `choice` and `method`.
+/// Optionally, performs an additional `merge` step to keep fragmentation to a minimum.
+///
+/// [`insert_list`]: GuillotineBin::insert_list
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value. The height of the bin
+/// is the final strip height.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too wide for the strip.
+///
+/// # Examples
+/// ```
+/// use binpack2d::binpack::BinPacker;
+/// use binpack2d::binpack::guillotine::{RectHeuristic, SplitHeuristic, pack_strip};
+/// use binpack2d::dimension::Dimension;
+///
+/// // Defining three items of different size
+/// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
+///
+/// // Returned strip contains all nodes, placed according to the given heuristic rule
+/// let strip = pack_strip(&nodes,
+///                       8,
+///                       true,
+///                       RectHeuristic::BestShortSideFit,
+///                       SplitHeuristic::ShorterLeftoverAxis)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(8, strip.width());
+/// assert_eq!(3, strip.len());
+/// ```
+pub fn pack_strip(
     nodes: &[Dimension],
-    bin_width: i32,
-    bin_height: i32,
+    strip_width: i32,
     merge: bool,
     choice: RectHeuristic,
     method: SplitHeuristic,
-) -> Result<Vec<GuillotineBin>, BinError> {
-    let mut bins = Vec::new();
-    if nodes.is_empty() || bin_width == 0 || bin_height == 0 {
-        return Ok(bins);
-    }
-
-    for node in nodes {
-        if node.is_empty() {
-            return Err(BinError::ItemTooSmall);
-        } else if node.width_total() > bin_width || node.height_total() > bin_height {
-            return Err(BinError::ItemTooBig);
-        }
-
-        // try inserting node into existing bins
-        let mut inserted = false;
-        for bin in &mut bins {
-            if bin.insert(node, merge, choice, method).is_some() {
-                inserted = true;
-                break;
-            }
-        }
-
-        // create new bin if needed
-        if !inserted {
-            bins.push(GuillotineBin::new(bin_width, bin_height));
-            if let Some(bin) = bins.last_mut() {
-                bin.insert(node, merge, choice, method)
-                    .expect("Object should fit into the bin");
-            }
-        }
-    }
-
-    Ok(bins)
+) -> Result<GuillotineBin, BinError> {
+    let strip = pack_strip_with(nodes, strip_width, |width, height| {
+        let mut bin = GuillotineBin::new(width, height);
+        bin.set_default_merge(merge);
+        bin.set_default_choice(choice);
+        bin.set_default_method(method);
+        Box::new(bin)
+    })?;
+
+    Ok(*strip)
 }
 
 #[cfg(test)]
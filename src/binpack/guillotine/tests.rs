@@ -16,6 +16,14 @@ fn bin_shrink() {
     bin.shrink(false);
     assert_eq!(6, bin.width());
     assert_eq!(7, bin.height());
+
+    let result = bin.insert(
+        &Dimension::new(1, 1),
+        true,
+        RectHeuristic::BestAreaFit,
+        SplitHeuristic::MinimizeArea,
+    );
+    assert!(result.is_none());
 }
 
 #[test]
@@ -80,3 +88,301 @@ fn bin_insert_list() {
         }
     }
 }
+
+#[test]
+fn bin_insert_rotated() {
+    let choice = RectHeuristic::BestAreaFit;
+    let method = SplitHeuristic::MinimizeArea;
+
+    let mut bin = GuillotineBin::new(6, 4);
+    bin.insert(&Dimension::new(4, 4), true, choice, method);
+
+    let dim = Dimension::new(4, 2);
+    let rect = bin.insert(&dim, true, choice, method);
+    assert!(rect.is_some());
+    let rect = rect.unwrap();
+    assert!(rect.is_rotated());
+    assert_eq!(2, rect.width());
+    assert_eq!(4, rect.height());
+    assert_eq!(dim, rect.original_dim());
+
+    let mut bin = GuillotineBin::new(6, 4);
+    let (inserted, _) = bin.insert_list(&[Dimension::new(4, 4), dim], true, choice, method);
+    assert_eq!(2, inserted.len());
+    assert!(!inserted[0].is_rotated());
+    assert!(inserted[1].is_rotated());
+}
+
+#[test]
+fn bin_remove() {
+    let choice = RectHeuristic::BestAreaFit;
+    let method = SplitHeuristic::MinimizeArea;
+
+    let mut bin = GuillotineBin::new(8, 8);
+    let rects: Vec<Rectangle> = (0..4)
+        .filter_map(|_| bin.insert(&Dimension::new(8, 2), true, choice, method))
+        .collect();
+    assert_eq!(4, rects.len());
+
+    // two adjacent strips are merged into a single free rectangle
+    for rect in rects.iter().filter(|r| r.y() == 2 || r.y() == 4) {
+        assert!(bin.remove_rect(rect));
+    }
+
+    let rect = bin.insert(&Dimension::new(8, 4), false, choice, method);
+    assert_eq!(Some((0, 2)), rect.map(|r| (r.x(), r.y())));
+}
+
+#[test]
+fn bin_grow() {
+    let choice = RectHeuristic::BestAreaFit;
+    let method = SplitHeuristic::MinimizeArea;
+
+    let mut bin = GuillotineBin::new(8, 8);
+    bin.insert(&Dimension::new(4, 8), true, choice, method);
+    bin.grow(4, 4);
+
+    // free space next to the item is extended into the new area
+    let rect = bin.insert(&Dimension::new(8, 12), true, choice, method);
+    assert_eq!(Some((4, 0)), rect.map(|r| (r.x(), r.y())));
+    let rect = bin.insert(&Dimension::new(4, 4), true, choice, method);
+    assert_eq!(Some((0, 8)), rect.map(|r| (r.x(), r.y())));
+}
+
+#[test]
+fn bin_grow_float_coords() {
+    let choice = RectHeuristic::BestAreaFit;
+    let method = SplitHeuristic::MinimizeArea;
+
+    for count in 1..17 {
+        // free space behind strips whose sizes are not exactly representable is extended
+        let mut bin: GuillotineBin<f64> = GuillotineBin::new(1.7, 1.7);
+        let nodes = vec![Dimension::new(0.1, 1.7); count];
+        bin.insert_list(&nodes, false, choice, method);
+        bin.grow(1, 0);
+        let width = 2.7 - 0.1 * count as f64 - 1e-9;
+        let rect = bin.insert(&Dimension::new(width, 1.7), false, choice, method);
+        assert!(rect.is_some());
+
+        let mut bin: GuillotineBin<f64> = GuillotineBin::new(1.7, 1.7);
+        let nodes = vec![Dimension::new(1.7, 0.1); count];
+        bin.insert_list(&nodes, false, choice, method);
+        bin.grow(0, 1);
+        let height = 2.7 - 0.1 * count as f64 - 1e-9;
+        let rect = bin.insert(&Dimension::new(1.7, height), false, choice, method);
+        assert!(rect.is_some());
+        assert!(bin.validate().is_empty());
+    }
+}
+
+#[test]
+fn bin_obstacles() {
+    let choice = RectHeuristic::BestAreaFit;
+    let method = SplitHeuristic::MinimizeArea;
+
+    let mut bin = GuillotineBin::new(8, 8);
+    let obstacle1 = Rectangle::new(2, 2, Dimension::new(4, 4));
+    let obstacle2 = Rectangle::new(4, 4, Dimension::new(4, 4));
+    assert!(bin.add_obstacle(&obstacle1));
+    assert!(bin.add_obstacle(&obstacle2));
+    assert!(!bin.add_obstacle(&Rectangle::new(-1, 0, Dimension::new(2, 2))));
+    assert_eq!(2, bin.obstacles().len());
+
+    // free space is limited to the area around the obstacles
+    let nodes = vec![Dimension::new(2, 2); 11];
+    let (inserted, rejected) = bin.insert_list(&nodes, true, choice, method);
+    assert_eq!(9, inserted.len());
+    assert_eq!(2, rejected.len());
+    for rect in &inserted {
+        assert!(!rect.intersects(&obstacle1) && !rect.intersects(&obstacle2));
+    }
+    assert_eq!(9, bin.len());
+    assert_eq!(36.0 / 64.0, bin.occupancy());
+
+    // area of the overlapping obstacle remains unavailable
+    bin.clear();
+    assert!(bin.remove_obstacle(&obstacle1));
+    assert!(bin
+        .insert(&Dimension::new(4, 4), true, choice, method)
+        .is_some());
+    assert!(bin
+        .insert(&Dimension::new(4, 4), true, choice, method)
+        .is_some());
+    assert!(bin
+        .insert(&Dimension::new(4, 4), true, choice, method)
+        .is_some());
+    assert!(bin
+        .insert(&Dimension::new(1, 1), true, choice, method)
+        .is_none());
+}
+
+#[test]
+fn bin_alignment() {
+    let choice = RectHeuristic::BestShortSideFit;
+    let method = SplitHeuristic::ShorterLeftoverAxis;
+
+    let mut bin = GuillotineBin::new(32, 32);
+    bin.set_alignment(Some(Alignment::new(4, 4, false)));
+    assert_eq!(Some(Alignment::new(4, 4, false)), bin.alignment());
+
+    let mut nodes = vec![
+        Dimension::new(3, 5),
+        Dimension::new(6, 2),
+        Dimension::new(5, 5),
+        Dimension::new(7, 3),
+        Dimension::with_padding(2, 2, 1),
+    ];
+    let mut custom = Dimension::new(3, 3);
+    custom.set_alignment(Some(Alignment::new(8, 8, false)));
+    nodes.push(custom);
+
+    let (mut inserted, rejected) = bin.insert_list(&nodes, true, choice, method);
+    assert!(rejected.is_empty());
+    for node in &nodes {
+        inserted.push(bin.insert(node, true, choice, method).unwrap());
+    }
+    for rect1 in &inserted {
+        let step = if rect1.dim().alignment().is_some() {
+            8
+        } else {
+            4
+        };
+        assert_eq!((0, 0), (rect1.x() % step, rect1.y() % step));
+        for rect2 in &inserted {
+            if rect1 != rect2 {
+                assert!(!rect1.intersects_total(rect2));
+            }
+        }
+    }
+
+    assert!(bin.insert_at(30, 30, &Dimension::new(1, 1)).is_none());
+    assert!(bin.insert_at(28, 28, &Dimension::new(1, 1)).is_some());
+
+    // sizes are rounded up to multiples of the alignment
+    let node = Dimension::new(5, 5);
+    let mut bin = GuillotineBin::new(16, 14);
+    bin.set_alignment(Some(Alignment::new(4, 4, false)));
+    assert_eq!(4, bin.count_fit(&node));
+    bin.set_alignment(Some(Alignment::new(4, 4, true)));
+    assert_eq!(2, bin.count_fit(&node));
+
+    // the rounding assigned by the bin does not affect comparisons with the original object
+    let rect = bin
+        .insert(
+            &node,
+            false,
+            RectHeuristic::BestAreaFit,
+            SplitHeuristic::MinimizeArea,
+        )
+        .unwrap();
+    assert_eq!(&node, rect.dim());
+    assert!(bin.remove_rect(&Rectangle::new(rect.x(), rect.y(), node)));
+}
+
+#[test]
+fn bin_coord_types() {
+    let choices = [
+        RectHeuristic::BestShortSideFit,
+        RectHeuristic::BestLongSideFit,
+        RectHeuristic::BestAreaFit,
+        RectHeuristic::WorstShortSideFit,
+        RectHeuristic::WorstLongSideFit,
+        RectHeuristic::WorstAreaFit,
+    ];
+    let method = SplitHeuristic::MinimizeArea;
+
+    for choice in choices {
+        let mut bin: GuillotineBin<u16> = GuillotineBin::new(64, 64);
+        let nodes = vec![Dimension::with_padding(30, 30, 1); 5];
+        let (inserted, rejected) = bin.insert_list(&nodes, true, choice, method);
+        assert_eq!(4, inserted.len());
+        assert_eq!(1, rejected.len());
+
+        bin.remove(inserted[0].id());
+        assert!(bin.insert(&nodes[4], true, choice, method).is_some());
+
+        // sizes close to the limits of the coordinate type do not overflow
+        let mut bin: GuillotineBin<u16> = GuillotineBin::new(u16::MAX, u16::MAX);
+        bin.set_spacing(2);
+        let node = Dimension::new(u16::MAX / 2 + 1, u16::MAX - 2);
+        assert!(bin.insert(&node, true, choice, method).is_some());
+        assert!(bin.insert(&node, true, choice, method).is_none());
+        let node = Dimension::new(u16::MAX / 2 - 4, 8);
+        assert!(bin.insert(&node, true, choice, method).is_some());
+
+        // objects exceeding the coordinate range including padding are rejected
+        let huge = Dimension::with_padding(u16::MAX, 8, 1);
+        assert!(bin.insert(&huge, true, choice, method).is_none());
+        let nodes = [huge, Dimension::new(8, 8)];
+        let (inserted, rejected) = bin.insert_list(&nodes, true, choice, method);
+        assert_eq!((1, vec![huge]), (inserted.len(), rejected));
+        assert!(!bin.add_obstacle(&Rectangle::new(4, 4, huge)));
+
+        let size = 4_000_000_000_000i64;
+        let mut bin: GuillotineBin<i64> = GuillotineBin::new(size, size);
+        let node = Dimension::new(size / 2, size / 2);
+        let (inserted, _) = bin.insert_list(&vec![node; 5], true, choice, method);
+        assert_eq!(4, inserted.len());
+        assert_eq!(1.0, bin.occupancy());
+        assert_eq!(
+            (size / 2) as i128 * (size / 2) as i128,
+            inserted[0].dim().area()
+        );
+    }
+}
+
+#[test]
+fn bin_float_coords() {
+    let choices = [
+        RectHeuristic::BestShortSideFit,
+        RectHeuristic::BestLongSideFit,
+        RectHeuristic::BestAreaFit,
+        RectHeuristic::WorstShortSideFit,
+        RectHeuristic::WorstLongSideFit,
+        RectHeuristic::WorstAreaFit,
+    ];
+    let method = SplitHeuristic::MinimizeArea;
+
+    for choice in choices {
+        // strips whose sizes are not exactly representable fill the bin entirely
+        let mut bin: GuillotineBin<f64> = GuillotineBin::new(1.0, 1.0);
+        let nodes = vec![Dimension::new(0.1, 1.0); 10];
+        let (inserted, rejected) = bin.insert_list(&nodes, false, choice, method);
+        assert_eq!((10, 0), (inserted.len(), rejected.len()));
+        assert!((bin.occupancy() - 1.0).abs() < 1e-9);
+        for (i, r1) in inserted.iter().enumerate() {
+            assert!(inserted[i + 1..].iter().all(|r2| !r1.intersects(r2)));
+        }
+
+        let mut bin: GuillotineBin<f32> = GuillotineBin::new(297.0, 69.8);
+        bin.set_spacing(0.3);
+        // a row of objects including padding and spacing fills the packing area entirely
+        let nodes = vec![Dimension::with_padding(98.6, 69.6, 0.1); 4];
+        let (inserted, rejected) = bin.insert_list(&nodes, true, choice, method);
+        assert_eq!((3, 1), (inserted.len(), rejected.len()));
+    }
+}
+
+#[test]
+fn bin_validate() {
+    let mut bin = GuillotineBin::new(64, 64);
+    let obstacle = Rectangle::new(20, 20, Dimension::new(8, 8));
+    assert!(bin.add_obstacle(&obstacle));
+    let nodes = vec![Dimension::with_padding(14, 10, 1); 12];
+    let choice = RectHeuristic::BestAreaFit;
+    bin.insert_list(&nodes, true, choice, SplitHeuristic::MinimizeArea);
+    assert!(bin.validate().is_empty());
+
+    // corrupted free rectangle lists are reported
+    let free = bin.rects_free[0];
+    let mut inner = free;
+    inner.dim_mut().set_width(1);
+    bin.rects_free.push(inner);
+    assert_eq!(vec![Violation::FreeOverlap(free, inner)], bin.validate());
+
+    bin.rects_free.as_mut_vec().pop();
+    let placed = Rectangle::new(20, 24, Dimension::new(8, 8));
+    bin.rects_used.push(placed);
+    let violations = bin.validate();
+    assert!(violations.contains(&Violation::Overlap(placed, obstacle)));
+}
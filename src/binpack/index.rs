@@ -0,0 +1,147 @@
+//! Provides a list of rectangles which is indexed by the identifiers of the rectangles.
+
+use super::journal::TrackedVec;
+use crate::coord::Coord;
+use crate::rectangle::Rectangle;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+use std::slice::Iter;
+
+/// A [`TrackedVec`] of rectangles with an index, which maps identifiers to the position of the
+/// first rectangle with that identifier. Lookups by identifier take constant time.
+///
+/// Appending rectangles updates the index in constant time. All other modifications rebuild
+/// the index, which takes linear time, just like the modifications themselves.
+#[derive(Clone)]
+pub(crate) struct IndexedRects<C: Coord = i32> {
+    /// The list of rectangles.
+    rects: TrackedVec<Rectangle<C>>,
+    /// Maps identifiers to the position of the first rectangle with that identifier.
+    ids: HashMap<isize, usize>,
+}
+
+impl<C: Coord> IndexedRects<C> {
+    /// Creates an empty list with space for at least `capacity` rectangles.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            rects: TrackedVec::with_capacity(capacity),
+            ids: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Starts recording modifications. Checkpoints can be nested.
+    pub(crate) fn checkpoint(&mut self) {
+        self.rects.checkpoint();
+    }
+
+    /// Undoes all modifications since the most recent checkpoint, and removes the checkpoint.
+    ///
+    /// Returns `false` if no checkpoint is active.
+    pub(crate) fn rollback(&mut self) -> bool {
+        let result = self.rects.rollback();
+        if result {
+            self.reindex();
+        }
+        result
+    }
+
+    /// Keeps all modifications since the most recent checkpoint, and removes the checkpoint.
+    ///
+    /// Returns `false` if no checkpoint is active.
+    pub(crate) fn commit(&mut self) -> bool {
+        self.rects.commit()
+    }
+
+    /// Returns the first rectangle with the specified identifier, if available.
+    pub(crate) fn find_by_id(&self, id: isize) -> Option<&Rectangle<C>> {
+        self.ids.get(&id).map(|&index| &self.rects[index])
+    }
+
+    /// Returns the position of the rectangle with the identifier and location of the given
+    /// rectangle, if available.
+    ///
+    /// Takes constant time if identifiers are unique.
+    pub(crate) fn position(&self, rect: &Rectangle<C>) -> Option<usize> {
+        let first = *self.ids.get(&rect.id())?;
+        self.rects[first..]
+            .iter()
+            .position(|r| r.id() == rect.id() && r.x() == rect.x() && r.y() == rect.y())
+            .map(|offset| first + offset)
+    }
+
+    /// Appends a rectangle to the end of the list.
+    pub(crate) fn push(&mut self, rect: Rectangle<C>) {
+        self.ids.entry(rect.id()).or_insert(self.rects.len());
+        self.rects.push(rect);
+    }
+
+    /// Removes and returns the rectangle at the given index, shifting all following rectangles.
+    pub(crate) fn remove(&mut self, index: usize) -> Rectangle<C> {
+        let rect = self.rects.remove(index);
+        self.reindex();
+        rect
+    }
+
+    /// Removes all rectangles from the list.
+    pub(crate) fn clear(&mut self) {
+        self.rects.clear();
+        self.ids.clear();
+    }
+
+    /// Modifies all rectangles by the given function. The full content of the list is recorded
+    /// if a checkpoint is active.
+    pub(crate) fn update_all<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut Rectangle<C>),
+    {
+        self.rects.as_mut_vec().iter_mut().for_each(f);
+        self.reindex();
+    }
+
+    /// Shrinks the capacity of the list with a lower limit.
+    pub(crate) fn shrink_to(&mut self, min_capacity: usize) {
+        self.rects.shrink_to(min_capacity);
+        self.ids.shrink_to(min_capacity);
+    }
+
+    /// Rebuilds the index from the current list of rectangles.
+    fn reindex(&mut self) {
+        self.ids.clear();
+        for (index, rect) in self.rects.iter().enumerate() {
+            self.ids.entry(rect.id()).or_insert(index);
+        }
+    }
+}
+
+impl<C: Coord> Deref for IndexedRects<C> {
+    type Target = Vec<Rectangle<C>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rects
+    }
+}
+
+impl<'a, C: Coord> IntoIterator for &'a IndexedRects<C> {
+    type Item = &'a Rectangle<C>;
+    type IntoIter = Iter<'a, Rectangle<C>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rects.iter()
+    }
+}
+
+impl<C: Coord> PartialEq for IndexedRects<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rects == other.rects
+    }
+}
+
+impl<C: Coord> Debug for IndexedRects<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.rects.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,51 @@
+use super::*;
+use crate::dimension::Dimension;
+
+fn indexed(ids: &[isize]) -> IndexedRects {
+    let mut result = IndexedRects::with_capacity(ids.len());
+    for (x, &id) in ids.iter().enumerate() {
+        result.push(Rectangle::new(x as i32, 0, Dimension::with_id(id, 1, 1, 0)));
+    }
+    result
+}
+
+#[test]
+fn index_find_by_id() {
+    let mut list = indexed(&[1, 2, 3, 2]);
+    assert_eq!(Some(1), list.find_by_id(2).map(|r| r.x()));
+    assert_eq!(Some(2), list.find_by_id(3).map(|r| r.x()));
+    assert_eq!(None, list.find_by_id(4));
+
+    // remaining rectangles are reindexed after removal
+    list.remove(1);
+    assert_eq!(Some(3), list.find_by_id(2).map(|r| r.x()));
+    assert_eq!(Some(2), list.find_by_id(3).map(|r| r.x()));
+
+    list.update_all(|rect| rect.translate(0, 1));
+    assert_eq!(Some(1), list.find_by_id(3).map(|r| r.y()));
+
+    list.clear();
+    assert!(list.is_empty());
+    assert_eq!(None, list.find_by_id(1));
+}
+
+#[test]
+fn index_rollback() {
+    let mut list = indexed(&[1, 2]);
+
+    list.checkpoint();
+    list.remove(0);
+    list.push(Rectangle::new(5, 0, Dimension::with_id(3, 1, 1, 0)));
+    assert_eq!(None, list.find_by_id(1));
+    assert!(list.rollback());
+    assert_eq!(Some(0), list.find_by_id(1).map(|r| r.x()));
+    assert_eq!(Some(1), list.find_by_id(2).map(|r| r.x()));
+    assert_eq!(None, list.find_by_id(3));
+
+    list.checkpoint();
+    list.clear();
+    assert_eq!(None, list.find_by_id(2));
+    assert!(list.rollback());
+    assert_eq!(Some(1), list.find_by_id(2).map(|r| r.x()));
+    assert!(!list.commit());
+}
@@ -0,0 +1,247 @@
+//! Provides a list type which records its modifications, so that they can be undone later.
+
+use std::fmt::{Debug, Formatter};
+use std::ops::Deref;
+use std::slice::Iter;
+
+/// A single modification of a [`TrackedVec`], together with the data needed to undo it.
+#[derive(Clone)]
+enum Change<T> {
+    /// An element was appended.
+    Push,
+    /// An element was inserted at the given index.
+    Insert(usize),
+    /// The given element was removed from the given index, shifting all following elements.
+    Remove(usize, T),
+    /// The given element was removed from the given index, and replaced by the last element.
+    SwapRemove(usize, T),
+    /// The element at the given index was replaced by a new value.
+    Set(usize, T),
+    /// The given number of elements was appended.
+    Extend(usize),
+    /// The given elements were removed from the end of the list.
+    Truncate(Vec<T>),
+    /// The list was modified arbitrarily. Holds the full content of the list before modification.
+    Replace(Vec<T>),
+}
+
+/// A list which records all modifications while a checkpoint is active, so that they can be
+/// undone by [`rollback`].
+///
+/// Read access is provided by dereferencing to `Vec<T>`. Modifications are only possible through
+/// the methods of this type. Most modifications are recorded with a constant overhead, except for
+/// [`retain`] and [`as_mut_vec`] which record the full content of the list.
+///
+/// [`rollback`]: TrackedVec::rollback
+/// [`retain`]: TrackedVec::retain
+/// [`as_mut_vec`]: TrackedVec::as_mut_vec
+#[derive(Clone)]
+pub(crate) struct TrackedVec<T> {
+    /// The elements of the list.
+    items: Vec<T>,
+    /// Modifications since the first active checkpoint, in chronological order.
+    changes: Vec<Change<T>>,
+    /// Length of the list of changes for each active checkpoint.
+    marks: Vec<usize>,
+}
+
+impl<T: Clone> TrackedVec<T> {
+    /// Creates an empty list.
+    pub(crate) fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates an empty list with space for at least `capacity` elements.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            changes: Vec::new(),
+            marks: Vec::new(),
+        }
+    }
+
+    /// Starts recording modifications. Checkpoints can be nested.
+    pub(crate) fn checkpoint(&mut self) {
+        self.marks.push(self.changes.len());
+    }
+
+    /// Undoes all modifications since the most recent checkpoint, and removes the checkpoint.
+    ///
+    /// Returns `false` if no checkpoint is active.
+    pub(crate) fn rollback(&mut self) -> bool {
+        let mark = match self.marks.pop() {
+            Some(mark) => mark,
+            None => return false,
+        };
+
+        while self.changes.len() > mark {
+            match self.changes.pop().expect("Change should be available") {
+                Change::Push => {
+                    self.items.pop();
+                }
+                Change::Insert(index) => {
+                    self.items.remove(index);
+                }
+                Change::Remove(index, item) => self.items.insert(index, item),
+                Change::SwapRemove(index, item) => {
+                    self.items.push(item);
+                    let last = self.items.len() - 1;
+                    self.items.swap(index, last);
+                }
+                Change::Set(index, item) => self.items[index] = item,
+                Change::Extend(count) => self.items.truncate(self.items.len() - count),
+                Change::Truncate(mut items) => self.items.append(&mut items),
+                Change::Replace(items) => self.items = items,
+            }
+        }
+
+        true
+    }
+
+    /// Keeps all modifications since the most recent checkpoint, and removes the checkpoint.
+    ///
+    /// Returns `false` if no checkpoint is active.
+    pub(crate) fn commit(&mut self) -> bool {
+        if self.marks.pop().is_none() {
+            return false;
+        }
+
+        if self.marks.is_empty() {
+            self.changes.clear();
+        }
+
+        true
+    }
+
+    /// Appends an element to the end of the list.
+    pub(crate) fn push(&mut self, item: T) {
+        self.items.push(item);
+        self.record(|| Change::Push);
+    }
+
+    /// Inserts an element at the given index, shifting all following elements.
+    pub(crate) fn insert(&mut self, index: usize, item: T) {
+        self.items.insert(index, item);
+        self.record(|| Change::Insert(index));
+    }
+
+    /// Removes and returns the element at the given index, shifting all following elements.
+    pub(crate) fn remove(&mut self, index: usize) -> T {
+        let item = self.items.remove(index);
+        self.record(|| Change::Remove(index, item.clone()));
+        item
+    }
+
+    /// Removes and returns the element at the given index, which is replaced by the last element.
+    pub(crate) fn swap_remove(&mut self, index: usize) -> T {
+        let item = self.items.swap_remove(index);
+        self.record(|| Change::SwapRemove(index, item.clone()));
+        item
+    }
+
+    /// Replaces the element at the given index, and returns the previous element.
+    pub(crate) fn set(&mut self, index: usize, item: T) -> T {
+        let old = std::mem::replace(&mut self.items[index], item);
+        self.record(|| Change::Set(index, old.clone()));
+        old
+    }
+
+    /// Modifies the element at the given index by the given function.
+    pub(crate) fn update<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        if !self.marks.is_empty() {
+            self.changes
+                .push(Change::Set(index, self.items[index].clone()));
+        }
+        f(&mut self.items[index]);
+    }
+
+    /// Moves all elements of `other` to the end of the list, leaving `other` empty.
+    pub(crate) fn append(&mut self, other: &mut Vec<T>) {
+        let count = other.len();
+        if count > 0 {
+            self.items.append(other);
+            self.record(|| Change::Extend(count));
+        }
+    }
+
+    /// Removes all elements from the list.
+    pub(crate) fn clear(&mut self) {
+        if self.marks.is_empty() {
+            self.items.clear();
+        } else if !self.items.is_empty() {
+            let items = std::mem::take(&mut self.items);
+            self.changes.push(Change::Truncate(items));
+        }
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub(crate) fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if !self.marks.is_empty() {
+            self.changes.push(Change::Replace(self.items.clone()));
+        }
+        self.items.retain(f);
+    }
+
+    /// Provides unrestricted access to the underlying list. The full content of the list is
+    /// recorded if a checkpoint is active.
+    pub(crate) fn as_mut_vec(&mut self) -> &mut Vec<T> {
+        if !self.marks.is_empty() {
+            self.changes.push(Change::Replace(self.items.clone()));
+        }
+        &mut self.items
+    }
+
+    /// Shrinks the capacity of the list with a lower limit.
+    pub(crate) fn shrink_to(&mut self, min_capacity: usize) {
+        self.items.shrink_to(min_capacity);
+    }
+
+    /// Records the given modification if a checkpoint is active.
+    fn record<F>(&mut self, change: F)
+    where
+        F: FnOnce() -> Change<T>,
+    {
+        if !self.marks.is_empty() {
+            let change = change();
+            self.changes.push(change);
+        }
+    }
+}
+
+impl<T> Deref for TrackedVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.items
+    }
+}
+
+impl<'a, T> IntoIterator for &'a TrackedVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<T: PartialEq> PartialEq for TrackedVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<T: Debug> Debug for TrackedVec<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.items.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests;
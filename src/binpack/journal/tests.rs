@@ -0,0 +1,64 @@
+use super::*;
+
+fn tracked(items: &[i32]) -> TrackedVec<i32> {
+    let mut result = TrackedVec::new();
+    for &item in items {
+        result.push(item);
+    }
+    result
+}
+
+#[test]
+fn journal_rollback() {
+    let mut list = tracked(&[1, 2, 3, 4, 5]);
+    assert!(!list.rollback());
+
+    list.checkpoint();
+    list.push(6);
+    list.insert(0, 0);
+    list.remove(2);
+    list.swap_remove(1);
+    list.set(0, 10);
+    list.update(1, |item| *item *= 2);
+    list.append(&mut vec![7, 8]);
+    list.retain(|&item| item != 4);
+    list.as_mut_vec().reverse();
+    assert_ne!(&[1, 2, 3, 4, 5], list.as_slice());
+
+    assert!(list.rollback());
+    assert_eq!(&[1, 2, 3, 4, 5], list.as_slice());
+
+    list.checkpoint();
+    list.clear();
+    assert!(list.is_empty());
+    assert!(list.rollback());
+    assert_eq!(&[1, 2, 3, 4, 5], list.as_slice());
+}
+
+#[test]
+fn journal_nested() {
+    let mut list = tracked(&[1, 2]);
+
+    list.checkpoint();
+    list.push(3);
+    list.checkpoint();
+    list.push(4);
+    assert!(list.rollback());
+    assert_eq!(&[1, 2, 3], list.as_slice());
+
+    list.checkpoint();
+    list.push(5);
+    assert!(list.commit());
+    assert_eq!(&[1, 2, 3, 5], list.as_slice());
+
+    // changes of committed inner checkpoints are undone by the outer checkpoint
+    assert!(list.rollback());
+    assert_eq!(&[1, 2], list.as_slice());
+    assert!(!list.commit());
+
+    // changes are not recorded without an active checkpoint
+    list.push(3);
+    list.update(0, |item| *item += 10);
+    assert!(list.changes.is_empty());
+    assert_eq!(&[11, 2, 3], list.as_slice());
+}
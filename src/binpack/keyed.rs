@@ -0,0 +1,229 @@
+//! Provides a bin which identifies its items by arbitrary keys instead of numeric identifiers.
+//!
+//! # Quick Start
+//!
+//! This example demonstrates how items are inserted and looked up by a string key.
+//!
+//! ```rust
+//! use binpack2d::{BinType, Dimension};
+//! use binpack2d::keyed::KeyedBin;
+//!
+//! // Create a 64x64 bin, using the "MaxRects" bin type.
+//! let mut bin = KeyedBin::new(BinType::MaxRects, 64, 64);
+//!
+//! // Items are inserted with a unique key.
+//! bin.insert("sprites/player.png".to_string(), &Dimension::new(32, 32));
+//! bin.insert("sprites/enemy.png".to_string(), &Dimension::new(48, 16));
+//!
+//! // Keys cannot be used twice.
+//! assert!(bin.insert("sprites/enemy.png".to_string(), &Dimension::new(8, 8)).is_none());
+//!
+//! // Items are looked up in constant time.
+//! if let Some(rect) = bin.find("sprites/player.png") {
+//!     println!("Player sprite was placed at (x: {}, y: {})", rect.x(), rect.y());
+//! }
+//!
+//! // Identifiers are assigned by the bin, independently of other bins.
+//! assert_eq!(Some(2), bin.find("sprites/enemy.png").map(|rect| rect.id()));
+//! ```
+
+use super::payload::{Item, PayloadPacker, Placed};
+use super::{bin_new, BinPacker, BinType};
+use crate::dimension::{Dimension, IdAllocator};
+use crate::rectangle::Rectangle;
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// A bin which maps items by unique keys of an arbitrary type.
+///
+/// Identifiers of inserted items are generated by the bin, starting at 1. The same sequence of
+/// insertions therefore always results in the same identifiers.
+pub struct KeyedBin<K> {
+    /// The bin holding all items.
+    bin: Box<dyn BinPacker>,
+    /// Maps keys to the identifier of the item in the bin.
+    keys: HashMap<K, isize>,
+    /// Generator of identifiers for inserted items.
+    ids: IdAllocator,
+}
+
+impl<K: Hash + Eq + Clone> KeyedBin<K> {
+    /// Creates an empty keyed bin with the specified [`BinType`] and size.
+    pub fn new(bin_type: BinType, width: i32, height: i32) -> Self {
+        Self::with_bin(bin_new(bin_type, width, height))
+    }
+
+    /// Creates an empty keyed bin from the given bin. Configuration of the bin, such as border
+    /// and spacing, is preserved, but all mapped rectangles are removed.
+    pub fn with_bin(mut bin: Box<dyn BinPacker>) -> Self {
+        bin.clear();
+        Self {
+            bin,
+            keys: HashMap::new(),
+            ids: IdAllocator::new(),
+        }
+    }
+
+    /// Returns the underlying bin.
+    pub fn bin(&self) -> &dyn BinPacker {
+        self.bin.as_ref()
+    }
+
+    /// Inserts a single [`Dimension`] object with the given key into the bin. The identifier of
+    /// the object is replaced by the next identifier of the bin.
+    ///
+    /// Returns a copy of the packed [`Rectangle`] if the object was inserted successfully,
+    /// or `None` otherwise. Objects are rejected if the key is already in use.
+    pub fn insert(&mut self, key: K, dim: &Dimension) -> Option<Rectangle> {
+        if self.keys.contains_key(&key) {
+            return None;
+        }
+
+        let mut node = *dim;
+        node.set_id(self.ids.peek());
+        let rect = self.bin.insert(&node)?;
+        self.keys.insert(key, self.ids.next_id());
+        Some(rect)
+    }
+
+    /// Inserts the given list of [`Item`] objects into the bin, as if [`insert_list`] was
+    /// called for their [`Dimension`] objects. The payload of each item is used as the key.
+    ///
+    /// [`insert_list`]: BinPacker::insert_list
+    ///
+    /// Identifiers are assigned to the placed items in the order of the list, so they match the
+    /// identifiers assigned by inserting the placed items one by one. The list is packed twice
+    /// for this purpose, once to determine the placed items, and once with their identifiers.
+    ///
+    /// Returns a tuple consisting of the list with all successfully placed items and a list of
+    /// rejected items. Items are rejected unchanged if their key is already in use.
+    pub fn insert_list(&mut self, items: Vec<Item<K>>) -> (Vec<Placed<K>>, Vec<Item<K>>) {
+        let mut rejected = Vec::new();
+        let mut candidates = Vec::with_capacity(items.len());
+        let mut seen = HashSet::new();
+        for item in items {
+            if self.keys.contains_key(item.payload()) || !seen.insert(item.payload().clone()) {
+                rejected.push(item);
+            } else {
+                candidates.push(item);
+            }
+        }
+
+        // Like insert, identifiers are only assigned to placed items. The placed items are
+        // determined by a trial packing, before the list is packed with the final identifiers.
+        // Packing does not depend on identifiers, so both passes result in the same layout.
+        let tagged = |ids: &[isize]| -> Vec<Item<usize>> {
+            candidates
+                .iter()
+                .zip(ids)
+                .enumerate()
+                .map(|(idx, (item, &id))| {
+                    let mut dim = *item.dim();
+                    dim.set_id(id);
+                    Item::new(dim, idx)
+                })
+                .collect()
+        };
+
+        let start = self.ids.peek();
+        let ids: Vec<isize> = (start..).take(candidates.len()).collect();
+        self.bin.checkpoint();
+        let (placed, _) = self.bin.insert_items(tagged(&ids));
+        self.bin.rollback();
+
+        // placed items are numbered first, in the order of the list
+        let mut is_placed = vec![false; candidates.len()];
+        for placed in &placed {
+            is_placed[*placed.payload()] = true;
+        }
+        let mut next_placed = start;
+        let mut next_failed = start + placed.len() as isize;
+        let ids: Vec<isize> = is_placed
+            .into_iter()
+            .map(|is_placed| {
+                let next = if is_placed {
+                    &mut next_placed
+                } else {
+                    &mut next_failed
+                };
+                *next += 1;
+                *next - 1
+            })
+            .collect();
+        let (placed, _) = self.bin.insert_items(tagged(&ids));
+
+        let mut slots: Vec<_> = candidates.into_iter().map(Some).collect();
+        let inserted = placed
+            .into_iter()
+            .filter_map(|placed| {
+                let (rect, idx) = placed.into_parts();
+                let (_, key) = slots.get_mut(idx)?.take()?.into_parts();
+                self.ids.next_id();
+                self.keys.insert(key.clone(), rect.id());
+                Some(Placed::new(rect, key))
+            })
+            .collect();
+        rejected.extend(slots.into_iter().flatten());
+        (inserted, rejected)
+    }
+
+    /// Returns the mapped rectangle with the specified key, if available. Returns `None`
+    /// otherwise.
+    pub fn find<Q>(&self, key: &Q) -> Option<Rectangle>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.keys.get(key).and_then(|&id| self.bin.find_by_id(id))
+    }
+
+    /// Returns `true` if the bin contains a mapped rectangle with the specified key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.keys.contains_key(key)
+    }
+
+    /// Removes the mapped rectangle with the specified key from the bin and makes the occupied
+    /// space available for subsequent insertions.
+    ///
+    /// Returns the removed [`Rectangle`] if available, and `None` otherwise.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<Rectangle>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let id = self.keys.remove(key)?;
+        self.bin.remove(id)
+    }
+
+    /// Removes all mapped rectangles from the bin, and restarts identifiers at 1.
+    pub fn clear(&mut self) {
+        self.bin.clear();
+        self.keys.clear();
+        self.ids.reset();
+    }
+
+    /// Returns `true` if the bin contains no mapped rectangles.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the number of mapped rectangles in the bin.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns an iterator over all keys and their mapped rectangles, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Rectangle)> + '_ {
+        self.keys
+            .iter()
+            .filter_map(|(key, &id)| self.bin.find_by_id(id).map(|rect| (key, rect)))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,147 @@
+use super::*;
+
+const BIN_TYPES: [BinType; 4] = [
+    BinType::MaxRects,
+    BinType::Guillotine,
+    BinType::Skyline,
+    BinType::Shelf,
+];
+
+#[test]
+fn keyed_insert() {
+    for bin_type in BIN_TYPES {
+        let mut bin = KeyedBin::new(bin_type, 16, 16);
+        let rect = bin.insert("a", &Dimension::with_id(7, 8, 8, 0)).unwrap();
+        assert_eq!(1, rect.id());
+        assert!(bin.insert("a", &Dimension::new(4, 4)).is_none());
+        assert!(bin.insert("b", &Dimension::new(32, 4)).is_none());
+        assert_eq!(2, bin.insert("c", &Dimension::new(8, 8)).unwrap().id());
+
+        assert_eq!(2, bin.len());
+        assert_eq!(Some(rect), bin.find("a"));
+        assert!(bin.contains_key("c"));
+        assert!(!bin.contains_key("b"));
+        assert_eq!(None, bin.find("b"));
+
+        assert_eq!(Some(rect), bin.remove("a"));
+        assert_eq!(None, bin.remove("a"));
+        assert_eq!(1, bin.bin().len());
+        assert_eq!(
+            vec!["c"],
+            bin.iter().map(|(key, _)| *key).collect::<Vec<_>>()
+        );
+
+        bin.clear();
+        assert!(bin.is_empty());
+        assert_eq!(1, bin.insert("a", &Dimension::new(8, 8)).unwrap().id());
+    }
+}
+
+#[test]
+fn keyed_insert_list() {
+    for bin_type in BIN_TYPES {
+        let mut bin = KeyedBin::new(bin_type, 16, 16);
+        bin.insert(String::from("a"), &Dimension::new(8, 8));
+
+        let items = vec![
+            Item::new(Dimension::with_id(1, 8, 8, 0), String::from("a")),
+            Item::new(Dimension::with_id(2, 8, 8, 0), String::from("b")),
+            Item::new(Dimension::with_id(3, 8, 8, 0), String::from("b")),
+            Item::new(Dimension::with_id(4, 32, 8, 0), String::from("c")),
+            Item::new(Dimension::with_id(5, 8, 8, 0), String::from("d")),
+        ];
+        let (inserted, mut rejected) = bin.insert_list(items.clone());
+        let mut keys: Vec<&str> = inserted.iter().map(|p| p.payload().as_str()).collect();
+        keys.sort_unstable();
+        assert_eq!(vec!["b", "d"], keys);
+        for placed in &inserted {
+            assert_eq!(Some(*placed.rect()), bin.find(placed.payload()));
+        }
+
+        // rejected items are returned unchanged
+        rejected.sort_by_key(|item| item.dim().id());
+        assert_eq!(
+            vec![items[0].clone(), items[2].clone(), items[3].clone()],
+            rejected
+        );
+        assert_eq!(3, bin.len());
+    }
+}
+
+#[test]
+fn keyed_insert_list_ids() {
+    for bin_type in BIN_TYPES {
+        let mut bin = KeyedBin::new(bin_type, 16, 16);
+        let items = vec![
+            Item::new(Dimension::new(8, 8), "a"),
+            Item::new(Dimension::new(32, 8), "b"),
+            Item::new(Dimension::new(8, 8), "c"),
+            Item::new(Dimension::new(16, 32), "d"),
+            Item::new(Dimension::new(8, 8), "e"),
+        ];
+        let (inserted, rejected) = bin.insert_list(items);
+        assert_eq!(3, inserted.len());
+        assert_eq!(2, rejected.len());
+
+        // rejected items do not consume identifiers
+        let ids: Vec<_> = ["a", "c", "e"]
+            .iter()
+            .map(|key| bin.find(key).map(|rect| rect.id()))
+            .collect();
+        assert_eq!(vec![Some(1), Some(2), Some(3)], ids);
+        assert_eq!(4, bin.insert("f", &Dimension::new(8, 8)).unwrap().id());
+    }
+}
+
+#[test]
+fn keyed_insert_list_layout() {
+    let nodes: Vec<_> = (0..10)
+        .map(|i: i32| Dimension::new(3 + (i * 4) % 11, 2 + (i * 2) % 9))
+        .collect();
+    for bin_type in BIN_TYPES {
+        // the layout matches a single insert_list of the underlying bin
+        let mut expected = bin_new(bin_type, 16, 16);
+        let (expected, rejected) = expected.insert_list(&nodes);
+        assert!(!rejected.is_empty());
+
+        let mut bin = KeyedBin::new(bin_type, 16, 16);
+        let items = nodes
+            .iter()
+            .enumerate()
+            .map(|(key, node)| Item::new(*node, key))
+            .collect();
+        let (inserted, _) = bin.insert_list(items);
+        let mut actual: Vec<_> = inserted
+            .iter()
+            .map(|p| (p.rect().x(), p.rect().y(), p.rect().width()))
+            .collect();
+        let mut expected: Vec<_> = expected.iter().map(|r| (r.x(), r.y(), r.width())).collect();
+        actual.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn keyed_deterministic_ids() {
+    let nodes = [
+        Dimension::new(4, 4),
+        Dimension::new(8, 4),
+        Dimension::new(4, 8),
+    ];
+    for bin_type in BIN_TYPES {
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let mut bin = KeyedBin::with_bin(bin_new(bin_type, 16, 16));
+            for (key, node) in nodes.iter().enumerate() {
+                bin.insert(key, node);
+            }
+            let mut result: Vec<(usize, isize)> =
+                bin.iter().map(|(&key, rect)| (key, rect.id())).collect();
+            result.sort_unstable();
+            ids.push(result);
+        }
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3)], ids[0]);
+        assert_eq!(ids[0], ids[1]);
+    }
+}
@@ -30,7 +30,9 @@
 //! let mut bin = MaxRectsBin::new(1024, 1024);
 //!
 //! // Perform the bin packing operation on the list of items, using tetris-style placement rule.
-//! let (inserted, rejected) = bin.insert_list(&items_to_place, Heuristic::BottomLeftRule);
+//! // Passing `true` to the "rotate" parameter allows the method to rotate items by 90 degrees
+//! // if it results in a better placement.
+//! let (inserted, rejected) = bin.insert_list(&items_to_place, true, Heuristic::BottomLeftRule);
 //!
 //! // Let's see if our item with id=9528 was successfully inserted...
 //! if let Some(rect) = &bin.find_by_id(9528) {
@@ -57,12 +59,19 @@
 //! println!("Occupancy of the bin: {:.1} %", bin.occupancy() * 100.0);
 //! ```
 
-use crate::binpack::BinError;
+use crate::binpack::{BinError, PackError};
 use std::fmt::{Display, Formatter};
+use std::slice;
 use std::slice::Iter;
 
-use super::{visualize_bin, BinPacker};
-use crate::dimension::Dimension;
+use super::growth::{grow_bin, GrowthPolicy};
+use super::index::IndexedRects;
+use super::journal::TrackedVec;
+use super::sizing::pack_strip_with;
+use super::validate::{self, Violation};
+use super::{align_placement, pack_bins_with, packing_area, rect_at, visualize_bin, BinPacker};
+use crate::coord::{self, Coord};
+use crate::dimension::{Alignment, Dimension};
 use crate::rectangle::Rectangle;
 
 /// List of supported heuristic rules for *MAXRECTS* data structures that can be used when deciding
@@ -88,32 +97,49 @@ pub enum Heuristic {
 ///
 /// It can be used to pack multiple rectangles of arbitrary size into a "bin" of rectangular shape
 /// with the goal to add as many rectangles as possible into the bin.
+///
+/// Coordinates are of the [`Coord`] type `C`, which is `i32` by default.
 #[derive(Clone, Debug, PartialEq)]
-pub struct MaxRectsBin {
+pub struct MaxRectsBin<C: Coord = i32> {
     /// Horizontal dimension of the bin.
-    bin_width: i32,
+    bin_width: C,
     /// Vertical dimension of the bin.
-    bin_height: i32,
+    bin_height: C,
     /// Keeps track of used areas within the bin.
-    rects_used: Vec<Rectangle>,
+    rects_used: IndexedRects<C>,
     /// Keeps track of free areas within the bin.
-    rects_free: Vec<Rectangle>,
+    rects_free: TrackedVec<Rectangle<C>>,
+    /// Keeps track of areas within the bin which are not available for packing.
+    obstacles: TrackedVec<Rectangle<C>>,
+    /// Margin along the bin boundaries.
+    border: C,
+    /// Gap between neighbouring rectangles.
+    spacing: C,
+    /// Optional alignment of rectangle positions.
+    alignment: Option<Alignment<C>>,
+    /// Bin size at the time of each active checkpoint.
+    checkpoints: Vec<(C, C)>,
 
     // Internally used to speed up packing operations
     new_rects_free_size: usize,
     // Internally used to speed up packing operations
-    new_rects_free: Vec<Rectangle>,
+    new_rects_free: Vec<Rectangle<C>>,
+
+    /// Optionally enlarges the bin if objects do not fit.
+    growth_policy: Option<GrowthPolicy<C>>,
 
     /// Implicitly used for the methods defined by the `BinPacker` trait.
     default_heuristic: Heuristic,
+    /// Implicitly used for the methods defined by the `BinPacker` trait.
+    default_rotate: bool,
 }
 
-impl BinPacker for MaxRectsBin {
-    fn width(&self) -> i32 {
+impl<C: Coord> BinPacker<C> for MaxRectsBin<C> {
+    fn width(&self) -> C {
         self.bin_width
     }
 
-    fn height(&self) -> i32 {
+    fn height(&self) -> C {
         self.bin_height
     }
 
@@ -122,31 +148,71 @@ impl BinPacker for MaxRectsBin {
         self.rects_used.shrink_to(capacity.max(4));
         self.rects_free.clear();
         self.rects_free.shrink_to((capacity * 4).max(16));
-        self.rects_free.push(Rectangle::new(
-            0,
-            0,
-            Dimension::with_id(0, self.bin_width, self.bin_height, 0),
-        ));
+        // obstacles are preserved
+        self.rebuild_free_list();
     }
 
     fn grow(&mut self, dw: u32, dh: u32) {
-            if dw > 0 {
+        if dw > 0 {
+            let old_right = self.area().right_total();
+            self.bin_width = self.bin_width.saturating_add(C::from_u32(dw));
+            let area = self.area();
+            let dw = area.right_total() - old_right;
+
+            // free rectangles at the right border are extended into the new area
+            for rect in self.rects_free.as_mut_vec() {
+                if coord::approx_eq(rect.right_total(), old_right) {
+                    let width = rect.width() + (area.right_total() - rect.right_total());
+                    rect.dim_mut().set_width(width);
+                }
+            }
+
+            if dw > C::ZERO && area.height_total() > C::ZERO {
                 self.rects_free.push(Rectangle::new(
-                    self.bin_width,
-                    0,
-                    Dimension::with_id(0, dw as i32, self.bin_height, 0)
+                    old_right,
+                    area.y_total(),
+                    Dimension::with_id(0, dw, area.height_total(), C::ZERO),
                 ));
-                self.bin_width += dw as i32;
             }
+        }
 
-            if dh > 0 {
+        if dh > 0 {
+            let old_bottom = self.area().bottom_total();
+            self.bin_height = self.bin_height.saturating_add(C::from_u32(dh));
+            let area = self.area();
+            let dh = area.bottom_total() - old_bottom;
+
+            // free rectangles at the bottom border are extended into the new area
+            for rect in self.rects_free.as_mut_vec() {
+                if coord::approx_eq(rect.bottom_total(), old_bottom) {
+                    let height = rect.height() + (area.bottom_total() - rect.bottom_total());
+                    rect.dim_mut().set_height(height);
+                }
+            }
+
+            if dh > C::ZERO && area.width_total() > C::ZERO {
                 self.rects_free.push(Rectangle::new(
-                    0,
-                    self.bin_height,
-                    Dimension::with_id(0, self.bin_width, dh as i32, 0)
+                    area.x_total(),
+                    old_bottom,
+                    Dimension::with_id(0, area.width_total(), dh, C::ZERO),
                 ));
-                self.bin_height += dh as i32;
             }
+        }
+
+        // extended free rectangles may contain other free rectangles
+        let rects = &self.rects_free;
+        let redundant: Vec<bool> = (0..rects.len())
+            .map(|i| {
+                (0..rects.len()).any(|j| {
+                    j != i
+                        && rects[j].contains_total(&rects[i])
+                        && (j < i || !rects[i].contains_total(&rects[j]))
+                })
+            })
+            .collect();
+        let mut redundant = redundant.into_iter();
+        self.rects_free
+            .retain(|_| !redundant.next().unwrap_or(false));
     }
 
     fn shrink(&mut self, binary: bool) {
@@ -154,78 +220,149 @@ impl BinPacker for MaxRectsBin {
             return;
         }
 
-        let mut min_x = i32::MAX;
-        let mut min_y = i32::MAX;
-        let mut max_x = i32::MIN;
-        let mut max_y = i32::MIN;
-
-        // finding borders
-        for rect in &self.rects_used {
-            min_x = min_x.min(rect.x_total());
-            min_y = min_y.min(rect.y_total());
-            max_x = max_x.max(rect.x_total() + rect.width_total());
-            max_y = max_y.max(rect.y_total() + rect.height_total());
+        let mut min_x = C::MAX;
+        let mut min_y = C::MAX;
+        let mut max_x = C::MIN;
+        let mut max_y = C::MIN;
+
+        // finding borders, the spacing of rectangles is not needed at the bin boundary
+        for rect in self.rects_used.iter().chain(&self.obstacles) {
+            min_x = coord::min(min_x, rect.x_total());
+            min_y = coord::min(min_y, rect.y_total());
+            max_x = coord::max(max_x, rect.right_total() - rect.dim().spacing);
+            max_y = coord::max(max_y, rect.bottom_total() - rect.dim().spacing);
         }
 
-        let mut new_width = max_x - min_x;
-        let mut new_height = max_y - min_y;
+        // obstacles may be located within the bin border
+        let shift_x = coord::max(min_x.saturating_sub(self.border), C::ZERO);
+        let shift_y = coord::max(min_y.saturating_sub(self.border), C::ZERO);
+        let mut new_width = coord::min(
+            (max_x - shift_x).saturating_add(self.border),
+            self.bin_width,
+        );
+        let mut new_height = coord::min(
+            (max_y - shift_y).saturating_add(self.border),
+            self.bin_height,
+        );
 
         if binary {
             // attempt to shrink to the next lower power of two
+            let two = C::ONE + C::ONE;
             let mut cur_width = self.bin_width;
-            while new_width <= (cur_width >> 1) {
-                cur_width >>= 1;
+            while new_width <= cur_width / two {
+                cur_width = cur_width / two;
             }
             new_width = cur_width;
 
             let mut cur_height = self.bin_height;
-            while new_height <= (cur_height >> 1) {
-                cur_height >>= 1;
+            while new_height <= cur_height / two {
+                cur_height = cur_height / two;
             }
             new_height = cur_height;
         }
 
         // adjusting rectangle positions
         if new_width != self.bin_width || new_height != self.bin_height {
-            if min_x > 0 || min_y > 0 {
-                for rect in &mut self.rects_used {
-                    rect.set_x_total(rect.x_total() - min_x);
-                    rect.set_y_total(rect.y_total() - min_y);
-                }
-                for rect in &mut self.rects_free {
-                    rect.set_x_total(rect.x_total() - min_x);
-                    rect.set_y_total(rect.y_total() - min_y);
-                }
-                for rect in &mut self.new_rects_free {
-                    rect.set_x_total(rect.x_total() - min_x);
-                    rect.set_y_total(rect.y_total() - min_y);
+            if shift_x > C::ZERO || shift_y > C::ZERO {
+                self.rects_used.update_all(|rect| {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
+                });
+                for rect in self.obstacles.as_mut_vec() {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
                 }
             }
 
             self.bin_width = new_width;
             self.bin_height = new_height;
+
+            // free areas outside of the new bin boundaries are no longer available
+            self.rebuild_free_list();
         }
     }
 
-    fn insert(&mut self, dim: &Dimension) -> Option<Rectangle> {
-        self.insert(dim, self.default_heuristic)
+    fn border(&self) -> C {
+        self.border
+    }
+
+    fn set_border(&mut self, border: C) {
+        self.border = coord::max(border, C::ZERO);
+        self.clear();
+    }
+
+    fn spacing(&self) -> C {
+        self.spacing
+    }
+
+    fn set_spacing(&mut self, spacing: C) {
+        self.spacing = coord::max(spacing, C::ZERO);
+        self.clear();
+    }
+
+    fn growth_policy(&self) -> Option<GrowthPolicy<C>> {
+        self.growth_policy
+    }
+
+    fn set_growth_policy(&mut self, policy: Option<GrowthPolicy<C>>) {
+        self.growth_policy = policy;
     }
 
-    fn insert_list(&mut self, nodes: &[Dimension]) -> (Vec<Rectangle>, Vec<Dimension>) {
-        self.insert_list(nodes, self.default_heuristic)
+    fn insert(&mut self, dim: &Dimension<C>) -> Option<Rectangle<C>> {
+        self.insert(dim, self.default_rotate, self.default_heuristic)
+    }
+
+    fn insert_list(&mut self, nodes: &[Dimension<C>]) -> (Vec<Rectangle<C>>, Vec<Dimension<C>>) {
+        self.insert_list(nodes, self.default_rotate, self.default_heuristic)
+    }
+
+    fn insert_at(&mut self, x: C, y: C, dim: &Dimension<C>) -> Option<Rectangle<C>> {
+        let alignment = dim.alignment().or(self.alignment);
+        let rect = rect_at(x, y, &dim.to_spaced(self.spacing), &self.area(), alignment)?;
+
+        // free areas are always covered by at least one maximal free rectangle
+        if !self.rects_free.iter().any(|r| r.contains_total(&rect)) {
+            return None;
+        }
+
+        self.place_rect(&rect);
+        Some(rect)
+    }
+
+    fn preview_insert(&self, dim: &Dimension<C>) -> Option<Rectangle<C>> {
+        let dim = &dim.to_spaced(self.spacing);
+        if dim.is_empty() || !self.fits_bin(dim, self.default_rotate) {
+            return None;
+        }
+
+        let (_, _, result) = self.score_rect(dim, self.default_rotate, self.default_heuristic);
+        result
+    }
+
+    fn count_fit(&self, dim: &Dimension<C>) -> usize {
+        let dim = &dim.to_spaced(self.spacing);
+        let mut bin = self.scratch_bin(self.default_heuristic);
+        let mut count = 0;
+        while bin
+            .try_insert(dim, self.default_rotate, self.default_heuristic)
+            .is_some()
+        {
+            count += 1;
+        }
+        count
     }
 
     fn occupancy(&self) -> f32 {
-        if self.bin_width == 0 || self.bin_height == 0 {
+        if self.bin_width == C::ZERO || self.bin_height == C::ZERO {
             return 0.0;
         }
 
-        let area: i64 = self.rects_used.iter().map(|r| r.dim().area()).sum();
+        let area: C::Wide = self.rects_used.iter().map(|r| r.dim().area()).sum();
 
-        area as f32 / (self.bin_width * self.bin_height) as f32
+        (C::wide_to_f64(area) / C::wide_to_f64(self.bin_width.mul_wide(self.bin_height))) as f32
     }
 
-    fn as_slice(&self) -> &[Rectangle] {
+    fn as_slice(&self) -> &[Rectangle<C>] {
         &self.rects_used
     }
 
@@ -237,15 +374,71 @@ impl BinPacker for MaxRectsBin {
         self.rects_used.len()
     }
 
-    fn iter(&self) -> Iter<'_, Rectangle> {
+    fn iter(&self) -> Iter<'_, Rectangle<C>> {
         self.rects_used.iter()
     }
 
-    fn find_by_id(&self, id: isize) -> Option<Rectangle> {
-        self.rects_used
-            .iter()
-            .find(|&n| n.dim().id() == id)
-            .map(|r| r.to_owned())
+    fn find_by_id(&self, id: isize) -> Option<Rectangle<C>> {
+        self.rects_used.find_by_id(id).copied()
+    }
+
+    fn remove_rect(&mut self, rect: &Rectangle<C>) -> bool {
+        if let Some(index) = self.rects_used.position(rect) {
+            let rect = self.rects_used.remove(index);
+            self.release_area(&rect);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        self.checkpoints.push((self.bin_width, self.bin_height));
+        self.rects_used.checkpoint();
+        self.rects_free.checkpoint();
+        self.obstacles.checkpoint();
+    }
+
+    fn rollback(&mut self) -> bool {
+        if let Some((width, height)) = self.checkpoints.pop() {
+            self.bin_width = width;
+            self.bin_height = height;
+            self.rects_used.rollback();
+            self.rects_free.rollback();
+            self.obstacles.rollback();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn commit(&mut self) -> bool {
+        if self.checkpoints.pop().is_some() {
+            self.rects_used.commit();
+            self.rects_free.commit();
+            self.obstacles.commit();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn validate(&self) -> Vec<Violation<C>> {
+        let mut violations = validate::check_layout(
+            self.bin_width,
+            self.bin_height,
+            self.border,
+            &self.rects_used,
+            &self.obstacles,
+        );
+        violations.extend(validate::check_free_bounds(
+            &self.area(),
+            &self.rects_free,
+            &self.rects_used,
+            &self.obstacles,
+        ));
+        violations.extend(validate::check_free_maximal(&self.rects_free));
+        violations
     }
 
     fn visualize(&self) -> String {
@@ -257,11 +450,11 @@ impl BinPacker for MaxRectsBin {
     }
 }
 
-impl MaxRectsBin {
+impl<C: Coord> MaxRectsBin<C> {
     /// Creates an empty bin of the given size.
     ///
     /// Minimum width and height of a bin is 1.
-    pub fn new(width: i32, height: i32) -> Self {
+    pub fn new(width: C, height: C) -> Self {
         Self::with_capacity(width, height, 4)
     }
 
@@ -269,21 +462,24 @@ impl MaxRectsBin {
     /// of mapped rectangle to improve performance.
     ///
     /// Minimum width and height of a bin is 1.
-    pub fn with_capacity(width: i32, height: i32, capacity: usize) -> Self {
+    pub fn with_capacity(width: C, height: C, capacity: usize) -> Self {
         let mut result = Self {
-            bin_width: width.max(1),
-            bin_height: height.max(1),
-            rects_used: Vec::with_capacity(capacity.max(4)),
-            rects_free: Vec::with_capacity((capacity * 4).max(4 * 4)),
+            bin_width: coord::max(width, C::ONE),
+            bin_height: coord::max(height, C::ONE),
+            rects_used: IndexedRects::with_capacity(capacity.max(4)),
+            rects_free: TrackedVec::with_capacity((capacity * 4).max(4 * 4)),
+            obstacles: TrackedVec::new(),
+            border: C::ZERO,
+            spacing: C::ZERO,
+            alignment: None,
+            checkpoints: Vec::new(),
             new_rects_free_size: 0,
             new_rects_free: Vec::new(),
             default_heuristic: Heuristic::BestShortSideFit,
+            default_rotate: false,
+            growth_policy: None,
         };
-        result.rects_free.push(Rectangle::new(
-            0,
-            0,
-            Dimension::with_id(0, result.bin_width, result.bin_height, 0),
-        ));
+        result.rebuild_free_list();
 
         result
     }
@@ -306,29 +502,171 @@ impl MaxRectsBin {
         self.default_heuristic = rule;
     }
 
+    /// Returns whether objects may be rotated by 90 degrees by the [`BinPacker`] trait's
+    /// [`insert`] and [`insert_list`] methods.
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn default_rotate(&self) -> bool {
+        self.default_rotate
+    }
+
+    /// Can be used to override whether objects may be rotated by 90 degrees by the [`BinPacker`]
+    /// trait's [`insert`] and [`insert_list`] methods. Rotation is disabled by default.
+    ///
+    /// The [`Rotation`] policy of the individual objects takes precedence over this setting.
+    ///
+    /// [`Rotation`]: crate::dimension::Rotation
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn set_default_rotate(&mut self, rotate: bool) {
+        self.default_rotate = rotate;
+    }
+
+    /// Returns the [`Alignment`] of rectangle positions, if available.
+    pub fn alignment(&self) -> Option<Alignment<C>> {
+        self.alignment
+    }
+
+    /// Specifies the [`Alignment`] of rectangle positions. The alignment of individual objects
+    /// takes precedence over this setting. Specify `None` to place objects at arbitrary
+    /// positions. Positions are not aligned by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{Alignment, Dimension};
+    /// use binpack2d::maxrects::{Heuristic, MaxRectsBin};
+    ///
+    /// let mut bin = MaxRectsBin::new(32, 32);
+    /// bin.set_alignment(Some(Alignment::new(4, 4, true)));
+    ///
+    /// let rule = Heuristic::BestShortSideFit;
+    /// let rect = bin.insert(&Dimension::new(6, 6), false, rule).unwrap();
+    /// assert_eq!((0, 0), (rect.x(), rect.y()));
+    ///
+    /// // The size of the first object has been rounded up to 8x8
+    /// let rect = bin.insert(&Dimension::new(3, 8), false, rule).unwrap();
+    /// assert_eq!(0, rect.x() % 4);
+    /// assert!(rect.x() >= 8 || rect.y() >= 8);
+    /// ```
+    pub fn set_alignment(&mut self, alignment: Option<Alignment<C>>) {
+        self.alignment = alignment;
+    }
+
+    /// Returns the list of obstacles, which mark areas of the bin that are not available for
+    /// packing.
+    pub fn obstacles(&self) -> &[Rectangle<C>] {
+        &self.obstacles
+    }
+
+    /// Marks the area of the given rectangle as not available for packing.
+    ///
+    /// Obstacles are not considered mapped rectangles and are therefore not included in the
+    /// results of [`len`], [`as_slice`] or [`occupancy`]. They are preserved when the bin is
+    /// cleared.
+    ///
+    /// [`len`]: BinPacker::len
+    /// [`as_slice`]: BinPacker::as_slice
+    /// [`occupancy`]: BinPacker::occupancy
+    ///
+    /// Returns `true` if the obstacle was added, and `false` if it is empty, exceeds the
+    /// bin boundaries or intersects mapped rectangles.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use binpack2d::{BinPacker, Dimension, Rectangle};
+    /// use binpack2d::maxrects::{Heuristic, MaxRectsBin};
+    ///
+    /// let mut bin = MaxRectsBin::new(16, 16);
+    /// assert!(bin.add_obstacle(&Rectangle::new(4, 4, Dimension::new(8, 8))));
+    ///
+    /// // Only the space around the obstacle is available
+    /// let rule = Heuristic::BestShortSideFit;
+    /// assert!(bin.insert(&Dimension::new(12, 12), false, rule).is_none());
+    /// assert!(bin.insert(&Dimension::new(16, 4), false, rule).is_some());
+    /// assert_eq!(1, bin.len());
+    /// ```
+    pub fn add_obstacle(&mut self, rect: &Rectangle<C>) -> bool {
+        if rect.is_empty()
+            || !rect.is_total_valid()
+            || rect.x_total() < C::ZERO
+            || rect.y_total() < C::ZERO
+            || rect.right_total() > self.bin_width
+            || rect.bottom_total() > self.bin_height
+            || self.rects_used.iter().any(|r| r.intersects_total(rect))
+        {
+            return false;
+        }
+
+        self.split_free_list(rect);
+        self.obstacles.push(rect.to_owned());
+        true
+    }
+
+    /// Removes the specified obstacle from the bin and makes its area available for packing.
+    ///
+    /// Returns `true` if the obstacle was found and removed, and `false` otherwise.
+    pub fn remove_obstacle(&mut self, rect: &Rectangle<C>) -> bool {
+        if let Some(index) = self.obstacles.iter().position(|r| r == rect) {
+            let rect = self.obstacles.remove(index);
+            self.release_area(&rect);
+            true
+        } else {
+            false
+        }
+    }
+
     /// Inserts a single [`Dimension`] object into the bin.
     ///
     /// `dim` refers to the object to be packed into the bin.
     ///
+    /// `rotate` indicates whether the object may be rotated by 90 degrees if the rotated
+    /// placement scores better than the upright placement. The [`Rotation`] policy of the object
+    /// takes precedence over this parameter.
+    ///
+    /// [`Rotation`]: crate::dimension::Rotation
+    ///
     /// `rule` specifies the rectangle placement rule to use for the packing operation.
     ///
     /// Returns a copy of the packed [`Rectangle`] if the object was inserted successful,
     /// or `None` otherwise.
-    pub fn insert(&mut self, dim: &Dimension, rule: Heuristic) -> Option<Rectangle> {
+    pub fn insert(
+        &mut self,
+        dim: &Dimension<C>,
+        rotate: bool,
+        rule: Heuristic,
+    ) -> Option<Rectangle<C>> {
+        let dim = &dim.to_spaced(self.spacing);
+        loop {
+            let result = self.try_insert(dim, rotate, rule);
+            if result.is_some() || !grow_bin(self, self.growth_policy, slice::from_ref(dim), rotate)
+            {
+                return result;
+            }
+        }
+    }
+
+    /// Inserts a single `Dimension` object into the bin without enlarging the bin.
+    fn try_insert(
+        &mut self,
+        dim: &Dimension<C>,
+        rotate: bool,
+        rule: Heuristic,
+    ) -> Option<Rectangle<C>> {
         // Empty or too big dimension objects are always rejected
-        if dim.is_empty()
-            || dim.width_total() > self.bin_width
-            || dim.height_total() > self.bin_height
-        {
+        if dim.is_empty() || !self.fits_bin(dim, rotate) {
             return None;
         }
 
         let (_, _, result) = match rule {
-            Heuristic::BestShortSideFit => self.find_bssf(dim),
-            Heuristic::BestLongSideFit => self.find_blsf(dim),
-            Heuristic::BestAreaFit => self.find_baf(dim),
-            Heuristic::BottomLeftRule => self.find_blr(dim),
-            Heuristic::ContactPointRule => self.find_cpr(dim),
+            Heuristic::BestShortSideFit => self.find_bssf(dim, rotate),
+            Heuristic::BestLongSideFit => self.find_blsf(dim, rotate),
+            Heuristic::BestAreaFit => self.find_baf(dim, rotate),
+            Heuristic::BottomLeftRule => self.find_blr(dim, rotate),
+            Heuristic::ContactPointRule => self.find_cpr(dim, rotate),
         };
 
         if let Some(new_node) = &result {
@@ -345,6 +683,12 @@ impl MaxRectsBin {
     /// `nodes` specifies the list of [`Dimension`] objects to insert. All successfully inserted
     /// objects will be removed from the list in the process.
     ///
+    /// `rotate` indicates whether objects may be rotated by 90 degrees if the rotated
+    /// placement scores better than the upright placement. The [`Rotation`] policy of the objects
+    /// takes precedence over this parameter.
+    ///
+    /// [`Rotation`]: crate::dimension::Rotation
+    ///
     /// `rule` specifies the rectangle placement rule to use for the packing operations.
     ///
     /// Returns a list with all successfully inserted [`Rectangle`] objects.
@@ -355,20 +699,41 @@ impl MaxRectsBin {
     /// [`insert`]: MaxRectsBin::insert
     pub fn insert_list(
         &mut self,
-        nodes: &[Dimension],
+        nodes: &[Dimension<C>],
+        rotate: bool,
         rule: Heuristic,
-    ) -> (Vec<Rectangle>, Vec<Dimension>) {
+    ) -> (Vec<Rectangle<C>>, Vec<Dimension<C>>) {
+        let nodes: Vec<_> = nodes.iter().map(|n| n.to_spaced(self.spacing)).collect();
+        let (mut inserted, mut rejected) = self.try_insert_list(&nodes, rotate, rule);
+        while !rejected.is_empty() && grow_bin(self, self.growth_policy, &rejected, rotate) {
+            let (mut new_nodes, remaining) = self.try_insert_list(&rejected, rotate, rule);
+            inserted.append(&mut new_nodes);
+            rejected = remaining;
+        }
+
+        let rejected = rejected.into_iter().map(|n| n.to_spaced(C::ZERO)).collect();
+        (inserted, rejected)
+    }
+
+    /// Attempts to insert the given list of `Dimension` objects into the bin without enlarging
+    /// the bin.
+    fn try_insert_list(
+        &mut self,
+        nodes: &[Dimension<C>],
+        rotate: bool,
+        rule: Heuristic,
+    ) -> (Vec<Rectangle<C>>, Vec<Dimension<C>>) {
         let mut inserted = Vec::with_capacity(nodes.len());
         let mut rejected = nodes.to_vec();
 
         while !rejected.is_empty() {
-            let mut best_score1 = i32::MAX;
-            let mut best_score2 = i32::MAX;
+            let mut best_score1 = C::WIDE_MAX;
+            let mut best_score2 = C::WIDE_MAX;
             let mut best_index = None;
             let mut best_node = None;
 
             for (i, dim) in rejected.iter().enumerate() {
-                let (score1, score2, new_node) = self.score_rect(dim, rule);
+                let (score1, score2, new_node) = self.score_rect(dim, rotate, rule);
 
                 if score1 < best_score1 || (score1 == best_score1 && score2 < best_score2) {
                     best_score1 = score1;
@@ -396,26 +761,38 @@ impl MaxRectsBin {
     ///
     /// Returns a tuple consisting of the primary and secondary placement scores, as well as
     /// the `Rectangle` structure where the requested `Dimension` can be placed.
-    fn score_rect(&self, dim: &Dimension, rule: Heuristic) -> (i32, i32, Option<Rectangle>) {
+    fn score_rect(
+        &self,
+        dim: &Dimension<C>,
+        rotate: bool,
+        rule: Heuristic,
+    ) -> (C::Wide, C::Wide, Option<Rectangle<C>>) {
         let (mut score1, mut score2, new_node) = match rule {
-            Heuristic::BestShortSideFit => self.find_bssf(dim),
-            Heuristic::BestLongSideFit => self.find_blsf(dim),
-            Heuristic::BestAreaFit => self.find_baf(dim),
-            Heuristic::BottomLeftRule => self.find_blr(dim),
-            Heuristic::ContactPointRule => self.find_cpr(dim),
+            Heuristic::BestShortSideFit => self.find_bssf(dim, rotate),
+            Heuristic::BestLongSideFit => self.find_blsf(dim, rotate),
+            Heuristic::BestAreaFit => self.find_baf(dim, rotate),
+            Heuristic::BottomLeftRule => self.find_blr(dim, rotate),
+            Heuristic::ContactPointRule => self.find_cpr(dim, rotate),
         };
 
         // Cannot fit the current rectangle.
         if new_node.is_none() {
-            score1 = i32::MAX;
-            score2 = i32::MAX;
+            score1 = C::WIDE_MAX;
+            score2 = C::WIDE_MAX;
         }
 
         (score1, score2, new_node)
     }
 
     /// Places the given rectangle into the bin.
-    fn place_rect(&mut self, rect: &Rectangle) {
+    fn place_rect(&mut self, rect: &Rectangle<C>) {
+        self.split_free_list(rect);
+
+        self.rects_used.push(rect.to_owned());
+    }
+
+    /// Removes the area of the given rectangle from the list of free rectangles.
+    fn split_free_list(&mut self, rect: &Rectangle<C>) {
         let mut idx = 0usize;
         while idx < self.rects_free.len() {
             let node = self.rects_free[idx];
@@ -427,28 +804,212 @@ impl MaxRectsBin {
         }
 
         self.prune_free_list();
+    }
 
-        self.rects_used.push(rect.to_owned());
+    /// Makes the area of the given rectangle available for packing, after it has been removed
+    /// from the lists of used rectangles or obstacles.
+    ///
+    /// Maximal free rectangles which overlap the released area can only consist of the released
+    /// area and of free rectangles which overlap or touch it. They are recomputed within the
+    /// bounding box of these rectangles, which keeps the effort local to the released area.
+    fn release_area(&mut self, rect: &Rectangle<C>) {
+        // obstacles may extend into the bin border
+        let area = self.area();
+        let left = coord::max(rect.x_total(), area.x_total());
+        let top = coord::max(rect.y_total(), area.y_total());
+        let right = coord::min(rect.right_total(), area.right_total());
+        let bottom = coord::min(rect.bottom_total(), area.bottom_total());
+        if !coord::approx_lt(left, right) || !coord::approx_lt(top, bottom) {
+            return;
+        }
+        let released = Rectangle::new(
+            left,
+            top,
+            Dimension::with_id(0, right - left, bottom - top, C::ZERO),
+        );
+
+        let touches = |r: &Rectangle<C>| {
+            coord::approx_le(r.x_total(), right)
+                && coord::approx_le(left, r.right_total())
+                && coord::approx_le(r.y_total(), bottom)
+                && coord::approx_le(top, r.bottom_total())
+        };
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (left, top, right, bottom);
+        for free in self.rects_free.iter().filter(|r| touches(r)) {
+            min_x = coord::min(min_x, free.x_total());
+            min_y = coord::min(min_y, free.y_total());
+            max_x = coord::max(max_x, free.right_total());
+            max_y = coord::max(max_y, free.bottom_total());
+        }
+        let bounds = Rectangle::new(
+            min_x,
+            min_y,
+            Dimension::with_id(0, max_x - min_x, max_y - min_y, C::ZERO),
+        );
+
+        // free rectangles within the bounding box are split by the occupied areas as usual
+        let occupied: Vec<_> = self
+            .rects_used
+            .iter()
+            .chain(&self.obstacles)
+            .filter(|r| r.intersects_total(&bounds))
+            .copied()
+            .collect();
+        let mut new_rects = vec![bounds];
+        for rect in &occupied {
+            self.split_local_list(&mut new_rects, rect);
+        }
+        new_rects.retain(|r| r.intersects_total(&released));
+
+        // adjacent free rectangles may have become part of larger free rectangles
+        let mut idx = 0usize;
+        while idx < self.rects_free.len() {
+            let free = &self.rects_free[idx];
+            if touches(free) && new_rects.iter().any(|r| r.contains_total(free)) {
+                self.rects_free.swap_remove(idx);
+            } else {
+                idx += 1;
+            }
+        }
+        self.rects_free.append(&mut new_rects);
+    }
+
+    /// Splits the given list of free rectangles by the given rectangle, just like
+    /// [`split_free_list`] does for the free rectangles of the bin.
+    ///
+    /// [`split_free_list`]: MaxRectsBin::split_free_list
+    fn split_local_list(&mut self, rects_free: &mut Vec<Rectangle<C>>, rect: &Rectangle<C>) {
+        let mut idx = 0usize;
+        while idx < rects_free.len() {
+            let node = rects_free[idx];
+            if self.split_free_node(&node, rect) {
+                rects_free.swap_remove(idx);
+                continue;
+            }
+            idx += 1;
+        }
+
+        for free in rects_free.iter() {
+            self.new_rects_free.retain(|r| !free.contains_total(r));
+        }
+        rects_free.append(&mut self.new_rects_free);
+    }
+
+    /// Creates a bin for simulating insertions with the given rule, which shares the
+    /// configuration and the free rectangles of this bin, but holds no mapped rectangles.
+    ///
+    /// Mapped rectangles and obstacles are only needed to score contacts by the
+    /// [`Heuristic::ContactPointRule`], and are carried over as obstacles in this case.
+    fn scratch_bin(&self, rule: Heuristic) -> Self {
+        let mut rects_free = self.rects_free.to_vec();
+        let mut obstacles = if rule == Heuristic::ContactPointRule {
+            self.rects_used
+                .iter()
+                .chain(&self.obstacles)
+                .copied()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut result = Self {
+            bin_width: self.bin_width,
+            bin_height: self.bin_height,
+            rects_used: IndexedRects::with_capacity(4),
+            rects_free: TrackedVec::with_capacity(rects_free.len()),
+            obstacles: TrackedVec::with_capacity(obstacles.len()),
+            border: self.border,
+            spacing: self.spacing,
+            alignment: self.alignment,
+            checkpoints: Vec::new(),
+            new_rects_free_size: 0,
+            new_rects_free: Vec::new(),
+            default_heuristic: self.default_heuristic,
+            default_rotate: self.default_rotate,
+            growth_policy: None,
+        };
+        result.rects_free.append(&mut rects_free);
+        result.obstacles.append(&mut obstacles);
+
+        result
+    }
+
+    /// Recreates the list of maximal free rectangles from the lists of used rectangles and
+    /// obstacles.
+    fn rebuild_free_list(&mut self) {
+        self.rects_free.clear();
+        let area = self.area();
+        if !area.is_empty() {
+            self.rects_free.push(area);
+        }
+
+        for i in 0..self.obstacles.len() {
+            let rect = self.obstacles[i];
+            self.split_free_list(&rect);
+        }
+        for i in 0..self.rects_used.len() {
+            let rect = self.rects_used[i];
+            self.split_free_list(&rect);
+        }
+    }
+
+    /// Returns whether the given `Dimension` fits into the empty bin, optionally rotated by
+    /// 90 degrees.
+    fn fits_bin(&self, dim: &Dimension<C>, rotate: bool) -> bool {
+        let area = self.area();
+        dim.fits_into(area.width(), area.height(), rotate)
+    }
+
+    /// Returns the placement candidates for the given `Dimension` as tuples of the free area
+    /// where it can be placed, the oriented `Dimension` and whether it was rotated.
+    ///
+    /// Free areas start at the aligned position of the `Dimension` if an [`Alignment`] applies.
+    fn candidates<'a>(
+        &'a self,
+        dim: &'a Dimension<C>,
+        rotate: bool,
+    ) -> impl Iterator<Item = (Rectangle<C>, Dimension<C>, bool)> + 'a {
+        let alignment = dim.alignment().or(self.alignment);
+        let rects_free: &[Rectangle<C>] = if dim.is_total_valid() {
+            &self.rects_free
+        } else {
+            &[]
+        };
+        rects_free.iter().flat_map(move |free| {
+            dim.orientations(rotate).filter_map(move |(dim, rotated)| {
+                let (rect, dim) = align_placement(free, &dim, alignment)?;
+                let fits = coord::approx_le(dim.width_total(), rect.width_total())
+                    && coord::approx_le(dim.height_total(), rect.height_total());
+                fits.then_some((rect, dim, rotated))
+            })
+        })
+    }
+
+    /// Returns the area of the bin which is available for packing rectangles.
+    fn area(&self) -> Rectangle<C> {
+        packing_area(self.bin_width, self.bin_height, self.border, self.spacing)
     }
 
     /// Attempts to find the best rectangle position in the bin, using the [`Heuristic::BottomLeftRule`] rule.
-    fn find_blr(&self, dim: &Dimension) -> (i32, i32, Option<Rectangle>) {
+    fn find_blr(
+        &self,
+        dim: &Dimension<C>,
+        rotate: bool,
+    ) -> (C::Wide, C::Wide, Option<Rectangle<C>>) {
         let mut result = None;
 
-        let mut best_x = i32::MAX;
-        let mut best_y = i32::MAX;
-        for rect in &self.rects_free {
-            if rect.width_total() >= dim.width_total() && rect.height_total() >= dim.height_total()
-            {
-                let top_y = rect.y_total() + dim.height_total();
-
-                if top_y < best_y || (top_y == best_y && rect.x_total() < best_x) {
-                    let best_node = result.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                    best_node.set_location_total(rect.x_total(), rect.y_total());
-                    best_node.dim_mut().set_dimension(dim.width(), dim.height());
-                    best_x = rect.x_total();
-                    best_y = top_y;
-                }
+        let mut best_x = C::WIDE_MAX;
+        let mut best_y = C::WIDE_MAX;
+        for (rect, dim, rotated) in self.candidates(dim, rotate) {
+            let top_y = rect.y_total().widen() + dim.height_total().widen();
+            let x = rect.x_total().widen();
+
+            if top_y < best_y || (top_y == best_y && x < best_x) {
+                let mut best_node = Rectangle::new(C::ZERO, C::ZERO, dim);
+                best_node.set_location_total(rect.x_total(), rect.y_total());
+                best_node.set_rotated(rotated);
+                result = Some(best_node);
+                best_x = x;
+                best_y = top_y;
             }
         }
 
@@ -456,28 +1017,30 @@ impl MaxRectsBin {
     }
 
     /// Attempts to find the best rectangle position in the bin, using the [`Heuristic::BestShortSideFit`] rule.
-    fn find_bssf(&self, dim: &Dimension) -> (i32, i32, Option<Rectangle>) {
+    fn find_bssf(
+        &self,
+        dim: &Dimension<C>,
+        rotate: bool,
+    ) -> (C::Wide, C::Wide, Option<Rectangle<C>>) {
         let mut result = None;
 
-        let mut best_short_side_fit = i32::MAX;
-        let mut best_long_size_fit = i32::MAX;
-        for rect in &self.rects_free {
-            if rect.width_total() >= dim.width_total() && rect.height_total() >= dim.height_total()
+        let mut best_short_side_fit = C::WIDE_MAX;
+        let mut best_long_size_fit = C::WIDE_MAX;
+        for (rect, dim, rotated) in self.candidates(dim, rotate) {
+            let leftover_h = coord::abs_diff(rect.width_total(), dim.width_total());
+            let leftover_v = coord::abs_diff(rect.height_total(), dim.height_total());
+            let short_side_fit = coord::min(leftover_h, leftover_v);
+            let long_side_fit = coord::max(leftover_h, leftover_v);
+
+            if short_side_fit < best_short_side_fit
+                || (short_side_fit == best_short_side_fit && long_side_fit < best_long_size_fit)
             {
-                let leftover_h = rect.width_total().abs_diff(dim.width_total()) as i32;
-                let leftover_v = rect.height_total().abs_diff(dim.height_total()) as i32;
-                let short_side_fit = leftover_h.min(leftover_v);
-                let long_side_fit = leftover_h.max(leftover_v);
-
-                if short_side_fit < best_short_side_fit
-                    || (short_side_fit == best_short_side_fit && long_side_fit < best_long_size_fit)
-                {
-                    let best_node = result.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                    best_node.set_location_total(rect.x_total(), rect.y_total());
-                    best_node.dim_mut().set_dimension(dim.width(), dim.height());
-                    best_short_side_fit = short_side_fit;
-                    best_long_size_fit = long_side_fit;
-                }
+                let mut best_node = Rectangle::new(C::ZERO, C::ZERO, dim);
+                best_node.set_location_total(rect.x_total(), rect.y_total());
+                best_node.set_rotated(rotated);
+                result = Some(best_node);
+                best_short_side_fit = short_side_fit;
+                best_long_size_fit = long_side_fit;
             }
         }
 
@@ -485,28 +1048,30 @@ impl MaxRectsBin {
     }
 
     /// Attempts to find the best rectangle position in the bin, using the [`Heuristic::BestLongSideFit`] rule.
-    fn find_blsf(&self, dim: &Dimension) -> (i32, i32, Option<Rectangle>) {
+    fn find_blsf(
+        &self,
+        dim: &Dimension<C>,
+        rotate: bool,
+    ) -> (C::Wide, C::Wide, Option<Rectangle<C>>) {
         let mut result = None;
 
-        let mut best_short_side_fit = i32::MAX;
-        let mut best_long_size_fit = i32::MAX;
-        for rect in &self.rects_free {
-            if rect.width_total() >= dim.width_total() && rect.height_total() >= dim.height_total()
+        let mut best_short_side_fit = C::WIDE_MAX;
+        let mut best_long_size_fit = C::WIDE_MAX;
+        for (rect, dim, rotated) in self.candidates(dim, rotate) {
+            let leftover_h = coord::abs_diff(rect.width_total(), dim.width_total());
+            let leftover_v = coord::abs_diff(rect.height_total(), dim.height_total());
+            let short_side_fit = coord::min(leftover_h, leftover_v);
+            let long_side_fit = coord::max(leftover_h, leftover_v);
+
+            if long_side_fit < best_long_size_fit
+                || (long_side_fit == best_long_size_fit && short_side_fit < best_short_side_fit)
             {
-                let leftover_h = rect.width_total().abs_diff(dim.width_total()) as i32;
-                let leftover_v = rect.height_total().abs_diff(dim.height_total()) as i32;
-                let short_side_fit = leftover_h.min(leftover_v);
-                let long_side_fit = leftover_h.max(leftover_v);
-
-                if long_side_fit < best_long_size_fit
-                    || (long_side_fit == best_long_size_fit && short_side_fit < best_short_side_fit)
-                {
-                    let best_node = result.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                    best_node.set_location_total(rect.x_total(), rect.y_total());
-                    best_node.dim_mut().set_dimension(dim.width(), dim.height());
-                    best_short_side_fit = short_side_fit;
-                    best_long_size_fit = long_side_fit;
-                }
+                let mut best_node = Rectangle::new(C::ZERO, C::ZERO, dim);
+                best_node.set_location_total(rect.x_total(), rect.y_total());
+                best_node.set_rotated(rotated);
+                result = Some(best_node);
+                best_short_side_fit = short_side_fit;
+                best_long_size_fit = long_side_fit;
             }
         }
 
@@ -514,92 +1079,96 @@ impl MaxRectsBin {
     }
 
     /// Attempts to find the best rectangle position in the bin, using the [`Heuristic::BestAreaFit`] rule.
-    fn find_baf(&self, dim: &Dimension) -> (i32, i32, Option<Rectangle>) {
+    fn find_baf(
+        &self,
+        dim: &Dimension<C>,
+        rotate: bool,
+    ) -> (C::Wide, C::Wide, Option<Rectangle<C>>) {
         let mut result = None;
 
-        let mut best_area_fit = i64::MAX;
-        let mut best_short_side_fit = i64::MAX;
-        // let mut best_fit = (u32::MAX, 0u32);
-        for rect in &self.rects_free {
-            if rect.width_total() >= dim.width_total() && rect.height_total() >= dim.height_total()
+        let mut best_area_fit = C::WIDE_MAX;
+        let mut best_short_side_fit = C::WIDE_MAX;
+        for (rect, dim, rotated) in self.candidates(dim, rotate) {
+            let leftover_h = coord::abs_diff(rect.width_total(), dim.width_total());
+            let leftover_v = coord::abs_diff(rect.height_total(), dim.height_total());
+            let short_side_fit = coord::min(leftover_h, leftover_v);
+
+            let area_fit = rect.dim().area_total() - dim.area_total();
+            if area_fit < best_area_fit
+                || (area_fit == best_area_fit && short_side_fit < best_short_side_fit)
             {
-                let leftover_h = rect.width_total().abs_diff(dim.width_total());
-                let leftover_v = rect.height_total().abs_diff(dim.height_total());
-                let short_side_fit = leftover_h.min(leftover_v) as i64;
-
-                let area_fit = rect.dim().area_total() - dim.area_total();
-                if area_fit < best_area_fit
-                    || (area_fit == best_area_fit && short_side_fit < best_short_side_fit)
-                {
-                    let best_node = result.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                    best_node.set_location_total(rect.x_total(), rect.y_total());
-                    best_node.dim_mut().set_dimension(dim.width(), dim.height());
-                    best_area_fit = area_fit;
-                    best_short_side_fit = short_side_fit;
-                }
+                let mut best_node = Rectangle::new(C::ZERO, C::ZERO, dim);
+                best_node.set_location_total(rect.x_total(), rect.y_total());
+                best_node.set_rotated(rotated);
+                result = Some(best_node);
+                best_area_fit = area_fit;
+                best_short_side_fit = short_side_fit;
             }
         }
 
-        (best_area_fit as i32, best_short_side_fit as i32, result)
+        (best_area_fit, best_short_side_fit, result)
     }
 
     /// Attempts to find the best rectangle position in the bin, using the [`Heuristic::ContactPointRule`] rule.
-    fn find_cpr(&self, dim: &Dimension) -> (i32, i32, Option<Rectangle>) {
+    fn find_cpr(
+        &self,
+        dim: &Dimension<C>,
+        rotate: bool,
+    ) -> (C::Wide, C::Wide, Option<Rectangle<C>>) {
         let mut result = None;
 
-        let mut best_score = -1;
-        for rect in &self.rects_free {
-            if rect.width_total() >= dim.width_total() && rect.height_total() >= dim.height_total()
-            {
-                let score = self.contact_point_score_node(
-                    rect.x_total(),
-                    rect.y_total(),
-                    dim.width_total(),
-                    dim.height_total(),
-                );
-                if score > best_score {
-                    let best_node = result.get_or_insert_with(|| Rectangle::new(0, 0, *dim));
-                    best_node.set_location_total(rect.x_total(), rect.y_total());
-                    best_node.dim_mut().set_dimension(dim.width(), dim.height());
-                    best_score = score;
-                }
+        let mut best_score = None;
+        for (rect, dim, rotated) in self.candidates(dim, rotate) {
+            let score = self.contact_point_score_node(
+                rect.x_total(),
+                rect.y_total(),
+                dim.width_total(),
+                dim.height_total(),
+            );
+            if best_score.is_none_or(|best| score > best) {
+                let mut best_node = Rectangle::new(C::ZERO, C::ZERO, dim);
+                best_node.set_location_total(rect.x_total(), rect.y_total());
+                best_node.set_rotated(rotated);
+                result = Some(best_node);
+                best_score = Some(score);
             }
         }
 
         // Reversing score since we are minimizing, but for contact point score, bigger is better.
-        best_score = -best_score;
+        let best_score = -best_score.unwrap_or_default();
 
         // No secondary score needed
-        (best_score, 0, result)
+        (best_score, C::Wide::default(), result)
     }
 
     /// Computes the placement score for the "CP" variant.
-    fn contact_point_score_node(&self, x: i32, y: i32, width: i32, height: i32) -> i32 {
-        let mut score = 0;
-
-        if x == 0 || x + width == self.bin_width {
-            score += height;
+    ///
+    /// The score is computed with the [`Wide`] type, which does not overflow for any number of
+    /// contacts.
+    ///
+    /// [`Wide`]: Coord::Wide
+    fn contact_point_score_node(&self, x: C, y: C, width: C, height: C) -> C::Wide {
+        let mut score = C::Wide::default();
+        let right = x.saturating_add(width);
+        let bottom = y.saturating_add(height);
+
+        let area = self.area();
+        if coord::approx_eq(x, area.x_total()) || coord::approx_eq(right, area.right_total()) {
+            score = score + height.widen();
         }
-        if y == 0 || y + height == self.bin_height {
-            score += width;
+        if coord::approx_eq(y, area.y_total()) || coord::approx_eq(bottom, area.bottom_total()) {
+            score = score + width.widen();
         }
 
-        for rect in &self.rects_used {
-            if rect.x_total() == x + width || rect.x_total() + rect.width_total() == x {
-                score += Self::common_interval_length(
-                    rect.y_total(),
-                    rect.y_total() + rect.height_total(),
-                    y,
-                    y + height,
-                );
+        for rect in self.rects_used.iter().chain(&self.obstacles) {
+            if coord::approx_eq(rect.x_total(), right) || coord::approx_eq(rect.right_total(), x) {
+                score = score
+                    + Self::common_interval_length(rect.y_total(), rect.bottom_total(), y, bottom);
             }
-            if rect.y_total() == y + height || rect.y_total() + rect.height_total() == y {
-                score += Self::common_interval_length(
-                    rect.x_total(),
-                    rect.x_total() + rect.width_total(),
-                    x,
-                    x + width,
-                );
+            if coord::approx_eq(rect.y_total(), bottom) || coord::approx_eq(rect.bottom_total(), y)
+            {
+                score = score
+                    + Self::common_interval_length(rect.x_total(), rect.right_total(), x, right);
             }
         }
 
@@ -607,28 +1176,29 @@ impl MaxRectsBin {
     }
 
     /// Returns whether the specified free node was split.
-    fn split_free_node(&mut self, free: &Rectangle, used: &Rectangle) -> bool {
+    fn split_free_node(&mut self, free: &Rectangle<C>, used: &Rectangle<C>) -> bool {
+        // Right and bottom edges saturate at the greatest coordinate value instead of overflowing
+        let (free_right, free_bottom) = (free.right_total(), free.bottom_total());
+        let (used_right, used_bottom) = (used.right_total(), used.bottom_total());
+
         // Test with SAT if the rectangles even intersect
-        if used.x_total() >= free.x_total() + free.width_total()
-            || used.x_total() + used.width_total() <= free.x_total()
-            || used.y_total() >= free.y_total() + free.height_total()
-            || used.y_total() + used.height_total() <= free.y_total()
+        if !coord::approx_lt(used.x_total(), free_right)
+            || !coord::approx_lt(free.x_total(), used_right)
+            || !coord::approx_lt(used.y_total(), free_bottom)
+            || !coord::approx_lt(free.y_total(), used_bottom)
         {
             return false;
         }
 
         // We add up to four new free rectangles to the free rectangles list below. None of these
         // four newly added free rectangles can overlap any other three, so keep a mark of them
-        // to avoid testing them against each other.
+        // to avoid testing them against each other. Strips which are not wider than the
+        // comparison tolerance are not added.
         self.new_rects_free_size = self.new_rects_free.len();
 
-        if used.x_total() < free.x_total() + free.width_total()
-            && used.x_total() + used.width_total() > free.x_total()
-        {
+        if used.x_total() < free_right && used_right > free.x_total() {
             // New node at the top side of the used node
-            if used.y_total() > free.y_total()
-                && used.y_total() < free.y_total() + free.height_total()
-            {
+            if coord::approx_lt(free.y_total(), used.y_total()) && used.y_total() < free_bottom {
                 let mut new_node = free.to_owned();
                 let new_y = new_node.y_total();
                 new_node.dim_mut().set_height(used.y_total() - new_y);
@@ -636,23 +1206,17 @@ impl MaxRectsBin {
             }
 
             // New node at the bottom side of the used node.
-            if used.y_total() + used.height_total() < free.y_total() + free.height_total() {
+            if coord::approx_lt(used_bottom, free_bottom) {
                 let mut new_node = free.to_owned();
-                new_node.set_y_total(used.y_total() + used.height_total());
-                new_node.dim_mut().set_height(
-                    free.y_total() + free.height_total() - (used.y_total() + used.height_total()),
-                );
+                new_node.set_y_total(used_bottom);
+                new_node.dim_mut().set_height(free_bottom - used_bottom);
                 self.insert_new_free_rect(&new_node);
             }
         }
 
-        if used.y_total() < free.y_total() + free.height_total()
-            && used.y_total() + used.height_total() > free.y_total()
-        {
+        if used.y_total() < free_bottom && used_bottom > free.y_total() {
             // New node at the left side of the used node.
-            if used.x_total() > free.x_total()
-                && used.x_total() < free.x_total() + free.width_total()
-            {
+            if coord::approx_lt(free.x_total(), used.x_total()) && used.x_total() < free_right {
                 let mut new_node = free.to_owned();
                 let new_x = new_node.x_total();
                 new_node.dim_mut().set_width(used.x_total() - new_x);
@@ -660,12 +1224,10 @@ impl MaxRectsBin {
             }
 
             // New node at the right side of the used node.
-            if used.x_total() + used.width_total() < free.x_total() + free.width_total() {
+            if coord::approx_lt(used_right, free_right) {
                 let mut new_node = free.to_owned();
-                new_node.set_x_total(used.x_total() + used.width_total());
-                new_node.dim_mut().set_width(
-                    free.x_total() + free.width_total() - (used.x_total() + used.width_total()),
-                );
+                new_node.set_x_total(used_right);
+                new_node.dim_mut().set_width(free_right - used_right);
                 self.insert_new_free_rect(&new_node);
             }
         }
@@ -673,9 +1235,9 @@ impl MaxRectsBin {
         true
     }
 
-    fn insert_new_free_rect(&mut self, new_node: &Rectangle) {
-        debug_assert!(new_node.width_total() > 0);
-        debug_assert!(new_node.height_total() > 0);
+    fn insert_new_free_rect(&mut self, new_node: &Rectangle<C>) {
+        debug_assert!(new_node.width_total() > C::ZERO);
+        debug_assert!(new_node.height_total() > C::ZERO);
 
         let mut i = 0usize;
         while i < self.new_rects_free_size {
@@ -727,29 +1289,23 @@ impl MaxRectsBin {
 
         // Merge new and old free rectangles to the group of old free rectangles.
         self.rects_free.append(&mut self.new_rects_free);
-
-        #[cfg(debug_assertions)]
-        for (i, rect1) in self.rects_free.iter().enumerate() {
-            for rect2 in self.rects_free.iter().skip(i + 1) {
-                debug_assert!(!rect1.contains_total(rect2));
-                debug_assert!(!rect2.contains_total(rect1));
-            }
-        }
     }
 
     /// Returns 0 if the two intervals i1 and i2 are disjoint, or the length of their overlap, otherwise.
-    fn common_interval_length(i1start: i32, i1end: i32, i2start: i32, i2end: i32) -> i32 {
-        if i1end < i2start || i2end < i1start {
-            0
+    ///
+    /// Overlaps of up to [`Coord::EPSILON`] are considered disjoint.
+    fn common_interval_length(i1start: C, i1end: C, i2start: C, i2end: C) -> C::Wide {
+        if coord::approx_le(i1end, i2start) || coord::approx_le(i2end, i1start) {
+            C::Wide::default()
         } else {
-            i1end.min(i2end) - i1start.max(i2start)
+            coord::min(i1end, i2end).widen() - coord::max(i1start, i2start).widen()
         }
     }
 }
 
-impl<Idx> std::ops::Index<Idx> for MaxRectsBin
+impl<C: Coord, Idx> std::ops::Index<Idx> for MaxRectsBin<C>
 where
-    Idx: std::slice::SliceIndex<[Rectangle]>,
+    Idx: std::slice::SliceIndex<[Rectangle<C>]>,
 {
     type Output = Idx::Output;
 
@@ -758,7 +1314,7 @@ where
     }
 }
 
-impl Display for MaxRectsBin {
+impl<C: Coord> Display for MaxRectsBin<C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
@@ -773,18 +1329,24 @@ impl Display for MaxRectsBin {
 /// A convenience function that attempts to insert a given list of `Dimension` objects into a
 /// variable number of bins.
 ///
-/// New bins are created on demand, using the given heuristic `rule`.
+/// New bins are created on demand, using the given heuristic `rule`. Specify `true` for `rotate`
+/// to allow rotating objects by 90 degrees if it results in a better placement.
 ///
 /// Specify true for `optimize` to use [`insert_list`] internally, which results in an improved
 /// bin layout but at the cost of a worse processing performance.
 ///
 /// [`insert_list`]: MaxRectsBin::insert_list
 ///
+/// Nodes with the same [`group`] key are always placed into the same bin.
+///
+/// [`group`]: Dimension::group
+///
 /// Returns a list of bins with the packed rectangle nodes as a [`Result`] value.
 ///
 /// # Errors
 ///
-/// A [`BinError`] is returned for nodes which are either empty or too big for the bin.
+/// A [`PackError`] is returned if any nodes could not be packed, listing each rejected node with
+/// the reason of rejection. All other nodes are still packed into the bins provided by the error.
 ///
 /// # Examples
 /// ```
@@ -796,7 +1358,7 @@ impl Display for MaxRectsBin {
 /// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
 ///
 /// // Returned list of bin object contains all nodes, placed according to the given heuristic rule
-/// let bins = pack_bins(&nodes, 16, 12, Heuristic::BestShortSideFit, true)
+/// let bins = pack_bins(&nodes, 16, 12, false, Heuristic::BestShortSideFit, true)
 ///     .expect("Items should not be rejected");
 ///
 /// assert_eq!(1, bins.len());
@@ -806,121 +1368,67 @@ pub fn pack_bins(
     nodes: &[Dimension],
     bin_width: i32,
     bin_height: i32,
+    rotate: bool,
     rule: Heuristic,
     optimized: bool,
-) -> Result<Vec<MaxRectsBin>, BinError> {
-    if optimized {
-        pack_bins_list(nodes, bin_width, bin_height, rule)
-    } else {
-        pack_bins_single(nodes, bin_width, bin_height, rule)
-    }
-}
-
-/// Inserts nodes via insert_list().
-fn pack_bins_list(
-    nodes: &[Dimension],
-    bin_width: i32,
-    bin_height: i32,
-    rule: Heuristic,
-) -> Result<Vec<MaxRectsBin>, BinError> {
-    let mut bins = Vec::new();
-    if nodes.is_empty() || bin_width == 0 || bin_height == 0 {
-        return Ok(bins);
-    }
-
-    // first pass is done separately to avoid a (potentially) costly clone operation
-    let mut bin = MaxRectsBin::new(bin_width, bin_height);
-    let (inserted, mut rejected) = bin.insert_list(nodes, rule);
-
-    if inserted.is_empty() && !rejected.is_empty() {
-        // remaining nodes are too big and will be silently skipped
-        rejected.clear();
-    }
-
-    if !inserted.is_empty() {
-        bins.push(bin);
-    }
-
-    // subsequent passes are done in a loop
-    let mut nodes_left = rejected;
-    while !nodes_left.is_empty() {
-        let mut bin = MaxRectsBin::new(bin_width, bin_height);
-        let (inserted, mut rejected) = bin.insert_list(&nodes_left, rule);
-
-        if inserted.is_empty() && !rejected.is_empty() {
-            // remaining nodes are too big or too small
-            let result = rejected
-                .iter()
-                .map(|r| {
-                    if r.width_total() == 0 || r.height_total() == 0 {
-                        BinError::ItemTooSmall
-                    } else if r.width_total() > bin_width || r.height_total() > bin_height {
-                        BinError::ItemTooBig
-                    } else {
-                        BinError::Unspecified
-                    }
-                })
-                .next();
-            if let Some(result) = result {
-                return Err(result);
-            } else {
-                // Should not happen
-                eprintln!("pack_bins(): Could not insert remaining items");
-                rejected.clear();
-            }
-        }
-
-        if !inserted.is_empty() {
-            bins.push(bin);
-        }
-
-        // preparing for next iteration
-        nodes_left.clear();
-        nodes_left.append(&mut rejected);
-    }
-
-    Ok(bins)
+) -> Result<Vec<MaxRectsBin>, PackError<MaxRectsBin>> {
+    let result = pack_bins_with(nodes, bin_width, bin_height, optimized, |width, height| {
+        let mut bin = MaxRectsBin::new(width, height);
+        bin.set_default_rotate(rotate);
+        bin.set_default_rule(rule);
+        Box::new(bin)
+    });
+
+    result
+        .map(|bins| bins.into_iter().map(|bin| *bin).collect())
+        .map_err(|err| err.map_bins(|bin| *bin))
 }
 
-/// Inserts nodes via insert().
-fn pack_bins_single(
+/// A convenience function that packs a given list of `Dimension` objects into a strip of the
+/// given width, and reduces the height of the strip as much as possible.
+///
+/// The strip is packed via [`insert_list`], using the given heuristic `rule`. Specify `true` for `rotate`
+/// to allow rotating objects by 90 degrees if it results in a better placement.
+///
+/// [`insert_list`]: MaxRectsBin::insert_list
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value. The height of the bin
+/// is the final strip height.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too wide for the strip.
+///
+/// # Examples
+/// ```
+/// use binpack2d::binpack::BinPacker;
+/// use binpack2d::binpack::maxrects::{Heuristic, pack_strip};
+/// use binpack2d::dimension::Dimension;
+///
+/// // Defining three items of different size
+/// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
+///
+/// // Returned strip contains all nodes, placed according to the given heuristic rule
+/// let strip = pack_strip(&nodes, 8, false, Heuristic::BottomLeftRule)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(8, strip.width());
+/// assert_eq!(3, strip.len());
+/// ```
+pub fn pack_strip(
     nodes: &[Dimension],
-    bin_width: i32,
-    bin_height: i32,
+    strip_width: i32,
+    rotate: bool,
     rule: Heuristic,
-) -> Result<Vec<MaxRectsBin>, BinError> {
-    let mut bins = Vec::new();
-    if nodes.is_empty() || bin_width == 0 || bin_height == 0 {
-        return Ok(bins);
-    }
-
-    for node in nodes {
-        if node.is_empty() {
-            return Err(BinError::ItemTooSmall);
-        } else if node.width_total() > bin_width || node.height_total() > bin_height {
-            return Err(BinError::ItemTooBig);
-        }
-
-        // try inserting node into existing bins
-        let mut inserted = false;
-        for bin in &mut bins {
-            if bin.insert(node, rule).is_some() {
-                inserted = true;
-                break;
-            }
-        }
-
-        // create new bin if needed
-        if !inserted {
-            bins.push(MaxRectsBin::new(bin_width, bin_height));
-            if let Some(bin) = bins.last_mut() {
-                bin.insert(node, rule)
-                    .expect("Object should fit into the bin");
-            }
-        }
-    }
-
-    Ok(bins)
+) -> Result<MaxRectsBin, BinError> {
+    let strip = pack_strip_with(nodes, strip_width, |width, height| {
+        let mut bin = MaxRectsBin::new(width, height);
+        bin.set_default_rotate(rotate);
+        bin.set_default_rule(rule);
+        Box::new(bin)
+    })?;
+
+    Ok(*strip)
 }
 
 #[cfg(test)]
@@ -3,7 +3,7 @@ use super::*;
 #[test]
 fn bin_shrink() {
     let mut bin = MaxRectsBin::new(16, 16);
-    bin.insert(&Dimension::new(6, 7), Heuristic::BestShortSideFit);
+    bin.insert(&Dimension::new(6, 7), false, Heuristic::BestShortSideFit);
     bin.shrink(true);
     assert_eq!(8, bin.width());
     assert_eq!(8, bin.height());
@@ -11,11 +11,14 @@ fn bin_shrink() {
     bin.shrink(false);
     assert_eq!(6, bin.width());
     assert_eq!(7, bin.height());
+
+    let result = bin.insert(&Dimension::new(1, 1), false, Heuristic::BestShortSideFit);
+    assert!(result.is_none());
 }
 
 #[test]
 fn bin_insert() {
-    let nodes = vec![
+    let nodes = [
         Dimension::with_padding(2, 4, 0),
         Dimension::with_padding(6, 4, 1),
         Dimension::with_padding(10, 3, 1),
@@ -32,7 +35,7 @@ fn bin_insert() {
     assert_eq!(16, bin.height());
 
     for node in nodes.iter() {
-        bin.insert(node, rule);
+        bin.insert(node, false, rule);
     }
 
     for rect1 in bin.iter() {
@@ -62,7 +65,7 @@ fn bin_insert_list() {
     assert_eq!(16, bin.width());
     assert_eq!(16, bin.height());
 
-    let (inserted, rejected) = bin.insert_list(&nodes, rule);
+    let (inserted, rejected) = bin.insert_list(&nodes, false, rule);
     assert_eq!(nodes.len(), inserted.len() + rejected.len());
 
     for rect1 in bin.iter() {
@@ -73,3 +76,363 @@ fn bin_insert_list() {
         }
     }
 }
+
+#[test]
+fn bin_insert_rotate() {
+    let mut bin = MaxRectsBin::new(6, 4);
+    bin.insert(&Dimension::new(4, 4), false, Heuristic::BestShortSideFit);
+
+    // item fits into the remaining space only if rotated
+    let dim = Dimension::new(4, 2);
+    assert!(bin
+        .insert(&dim, false, Heuristic::BestShortSideFit)
+        .is_none());
+
+    let rect = bin.insert(&dim, true, Heuristic::BestShortSideFit);
+    assert!(rect.is_some());
+    let rect = rect.unwrap();
+    assert_eq!(4, rect.x());
+    assert_eq!(2, rect.width());
+    assert_eq!(4, rect.height());
+    assert!(rect.is_rotated());
+    assert_eq!(dim, rect.original_dim());
+
+    for rule in [
+        Heuristic::BestShortSideFit,
+        Heuristic::BestLongSideFit,
+        Heuristic::BestAreaFit,
+        Heuristic::BottomLeftRule,
+        Heuristic::ContactPointRule,
+    ] {
+        let nodes = [Dimension::new(2, 5), Dimension::new(2, 5)];
+        let mut bin = MaxRectsBin::new(10, 2);
+        let (inserted, rejected) = bin.insert_list(&nodes, true, rule);
+        assert_eq!(2, inserted.len());
+        assert!(rejected.is_empty());
+    }
+}
+
+#[test]
+fn bin_remove() {
+    let mut bin = MaxRectsBin::new(8, 8);
+    let rects: Vec<Rectangle> = (0..4)
+        .filter_map(|_| bin.insert(&Dimension::new(8, 2), false, Heuristic::BottomLeftRule))
+        .collect();
+    assert_eq!(4, rects.len());
+
+    // two adjacent strips are merged into a single free rectangle
+    for rect in rects.iter().filter(|r| r.y() == 2 || r.y() == 4) {
+        assert!(bin.remove_rect(rect));
+    }
+
+    let rect = bin.insert(&Dimension::new(8, 4), false, Heuristic::BestShortSideFit);
+    assert_eq!(Some((0, 2)), rect.map(|r| (r.x(), r.y())));
+}
+
+#[test]
+fn bin_remove_local() {
+    let nodes: Vec<_> = (0..40)
+        .map(|i: i32| Dimension::with_id(i as isize, 3 + (i * 7) % 11, 2 + (i * 5) % 9, i % 2))
+        .collect();
+    let mut bin = MaxRectsBin::new(64, 64);
+    let (inserted, _) = bin.insert_list(&nodes, true, Heuristic::BestShortSideFit);
+    assert!(inserted.len() > 20);
+
+    // the free list updated around removed rectangles matches a full rebuild
+    for rect in inserted.iter().step_by(3) {
+        assert!(bin.remove(rect.id()).is_some());
+        assert!(bin.validate().is_empty());
+
+        let mut expected = bin.clone();
+        expected.rebuild_free_list();
+        let mut actual: Vec<_> = bin.rects_free.to_vec();
+        let mut expected: Vec<_> = expected.rects_free.to_vec();
+        actual.sort_by_key(|r| (r.x(), r.y(), r.width(), r.height()));
+        expected.sort_by_key(|r| (r.x(), r.y(), r.width(), r.height()));
+        assert_eq!(expected, actual);
+    }
+    assert!(bin.remove(inserted[0].id()).is_none());
+}
+
+#[test]
+fn bin_count_fit() {
+    for rule in [
+        Heuristic::BestShortSideFit,
+        Heuristic::BestLongSideFit,
+        Heuristic::BestAreaFit,
+        Heuristic::BottomLeftRule,
+        Heuristic::ContactPointRule,
+    ] {
+        let mut bin = MaxRectsBin::new(40, 30);
+        bin.set_default_rule(rule);
+        assert!(bin.add_obstacle(&Rectangle::new(10, 10, Dimension::new(7, 9))));
+        bin.insert_list(&[Dimension::new(13, 7), Dimension::new(5, 11)], true, rule);
+
+        // contacts with mapped rectangles and obstacles are scored like in the bin itself
+        let node = Dimension::new(6, 4);
+        let count = bin.count_fit(&node);
+        assert!(count > 0);
+        assert_eq!(
+            count,
+            std::iter::from_fn(|| BinPacker::insert(&mut bin, &node)).count()
+        );
+    }
+}
+
+#[test]
+fn bin_pack_strip() {
+    let nodes = [
+        Dimension::new(4, 2),
+        Dimension::new(4, 2),
+        Dimension::new(2, 4),
+    ];
+
+    let strip = pack_strip(&nodes, 4, true, Heuristic::BottomLeftRule).unwrap();
+    assert_eq!(4, strip.width());
+    assert_eq!(6, strip.height());
+    assert_eq!(Heuristic::BottomLeftRule, strip.default_rule());
+
+    // item fits into the strip only if rotated
+    let nodes = [Dimension::new(6, 2)];
+    let strip = pack_strip(&nodes, 4, true, Heuristic::BottomLeftRule).unwrap();
+    assert_eq!(6, strip.height());
+
+    let result = pack_strip(&nodes, 4, false, Heuristic::BottomLeftRule);
+    assert_eq!(BinError::ItemTooBig, result.err().unwrap());
+}
+
+#[test]
+fn bin_grow() {
+    let mut bin = MaxRectsBin::new(8, 8);
+    bin.insert(&Dimension::new(4, 4), false, Heuristic::BottomLeftRule);
+    bin.grow(4, 4);
+
+    // free space next to the item is extended into the new area
+    let rect = bin.insert(&Dimension::new(8, 12), false, Heuristic::BottomLeftRule);
+    assert_eq!(Some((4, 0)), rect.map(|r| (r.x(), r.y())));
+    let rect = bin.insert(&Dimension::new(4, 8), false, Heuristic::BottomLeftRule);
+    assert_eq!(Some((0, 4)), rect.map(|r| (r.x(), r.y())));
+}
+
+#[test]
+fn bin_grow_float_coords() {
+    let rule = Heuristic::BottomLeftRule;
+
+    for count in 1..17 {
+        // free space behind strips whose sizes are not exactly representable is extended
+        let mut bin: MaxRectsBin<f64> = MaxRectsBin::new(1.7, 1.7);
+        let nodes = vec![Dimension::new(0.1, 1.7); count];
+        bin.insert_list(&nodes, false, rule);
+        bin.grow(1, 0);
+        let width = 2.7 - 0.1 * count as f64 - 1e-9;
+        let rect = bin.insert(&Dimension::new(width, 1.7), false, rule);
+        assert!(rect.is_some());
+
+        let mut bin: MaxRectsBin<f64> = MaxRectsBin::new(1.7, 1.7);
+        let nodes = vec![Dimension::new(1.7, 0.1); count];
+        bin.insert_list(&nodes, false, rule);
+        bin.grow(0, 1);
+        let height = 2.7 - 0.1 * count as f64 - 1e-9;
+        let rect = bin.insert(&Dimension::new(1.7, height), false, rule);
+        assert!(rect.is_some());
+        assert!(bin.validate().is_empty());
+    }
+}
+
+#[test]
+fn bin_obstacles() {
+    let mut bin = MaxRectsBin::new(8, 8);
+    let obstacle = Rectangle::new(2, 2, Dimension::new(4, 4));
+    assert!(bin.add_obstacle(&obstacle));
+    assert!(!bin.add_obstacle(&Rectangle::new(6, 6, Dimension::new(4, 4))));
+    assert_eq!(1, bin.obstacles().len());
+
+    // free space is limited to the border area around the obstacle
+    let (inserted, rejected) = bin.insert_list(
+        &vec![Dimension::new(2, 2); 13],
+        false,
+        Heuristic::BestAreaFit,
+    );
+    assert_eq!(12, inserted.len());
+    assert_eq!(1, rejected.len());
+    assert!(inserted.iter().all(|r| !r.intersects(&obstacle)));
+    assert_eq!(12, bin.len());
+    assert_eq!(0.75, bin.occupancy());
+
+    // obstacles cannot be placed on top of mapped rectangles
+    assert!(!bin.add_obstacle(&Rectangle::new(0, 0, Dimension::new(1, 1))));
+
+    bin.clear();
+    assert_eq!(1, bin.obstacles().len());
+    assert!(bin
+        .insert(&Dimension::new(8, 4), false, Heuristic::BestAreaFit)
+        .is_none());
+
+    assert!(bin.remove_obstacle(&obstacle));
+    assert!(!bin.remove_obstacle(&obstacle));
+    assert!(bin
+        .insert(&Dimension::new(8, 8), false, Heuristic::BestAreaFit)
+        .is_some());
+}
+
+#[test]
+fn bin_alignment() {
+    let mut bin = MaxRectsBin::new(32, 32);
+    bin.set_alignment(Some(Alignment::new(4, 4, false)));
+    assert_eq!(Some(Alignment::new(4, 4, false)), bin.alignment());
+
+    let mut nodes = vec![
+        Dimension::new(3, 5),
+        Dimension::new(6, 2),
+        Dimension::new(5, 5),
+        Dimension::new(7, 3),
+        Dimension::with_padding(2, 2, 1),
+    ];
+    let mut custom = Dimension::new(3, 3);
+    custom.set_alignment(Some(Alignment::new(8, 8, false)));
+    nodes.push(custom);
+
+    let (inserted, rejected) = bin.insert_list(&nodes, true, Heuristic::BestShortSideFit);
+    assert!(rejected.is_empty());
+    for rect1 in &inserted {
+        let step = if rect1.dim().alignment().is_some() {
+            8
+        } else {
+            4
+        };
+        assert_eq!((0, 0), (rect1.x() % step, rect1.y() % step));
+        for rect2 in &inserted {
+            if rect1 != rect2 {
+                assert!(!rect1.intersects_total(rect2));
+            }
+        }
+    }
+
+    assert!(bin.insert_at(30, 30, &Dimension::new(1, 1)).is_none());
+    assert!(bin.insert_at(28, 28, &Dimension::new(1, 1)).is_some());
+
+    // sizes are rounded up to multiples of the alignment
+    let node = Dimension::new(5, 5);
+    let mut bin = MaxRectsBin::new(16, 14);
+    bin.set_alignment(Some(Alignment::new(4, 4, false)));
+    assert_eq!(4, bin.count_fit(&node));
+    bin.set_alignment(Some(Alignment::new(4, 4, true)));
+    assert_eq!(2, bin.count_fit(&node));
+
+    // the rounding assigned by the bin does not affect comparisons with the original object
+    let rect = bin.insert(&node, false, Heuristic::BottomLeftRule).unwrap();
+    assert_eq!(&node, rect.dim());
+    assert!(bin.remove_rect(&Rectangle::new(rect.x(), rect.y(), node)));
+}
+
+#[test]
+fn bin_coord_types() {
+    let rules = [
+        Heuristic::BestShortSideFit,
+        Heuristic::BestLongSideFit,
+        Heuristic::BestAreaFit,
+        Heuristic::BottomLeftRule,
+        Heuristic::ContactPointRule,
+    ];
+
+    for rule in rules {
+        let mut bin: MaxRectsBin<u16> = MaxRectsBin::new(64, 64);
+        let nodes = vec![Dimension::with_padding(30, 30, 1); 5];
+        let (inserted, rejected) = bin.insert_list(&nodes, false, rule);
+        assert_eq!(4, inserted.len());
+        assert_eq!(1, rejected.len());
+        assert_eq!(1.0, bin.occupancy() / (30.0 * 30.0 * 4.0 / 64.0 / 64.0));
+
+        bin.remove(inserted[0].id());
+        assert!(bin.insert(&nodes[4], false, rule).is_some());
+
+        // sizes close to the limits of the coordinate type do not overflow
+        let mut bin: MaxRectsBin<u16> = MaxRectsBin::new(u16::MAX, u16::MAX);
+        bin.set_spacing(2);
+        let node = Dimension::new(u16::MAX / 2 + 1, u16::MAX - 2);
+        assert!(bin.insert(&node, false, rule).is_some());
+        assert!(bin.insert(&node, false, rule).is_none());
+        assert!(bin
+            .insert(&Dimension::new(u16::MAX / 2 - 4, 8), false, rule)
+            .is_some());
+
+        // objects exceeding the coordinate range including padding are rejected
+        let huge = Dimension::with_padding(u16::MAX, 8, 1);
+        assert!(bin.insert(&huge, false, rule).is_none());
+        let (inserted, rejected) = bin.insert_list(&[huge, Dimension::new(8, 8)], false, rule);
+        assert_eq!((1, vec![huge]), (inserted.len(), rejected));
+        assert!(!bin.add_obstacle(&Rectangle::new(4, 4, huge)));
+
+        let size = 4_000_000_000_000i64;
+        let mut bin: MaxRectsBin<i64> = MaxRectsBin::new(size, size);
+        let node = Dimension::new(size / 2, size / 2);
+        let (inserted, _) = bin.insert_list(&vec![node; 5], true, rule);
+        assert_eq!(4, inserted.len());
+        assert_eq!(1.0, bin.occupancy());
+        assert_eq!(
+            (size / 2) as i128 * (size / 2) as i128,
+            inserted[0].dim().area()
+        );
+    }
+}
+
+#[test]
+fn bin_float_coords() {
+    let rules = [
+        Heuristic::BestShortSideFit,
+        Heuristic::BestLongSideFit,
+        Heuristic::BestAreaFit,
+        Heuristic::BottomLeftRule,
+        Heuristic::ContactPointRule,
+    ];
+
+    for rule in rules {
+        // strips whose sizes are not exactly representable fill the bin entirely
+        let mut bin: MaxRectsBin<f64> = MaxRectsBin::new(1.0, 1.0);
+        let nodes = vec![Dimension::new(0.1, 1.0); 10];
+        let (inserted, rejected) = bin.insert_list(&nodes, false, rule);
+        assert_eq!((10, 0), (inserted.len(), rejected.len()));
+        assert!((bin.occupancy() - 1.0).abs() < 1e-9);
+        for (i, r1) in inserted.iter().enumerate() {
+            assert!(inserted[i + 1..].iter().all(|r2| !r1.intersects(r2)));
+        }
+
+        let mut bin: MaxRectsBin<f32> = MaxRectsBin::new(297.0, 69.8);
+        bin.set_spacing(0.3);
+        // a row of objects including padding and spacing fills the packing area entirely
+        let nodes = vec![Dimension::with_padding(98.6, 69.6, 0.1); 4];
+        let (inserted, rejected) = bin.insert_list(&nodes, true, rule);
+        assert_eq!((3, 1), (inserted.len(), rejected.len()));
+        assert!(inserted
+            .iter()
+            .all(|r| r.x() >= 0.1 && r.x() + r.width() <= 296.9));
+    }
+}
+
+#[test]
+fn bin_validate() {
+    let mut bin = MaxRectsBin::new(64, 64);
+    let obstacle = Rectangle::new(20, 20, Dimension::new(8, 8));
+    assert!(bin.add_obstacle(&obstacle));
+    let nodes = vec![Dimension::with_padding(14, 10, 1); 12];
+    bin.insert_list(&nodes, true, Heuristic::ContactPointRule);
+    assert!(bin.validate().is_empty());
+
+    // corrupted free rectangle lists are reported
+    let free = bin.rects_free[0];
+    let mut inner = free;
+    inner.dim_mut().set_width(1);
+    bin.rects_free.push(inner);
+    assert_eq!(vec![Violation::FreeRedundant(free, inner)], bin.validate());
+
+    bin.rects_free.as_mut_vec().pop();
+    let used = bin.rects_used[0];
+    bin.rects_free.push(used);
+    let violations = bin.validate();
+    assert!(violations.contains(&Violation::FreeOccupied(used, used)));
+
+    bin.rects_free.as_mut_vec().pop();
+    let moved = Rectangle::new(60, 0, Dimension::new(8, 8));
+    bin.rects_free.push(moved);
+    assert!(bin.validate().contains(&Violation::FreeOutOfBounds(moved)));
+}
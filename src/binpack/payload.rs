@@ -0,0 +1,310 @@
+//! Provides a generic API for packing items which carry an arbitrary user payload.
+//!
+//! Packed rectangles are matched with their payload internally, so the payload is returned
+//! reliably, even if items share the same identifier.
+//!
+//! # Quick Start
+//!
+//! This example demonstrates how items with a payload are placed into a bin and into multiple
+//! bins.
+//!
+//! ```rust
+//! use binpack2d::{bin_new, BinType, Dimension};
+//! use binpack2d::payload::{pack_items, Item, PayloadPacker};
+//!
+//! // Create a number of items with an asset name as payload.
+//! let items = vec![
+//!     Item::new(Dimension::new(32, 32), "player.png"),
+//!     Item::new(Dimension::new(48, 16), "enemy.png"),
+//!     Item::new(Dimension::new(80, 80), "background.png"),
+//! ];
+//!
+//! // Items are inserted into a single bin, rejected items are returned with their payload.
+//! let mut bin = bin_new(BinType::MaxRects, 64, 64);
+//! let (inserted, rejected) = bin.insert_items(items.clone());
+//! for placed in &inserted {
+//!     println!("{} was placed at (x: {}, y: {})", placed.payload(), placed.rect().x(),
+//!              placed.rect().y());
+//! }
+//! assert_eq!(2, inserted.len());
+//! assert_eq!("background.png", *rejected[0].payload());
+//!
+//! // Items are packed into as many bins as needed.
+//! let (bins, rejected) = pack_items(BinType::MaxRects, items, 128, 128, true);
+//! assert_eq!(1, bins.len());
+//! assert_eq!(3, bins[0].1.len());
+//! assert!(rejected.is_empty());
+//! ```
+
+use super::{pack_bins, rejection_reason, BinError, BinPacker, BinType};
+use crate::dimension::Dimension;
+use crate::rectangle::Rectangle;
+
+/// A bin together with the list of items which have been packed into it.
+pub type PackedBin<T> = (Box<dyn BinPacker>, Vec<Placed<T>>);
+
+/// An item to be placed into a bin, which consists of a [`Dimension`] object and an arbitrary
+/// user payload.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Item<T> {
+    /// Size and packing properties of the item.
+    dim: Dimension,
+    /// User payload of the item.
+    payload: T,
+}
+
+impl<T> Item<T> {
+    /// Creates a new item from the given [`Dimension`] object and payload.
+    pub fn new(dim: Dimension, payload: T) -> Self {
+        Self { dim, payload }
+    }
+
+    /// Returns the [`Dimension`] object of the item.
+    pub fn dim(&self) -> &Dimension {
+        &self.dim
+    }
+
+    /// Returns a mutable reference to the [`Dimension`] object of the item.
+    pub fn dim_mut(&mut self) -> &mut Dimension {
+        &mut self.dim
+    }
+
+    /// Returns the payload of the item.
+    pub fn payload(&self) -> &T {
+        &self.payload
+    }
+
+    /// Returns a mutable reference to the payload of the item.
+    pub fn payload_mut(&mut self) -> &mut T {
+        &mut self.payload
+    }
+
+    /// Consumes the item and returns the [`Dimension`] object and the payload.
+    pub fn into_parts(self) -> (Dimension, T) {
+        (self.dim, self.payload)
+    }
+}
+
+/// An item which has been placed into a bin, which consists of the packed [`Rectangle`] and
+/// the user payload of the item.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Placed<T> {
+    /// Location and size of the packed item.
+    rect: Rectangle,
+    /// User payload of the item.
+    payload: T,
+}
+
+impl<T> Placed<T> {
+    /// Creates a new placed item from the given [`Rectangle`] and payload.
+    pub(crate) fn new(rect: Rectangle, payload: T) -> Self {
+        Self { rect, payload }
+    }
+
+    /// Returns the packed [`Rectangle`] of the item.
+    pub fn rect(&self) -> &Rectangle {
+        &self.rect
+    }
+
+    /// Returns the payload of the item.
+    pub fn payload(&self) -> &T {
+        &self.payload
+    }
+
+    /// Returns a mutable reference to the payload of the item.
+    pub fn payload_mut(&mut self) -> &mut T {
+        &mut self.payload
+    }
+
+    /// Consumes the placed item and returns the [`Rectangle`] and the payload.
+    pub fn into_parts(self) -> (Rectangle, T) {
+        (self.rect, self.payload)
+    }
+}
+
+/// An item which could not be placed, together with the reason of rejection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rejected<T> {
+    /// The rejected item.
+    item: Item<T>,
+    /// Reason why the item was rejected.
+    reason: BinError,
+}
+
+impl<T> Rejected<T> {
+    /// Creates a new rejection of the given item.
+    pub(crate) fn new(item: Item<T>, reason: BinError) -> Self {
+        Self { item, reason }
+    }
+
+    /// Returns the rejected item.
+    pub fn item(&self) -> &Item<T> {
+        &self.item
+    }
+
+    /// Returns the payload of the rejected item.
+    pub fn payload(&self) -> &T {
+        &self.item.payload
+    }
+
+    /// Returns the reason why the item was rejected.
+    pub fn reason(&self) -> BinError {
+        self.reason
+    }
+
+    /// Consumes the rejection and returns the rejected item.
+    pub fn into_item(self) -> Item<T> {
+        self.item
+    }
+}
+
+/// Extends all bin packers by methods for inserting items with a user payload.
+pub trait PayloadPacker: BinPacker {
+    /// Inserts a single [`Item`] into the bin, as if [`insert`] was called for its [`Dimension`]
+    /// object.
+    ///
+    /// [`insert`]: BinPacker::insert
+    ///
+    /// Returns the placed item as a [`Result`] value.
+    ///
+    /// # Errors
+    ///
+    /// The item is returned unchanged if it could not be placed.
+    fn insert_item<T>(&mut self, item: Item<T>) -> Result<Placed<T>, Item<T>> {
+        match self.insert(&item.dim) {
+            Some(rect) => Ok(Placed::new(rect, item.payload)),
+            None => Err(item),
+        }
+    }
+
+    /// Inserts the given list of [`Item`] objects into the bin, as if [`insert_list`] was called
+    /// for their [`Dimension`] objects.
+    ///
+    /// [`insert_list`]: BinPacker::insert_list
+    ///
+    /// Returns a tuple consisting of the list with all successfully placed items and the list of
+    /// rejected items with the reason of rejection.
+    fn insert_items<T>(&mut self, items: Vec<Item<T>>) -> (Vec<Placed<T>>, Vec<Rejected<T>>) {
+        let (nodes, mut slots) = tag_items(items);
+        let (inserted, rejected) = self.insert_list(&nodes);
+        let inserted = inserted
+            .into_iter()
+            .filter_map(|rect| {
+                take_item(&mut slots, rect.dim()).map(|item| Placed::new(rect, item.payload))
+            })
+            .collect();
+        let rejected = rejected
+            .iter()
+            .filter_map(|node| {
+                let reason = rejection_reason(self, node);
+                take_item(&mut slots, node).map(|item| Rejected::new(item, reason))
+            })
+            .collect();
+        (inserted, rejected)
+    }
+}
+
+impl<B: BinPacker + ?Sized> PayloadPacker for B {}
+
+/// A convenience function that packs a given list of [`Item`] objects into a variable number of
+/// bins, as if [`pack_bins`] was called for their [`Dimension`] objects.
+///
+/// Returns a tuple consisting of the list of bins, each paired with the items placed into it,
+/// and the list of rejected items with the reason of rejection.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{BinError, BinType, Dimension};
+/// use binpack2d::payload::{pack_items, Item};
+///
+/// // Items may share identifiers, the payload is still returned reliably
+/// let items = vec![
+///     Item::new(Dimension::with_id(1, 8, 8, 0), 'a'),
+///     Item::new(Dimension::with_id(1, 8, 8, 0), 'b'),
+///     Item::new(Dimension::with_id(1, 20, 4, 0), 'c'),
+/// ];
+///
+/// let (bins, rejected) = pack_items(BinType::Skyline, items, 8, 8, false);
+///
+/// assert_eq!(2, bins.len());
+/// assert_eq!('a', *bins[0].1[0].payload());
+/// assert_eq!('b', *bins[1].1[0].payload());
+/// assert_eq!('c', *rejected[0].payload());
+/// assert_eq!(BinError::ItemTooBig, rejected[0].reason());
+/// ```
+pub fn pack_items<T>(
+    bin_type: BinType,
+    items: Vec<Item<T>>,
+    bin_width: i32,
+    bin_height: i32,
+    optimized: bool,
+) -> (Vec<PackedBin<T>>, Vec<Rejected<T>>) {
+    let (nodes, mut slots) = tag_items(items);
+    let (bins, rejections) = match pack_bins(bin_type, &nodes, bin_width, bin_height, optimized) {
+        Ok(bins) => (bins, Vec::new()),
+        Err(err) => {
+            let rejections = err.rejections().to_vec();
+            (err.into_bins(), rejections)
+        }
+    };
+
+    let bins: Vec<PackedBin<T>> = bins
+        .into_iter()
+        .map(|bin| {
+            let placed = bin
+                .iter()
+                .filter_map(|rect| {
+                    take_item(&mut slots, rect.dim()).map(|item| Placed::new(*rect, item.payload))
+                })
+                .collect();
+            (bin, placed)
+        })
+        .collect();
+
+    let mut rejected: Vec<Rejected<T>> = rejections
+        .iter()
+        .filter_map(|r| {
+            slots
+                .get_mut(r.tag)
+                .and_then(Option::take)
+                .map(|item| Rejected::new(item, r.reason()))
+        })
+        .collect();
+
+    // items are not processed at all if the bin has no space
+    rejected.extend(slots.into_iter().flatten().map(|item| {
+        let reason = if item.dim.is_empty() {
+            BinError::ItemTooSmall
+        } else {
+            BinError::ItemTooBig
+        };
+        Rejected::new(item, reason)
+    }));
+
+    (bins, rejected)
+}
+
+/// Tags the [`Dimension`] objects of the given items with their index.
+///
+/// Returns the list of tagged `Dimension` objects and the list of items, which can be taken by
+/// [`take_item`].
+fn tag_items<T>(items: Vec<Item<T>>) -> (Vec<Dimension>, Vec<Option<Item<T>>>) {
+    let nodes = items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let mut node = item.dim;
+            node.tag = idx;
+            node
+        })
+        .collect();
+    (nodes, items.into_iter().map(Some).collect())
+}
+
+/// Takes the item matching the tag of the given [`Dimension`] object from `slots`.
+fn take_item<T>(slots: &mut [Option<Item<T>>], node: &Dimension) -> Option<Item<T>> {
+    slots.get_mut(node.tag).and_then(Option::take)
+}
+
+#[cfg(test)]
+mod tests;
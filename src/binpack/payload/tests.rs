@@ -0,0 +1,113 @@
+use super::*;
+use crate::bin_new;
+
+const BIN_TYPES: [BinType; 4] = [
+    BinType::MaxRects,
+    BinType::Guillotine,
+    BinType::Skyline,
+    BinType::Shelf,
+];
+
+/// Returns a list of items with duplicate identifiers, carrying their index as payload.
+fn prepare_items() -> Vec<Item<usize>> {
+    vec![
+        Item::new(Dimension::with_id(1, 8, 8, 0), 0),
+        Item::new(Dimension::with_id(1, 8, 4, 0), 1),
+        Item::new(Dimension::with_id(1, 4, 4, 0), 2),
+        Item::new(Dimension::with_id(1, 20, 20, 0), 3),
+        Item::new(Dimension::with_id(1, 0, 4, 0), 4),
+    ]
+}
+
+#[test]
+fn payload_insert_item() {
+    for bin_type in BIN_TYPES {
+        let mut bin = bin_new(bin_type, 8, 8);
+        let placed = bin
+            .insert_item(Item::new(Dimension::new(8, 4), "first"))
+            .expect("Item should fit");
+        assert_eq!("first", *placed.payload());
+        assert_eq!(bin.as_slice()[0], *placed.rect());
+
+        let item = Item::new(Dimension::with_id(7, 8, 8, 0), "second");
+        let rejected = bin
+            .insert_item(item.clone())
+            .expect_err("Item should not fit");
+        assert_eq!(item, rejected);
+    }
+}
+
+#[test]
+fn payload_insert_items() {
+    let items = prepare_items();
+    for bin_type in BIN_TYPES {
+        let mut bin = bin_new(bin_type, 16, 16);
+        let (inserted, rejected) = bin.insert_items(items[..4].to_vec());
+        assert_eq!(3, inserted.len());
+        for placed in &inserted {
+            let item = &items[*placed.payload()];
+            let dim = placed.rect().original_dim();
+            assert_eq!(item.dim().width(), dim.width());
+            assert_eq!(item.dim().height(), dim.height());
+            if !placed.rect().is_rotated() {
+                assert_eq!(item.dim(), placed.rect().dim());
+            }
+        }
+
+        let mut payloads: Vec<usize> = rejected.iter().map(|r| *r.payload()).collect();
+        payloads.sort_unstable();
+        assert_eq!(vec![3], payloads);
+        for rejection in &rejected {
+            assert_eq!(items[*rejection.payload()], *rejection.item());
+            assert_eq!(BinError::ItemTooBig, rejection.reason());
+        }
+
+        // placed rectangles are the ones stored in the bin
+        assert!(bin.remove_rect(inserted[0].rect()));
+
+        let (_, rejected) = bin.insert_items(vec![Item::new(Dimension::new(16, 16), 0)]);
+        assert_eq!(BinError::NoSpaceLeft, rejected[0].reason());
+    }
+}
+
+#[test]
+fn payload_pack_items() {
+    let items = prepare_items();
+    for bin_type in BIN_TYPES {
+        for optimized in [false, true] {
+            let (bins, rejected) = pack_items(bin_type, items.clone(), 8, 8, optimized);
+            assert_eq!(2, bins.len());
+            let mut payloads = Vec::new();
+            for (bin, placed) in &bins {
+                assert_eq!(bin.len(), placed.len());
+                for placed in placed {
+                    let item = &items[*placed.payload()];
+                    let dim = placed.rect().original_dim();
+                    assert_eq!(item.dim().width(), dim.width());
+                    assert_eq!(item.dim().height(), dim.height());
+                    payloads.push(*placed.payload());
+                }
+            }
+            payloads.sort_unstable();
+            assert_eq!(vec![0, 1, 2], payloads);
+
+            let rejected: Vec<(usize, BinError)> = rejected
+                .into_iter()
+                .map(|r| (*r.payload(), r.reason()))
+                .collect();
+            assert_eq!(
+                vec![(3, BinError::ItemTooBig), (4, BinError::ItemTooSmall)],
+                rejected
+            );
+        }
+    }
+
+    // no space to pack any items
+    let (bins, rejected) = pack_items(BinType::MaxRects, prepare_items(), 0, 0, true);
+    assert!(bins.is_empty());
+    assert_eq!(items.len(), rejected.len());
+    assert!(rejected
+        .iter()
+        .zip(&items)
+        .all(|(r, item)| r.item() == item));
+}
@@ -0,0 +1,1071 @@
+//! A two-dimensional rectangle bin packer which places rectangles row by row on horizontal
+//! *SHELVES*. A very fast algorithm, which produces predictable layouts.
+//!
+//! # Quick Start
+//!
+//! This example demonstrates the usage of the "Shelf" bin-packing algorithm. A comparable code
+//! sample for a high-level implementation can be looked up in the [`binpack`] module description.
+//!
+//! [`binpack`]: crate::binpack
+//!
+//! ```rust
+//! use binpack2d::{BinPacker, Dimension};
+//! use binpack2d::shelf::{Heuristic, ShelfBin};
+//!
+//! // Create a number of items to be placed into the bin.
+//! let items_to_place = vec![
+//!     // Items with autogenerated identifiers.
+//!     // Identifiers start at 1 and increment by 1 per call.
+//!     Dimension::new(188, 300),
+//!     Dimension::new(32, 32),
+//!     Dimension::new(420, 512),
+//!     Dimension::new(620, 384),
+//!     // Three more items with explicit identifiers: -1, 300, and 9528 respectively
+//!     Dimension::with_id(-1, 160, 214, 0),
+//!     Dimension::with_id(300, 384, 640, 0),
+//!     Dimension::with_id(9528, 400, 200, 0),
+//! ];
+//!
+//! // Create a bin with the dimensions 1024x1024
+//! let mut bin = ShelfBin::new(1024, 1024);
+//!
+//! // Perform the bin packing operation on the list of items, using the best-area-fit shelf rule.
+//! // Passing `true` to the "rotate" parameter allows the method to rotate items by 90 degrees
+//! // if it results in a better placement.
+//! let (inserted, rejected) = bin.insert_list(&items_to_place, true, Heuristic::BestAreaFit);
+//!
+//! // Let's see if our item with id=9528 was successfully inserted...
+//! if let Some(rect) = &bin.find_by_id(9528) {
+//!     println!("Item with id {} was placed into the bin at position (x: {}, y: {})",
+//!              rect.id(), rect.x(), rect.y());
+//! } else {
+//!     println!("Item with id 9528 could not be placed into the bin.");
+//! }
+//!
+//! // List all successfully inserted rectangles.
+//! if !inserted.is_empty() {
+//!     inserted.iter().for_each(|rect| println!("Inserted: {}", rect));
+//! } else {
+//!     println!("No rectangles were added to the bin.");
+//! }
+//!
+//! // List all items which could not be inserted into the bin.
+//! if !rejected.is_empty() {
+//!     rejected.iter().for_each(|item| println!("Rejected: {}", item));
+//! } else {
+//!     println!("No items were rejected.");
+//! }
+//!
+//! println!("Occupancy of the bin: {:.1} %", bin.occupancy() * 100.0);
+//! ```
+
+use crate::binpack::{BinError, PackError};
+use std::fmt::{Display, Formatter};
+use std::slice;
+use std::slice::Iter;
+
+use super::growth::{grow_bin, GrowthPolicy};
+use super::guillotine::GuillotineBin;
+use super::index::IndexedRects;
+use super::journal::TrackedVec;
+use super::sizing::pack_strip_with;
+use super::{pack_bins_with, packing_area, rect_at, visualize_bin, BinPacker};
+use crate::coord::Coord;
+use crate::dimension::Dimension;
+use crate::rectangle::Rectangle;
+
+/// List of supported heuristic rules for *SHELF* data structures that can be used when deciding
+/// on which shelf to place a new rectangle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Heuristic {
+    /// Always places the new rectangle on the last open shelf.
+    NextFit,
+    /// Tests each shelf in turn and places the new rectangle on the first shelf where it fits.
+    FirstFit,
+    /// Chooses the shelf with the smallest remaining shelf area.
+    BestAreaFit,
+    /// Chooses the shelf with the largest remaining shelf area.
+    WorstAreaFit,
+    /// Chooses the lowest shelf where the rectangle fits.
+    BestHeightFit,
+    /// Chooses the shelf with the least remaining horizontal space after placing the rectangle.
+    BestWidthFit,
+    /// Chooses the shelf with the most remaining horizontal space after placing the rectangle.
+    WorstWidthFit,
+}
+
+/// A single horizontal row of rectangles.
+///
+/// Free areas above the rectangles of a shelf are moved to the waste map as soon as they are
+/// known, so a shelf does not need to keep track of its rectangles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Shelf {
+    /// Horizontal position where the next rectangle is placed.
+    current_x: i32,
+    /// Vertical position of the shelf.
+    start_y: i32,
+    /// Vertical dimension of the shelf, which is defined by the highest rectangle on the shelf.
+    height: i32,
+    /// Indicates whether the free areas of the shelf have been moved to the waste map.
+    closed: bool,
+}
+
+impl Shelf {
+    fn new(start_x: i32, start_y: i32, height: i32) -> Self {
+        Self {
+            current_x: start_x,
+            start_y,
+            height,
+            closed: false,
+        }
+    }
+}
+
+/// A two-dimensional rectangle bin packer which places rectangles row by row on horizontal
+/// shelves.
+///
+/// Only the last shelf is open for growing in height. Space above the rectangles of a closed
+/// shelf is lost, unless the optional waste map is enabled. The waste map keeps track of these
+/// areas and tries to fill them first when placing new rectangles.
+///
+/// It can be used to pack multiple rectangles of arbitrary size into a "bin" of rectangular shape
+/// with the goal to add as many rectangles as possible into the bin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShelfBin {
+    /// Horizontal dimension of the bin.
+    bin_width: i32,
+    /// Vertical dimension of the bin.
+    bin_height: i32,
+    /// Keeps track of used areas within the bin.
+    rects_used: IndexedRects,
+    /// List of shelves, ordered from top to bottom.
+    shelves: TrackedVec<Shelf>,
+
+    /// Keeps track of free areas of closed shelves.
+    waste_map: GuillotineBin,
+    /// Indicates whether the waste map is used to fill free areas of closed shelves.
+    use_waste_map: bool,
+    /// Margin along the bin boundaries.
+    border: i32,
+    /// Gap between neighbouring rectangles.
+    spacing: i32,
+    /// Bin size at the time of each active checkpoint.
+    checkpoints: Vec<(i32, i32)>,
+
+    /// Optionally enlarges the bin if objects do not fit.
+    growth_policy: Option<GrowthPolicy>,
+
+    /// Implicitly used for the methods defined by the `BinPacker` trait.
+    default_heuristic: Heuristic,
+    /// Implicitly used for the methods defined by the `BinPacker` trait.
+    default_rotate: bool,
+}
+
+impl BinPacker for ShelfBin {
+    fn width(&self) -> i32 {
+        self.bin_width
+    }
+
+    fn height(&self) -> i32 {
+        self.bin_height
+    }
+
+    fn clear_with(&mut self, capacity: usize) {
+        self.rects_used.clear();
+        self.rects_used.shrink_to(capacity.max(4));
+        self.shelves.clear();
+        let area = self.area();
+        self.shelves
+            .push(Shelf::new(area.x_total(), area.y_total(), 0));
+        self.waste_map.get_free_rects().clear();
+    }
+
+    fn grow(&mut self, dw: u32, dh: u32) {
+        if dw > 0 {
+            let old_right = self.area().right_total();
+            self.bin_width = self.bin_width.saturating_add(i32::from_u32(dw));
+            let area = self.area();
+            let dw = area.right_total() - old_right;
+
+            // closed shelves can only make use of the additional space via the waste map
+            let start_y = self
+                .shelves
+                .last()
+                .map_or(area.y_total(), |shelf| shelf.start_y);
+            if self.use_waste_map && dw > 0 && start_y > area.y_total() {
+                let waste = Rectangle::new(
+                    old_right,
+                    area.y_total(),
+                    Dimension::with_id(0, dw, start_y - area.y_total(), 0),
+                );
+                self.waste_map.get_free_rects().push(waste);
+            }
+        }
+
+        if dh > 0 {
+            self.bin_height = self.bin_height.saturating_add(i32::from_u32(dh));
+        }
+
+        self.waste_map.set_bin_size(self.bin_width, self.bin_height);
+    }
+
+    fn shrink(&mut self, binary: bool) {
+        if self.rects_used.is_empty() {
+            return;
+        }
+
+        let mut min_x = i32::MAX;
+        let mut min_y = i32::MAX;
+        let mut max_x = i32::MIN;
+        let mut max_y = i32::MIN;
+
+        // finding borders, the spacing of rectangles is not needed at the bin boundary
+        for rect in &self.rects_used {
+            min_x = min_x.min(rect.x_total());
+            min_y = min_y.min(rect.y_total());
+            max_x = max_x.max(rect.right_total() - rect.dim().spacing);
+            max_y = max_y.max(rect.bottom_total() - rect.dim().spacing);
+        }
+
+        let shift_x = min_x - self.border;
+        let shift_y = min_y - self.border;
+        let mut new_width = max_x - shift_x + self.border;
+        let mut new_height = max_y - shift_y + self.border;
+
+        if binary {
+            // attempt to shrink to the next lower power of two
+            let mut cur_width = self.bin_width;
+            while new_width <= (cur_width >> 1) {
+                cur_width >>= 1;
+            }
+            new_width = cur_width;
+
+            let mut cur_height = self.bin_height;
+            while new_height <= (cur_height >> 1) {
+                cur_height >>= 1;
+            }
+            new_height = cur_height;
+        }
+
+        // adjusting rectangle positions
+        if new_width != self.bin_width || new_height != self.bin_height {
+            if shift_x > 0 || shift_y > 0 {
+                self.rects_used.update_all(|rect| {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
+                });
+                for rect in self.waste_map.get_free_rects().as_mut_vec() {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
+                }
+            }
+
+            self.bin_width = new_width;
+            self.bin_height = new_height;
+            self.waste_map.set_bin_size(new_width, new_height);
+
+            // waste areas are always located within the used area
+            let area = self.area();
+            self.waste_map
+                .get_free_rects()
+                .retain(|r| area.contains_total(r));
+
+            // adjusting shelves to the new packing area
+            let (area_left, area_top) = (area.x_total(), area.y_total());
+            let (area_right, area_bottom) = (area.right_total(), area.bottom_total());
+            for shelf in self.shelves.as_mut_vec() {
+                shelf.current_x = (shelf.current_x - shift_x).clamp(area_left, area_right);
+                shelf.start_y = (shelf.start_y - shift_y).clamp(area_top, area_bottom);
+                shelf.height = shelf.height.min(area_bottom - shelf.start_y);
+            }
+        }
+    }
+
+    fn border(&self) -> i32 {
+        self.border
+    }
+
+    fn set_border(&mut self, border: i32) {
+        self.border = border.max(0);
+        self.waste_map.set_border(self.border);
+        self.clear();
+    }
+
+    fn spacing(&self) -> i32 {
+        self.spacing
+    }
+
+    fn set_spacing(&mut self, spacing: i32) {
+        self.spacing = spacing.max(0);
+        self.waste_map.set_spacing(self.spacing);
+        self.clear();
+    }
+
+    fn growth_policy(&self) -> Option<GrowthPolicy> {
+        self.growth_policy
+    }
+
+    fn set_growth_policy(&mut self, policy: Option<GrowthPolicy>) {
+        self.growth_policy = policy;
+    }
+
+    fn insert(&mut self, dim: &Dimension) -> Option<Rectangle> {
+        self.insert(dim, self.default_rotate, self.default_heuristic)
+    }
+
+    fn insert_list(&mut self, nodes: &[Dimension]) -> (Vec<Rectangle>, Vec<Dimension>) {
+        self.insert_list(nodes, self.default_rotate, self.default_heuristic)
+    }
+
+    /// The requested area must be located either on the open shelf behind the last rectangle,
+    /// below the open shelf, or in a free area of the waste map. A new shelf is opened at the
+    /// vertical position of the object if it is located below the open shelf.
+    fn insert_at(&mut self, x: i32, y: i32, dim: &Dimension) -> Option<Rectangle> {
+        let area = self.area();
+        let rect = rect_at(x, y, &dim.to_spaced(self.spacing), &area, None)?;
+
+        if self.use_waste_map && self.waste_map.is_free_area(&rect) {
+            self.waste_map.carve_free_list(&rect);
+            self.rects_used.push(rect);
+            return Some(rect);
+        }
+
+        let last = self.shelves.last().expect("Bin should have an open shelf");
+        let on_shelf = rect.y_total() == last.start_y && rect.x_total() >= last.current_x;
+        let end_y = last.start_y.saturating_add(last.height);
+        if !on_shelf && rect.y_total() < end_y {
+            return None;
+        }
+
+        if !on_shelf {
+            // the space between the open shelf and the object can only be reached through the
+            // waste map
+            if last.height > 0 {
+                if self.use_waste_map {
+                    self.move_shelf_to_waste_map(self.shelves.len() - 1);
+                }
+                self.shelves.push(Shelf::new(area.x_total(), end_y, 0));
+            }
+            let start_y = self
+                .shelves
+                .last()
+                .map_or(area.y_total(), |shelf| shelf.start_y);
+            let gap = rect.y_total() - start_y;
+            if self.use_waste_map && gap > 0 {
+                self.waste_map.reclaim_rect(&Rectangle::new(
+                    area.x_total(),
+                    start_y,
+                    Dimension::with_id(0, area.width_total(), gap, 0),
+                ));
+            }
+        }
+
+        let index = self.shelves.len() - 1;
+        self.shelves
+            .update(index, |shelf| shelf.start_y = rect.y_total());
+        let shelf = self.shelves[index];
+
+        // the space between the last rectangle on the shelf and the object can only be reached
+        // through the waste map
+        let gap = rect.x_total() - shelf.current_x;
+        if self.use_waste_map && gap > 0 {
+            self.waste_map.reclaim_rect(&Rectangle::new(
+                shelf.current_x,
+                shelf.start_y,
+                Dimension::with_id(0, gap, shelf.height, 0),
+            ));
+        }
+
+        self.place_on_shelf(index, &rect);
+        self.rects_used.push(rect);
+
+        Some(rect)
+    }
+
+    fn preview_insert(&self, dim: &Dimension) -> Option<Rectangle> {
+        let dim = &dim.to_spaced(self.spacing);
+        let rotate = self.default_rotate;
+        if dim.is_empty() || !self.fits_area(dim, rotate) {
+            return None;
+        }
+
+        if self.use_waste_map {
+            if let Some(result) = self.waste_map.preview_waste(dim, rotate) {
+                return Some(result);
+            }
+        }
+
+        match self.find_shelf(dim, rotate, self.default_heuristic) {
+            Some((_, rect)) => Some(rect),
+            None => self.new_shelf_rect(dim, rotate),
+        }
+    }
+
+    fn count_fit(&self, dim: &Dimension) -> usize {
+        let dim = &dim.to_spaced(self.spacing);
+        let mut bin = self.scratch_bin();
+        let mut count = 0;
+        while bin
+            .try_insert(dim, self.default_rotate, self.default_heuristic)
+            .is_some()
+        {
+            count += 1;
+        }
+        count
+    }
+
+    fn occupancy(&self) -> f32 {
+        if self.bin_width == 0 || self.bin_height == 0 {
+            return 0.0;
+        }
+
+        let area: i64 = self.rects_used.iter().map(|r| r.dim().area()).sum();
+
+        (area as f64 / self.bin_width.mul_wide(self.bin_height) as f64) as f32
+    }
+
+    fn as_slice(&self) -> &[Rectangle] {
+        &self.rects_used
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rects_used.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.rects_used.len()
+    }
+
+    fn iter(&self) -> Iter<'_, Rectangle> {
+        self.rects_used.iter()
+    }
+
+    fn find_by_id(&self, id: isize) -> Option<Rectangle> {
+        self.rects_used.find_by_id(id).copied()
+    }
+
+    /// Removed areas are reclaimed by the waste map. If the waste map is disabled, only the
+    /// area of the last rectangle placed on the open shelf is reclaimed, all other areas are lost.
+    fn remove_rect(&mut self, rect: &Rectangle) -> bool {
+        if let Some(index) = self.rects_used.position(rect) {
+            let rect = self.rects_used.remove(index);
+
+            let index = self.shelves.len() - 1;
+            let shelf = self.shelves[index];
+            if self.use_waste_map {
+                self.waste_map.reclaim_rect(&rect);
+            } else if !shelf.closed
+                && rect.y_total() == shelf.start_y
+                && rect.right_total() == shelf.current_x
+            {
+                // the open shelf is simply continued at the position of the rectangle
+                self.shelves
+                    .update(index, |shelf| shelf.current_x = rect.x_total());
+            }
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        self.checkpoints.push((self.bin_width, self.bin_height));
+        self.rects_used.checkpoint();
+        self.shelves.checkpoint();
+        self.waste_map.checkpoint();
+    }
+
+    fn rollback(&mut self) -> bool {
+        if let Some((width, height)) = self.checkpoints.pop() {
+            self.bin_width = width;
+            self.bin_height = height;
+            self.rects_used.rollback();
+            self.shelves.rollback();
+            self.waste_map.rollback();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn commit(&mut self) -> bool {
+        if self.checkpoints.pop().is_some() {
+            self.rects_used.commit();
+            self.shelves.commit();
+            self.waste_map.commit();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn visualize(&self) -> String {
+        if let Some(output) = visualize_bin(self.bin_width, self.bin_height, &self.rects_used) {
+            output
+        } else {
+            format!("{self}")
+        }
+    }
+}
+
+impl ShelfBin {
+    /// Creates an empty bin of the given size.
+    ///
+    /// Minimum width and height of a bin is 1.
+    pub fn new(width: i32, height: i32) -> Self {
+        Self::with_capacity(width, height, 4)
+    }
+
+    /// Creates an empty bin of the given size and reserves space for at least `capacity` number
+    /// of mapped rectangle to improve performance.
+    ///
+    /// Minimum width and height of a bin is 1.
+    pub fn with_capacity(width: i32, height: i32, capacity: usize) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut result = Self {
+            bin_width: width,
+            bin_height: height,
+            rects_used: IndexedRects::with_capacity(capacity.max(4)),
+            shelves: TrackedVec::new(),
+            waste_map: GuillotineBin::new(width, height),
+            use_waste_map: true,
+            border: 0,
+            spacing: 0,
+            checkpoints: Vec::new(),
+            default_heuristic: Heuristic::BestAreaFit,
+            default_rotate: false,
+            growth_policy: None,
+        };
+        result.shelves.push(Shelf::new(0, 0, 0));
+        result.waste_map.get_free_rects().clear();
+
+        result
+    }
+
+    /// Returns the default [`Heuristic`] rule, which is used by the [`BinPacker`] trait's
+    /// [`insert`] and [`insert_list`] methods.
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn default_rule(&self) -> Heuristic {
+        self.default_heuristic
+    }
+
+    /// Can be used to override the default [`Heuristic`] rule, which is used by the [`BinPacker`]
+    /// trait's [`insert`] and [`insert_list`] methods.
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn set_default_rule(&mut self, rule: Heuristic) {
+        self.default_heuristic = rule;
+    }
+
+    /// Returns whether objects may be rotated by 90 degrees by the [`BinPacker`] trait's
+    /// [`insert`] and [`insert_list`] methods.
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn default_rotate(&self) -> bool {
+        self.default_rotate
+    }
+
+    /// Can be used to override whether objects may be rotated by 90 degrees by the [`BinPacker`]
+    /// trait's [`insert`] and [`insert_list`] methods. Rotation is disabled by default.
+    ///
+    /// The [`Rotation`] policy of the individual objects takes precedence over this setting.
+    pub fn set_default_rotate(&mut self, rotate: bool) {
+        self.default_rotate = rotate;
+    }
+
+    /// Returns whether the waste map is used to keep track of free areas of closed shelves.
+    pub fn use_waste_map(&self) -> bool {
+        self.use_waste_map
+    }
+
+    /// Specifies whether the waste map should be used to keep track of free areas of closed
+    /// shelves, which would otherwise be lost. The waste map is enabled by default.
+    ///
+    /// Disabling the waste map discards all free areas that have been collected so far.
+    pub fn set_use_waste_map(&mut self, enable: bool) {
+        self.use_waste_map = enable;
+        if !enable {
+            self.waste_map.get_free_rects().clear();
+        }
+    }
+
+    /// Inserts a single [`Dimension`] object into the bin.
+    ///
+    /// `dim` refers to the object to be packed into the bin.
+    ///
+    /// `rotate` indicates whether the object may be rotated by 90 degrees if the rotated
+    /// orientation fits better on a shelf. The [`Rotation`] policy of the object takes precedence
+    /// over this parameter.
+    ///
+    /// `rule` specifies the shelf selection rule to use for the packing operation.
+    ///
+    /// Free areas of the waste map are considered first if the waste map is enabled. A new shelf
+    /// is opened if the object does not fit on any of the existing shelves.
+    ///
+    /// Returns a copy of the packed [`Rectangle`] if the object was inserted successful,
+    /// or `None` otherwise.
+    pub fn insert(&mut self, dim: &Dimension, rotate: bool, rule: Heuristic) -> Option<Rectangle> {
+        let dim = &dim.to_spaced(self.spacing);
+        loop {
+            let result = self.try_insert(dim, rotate, rule);
+            if result.is_some() || !grow_bin(self, self.growth_policy, slice::from_ref(dim), rotate)
+            {
+                return result;
+            }
+        }
+    }
+
+    /// Inserts a single `Dimension` object into the bin without enlarging the bin.
+    fn try_insert(&mut self, dim: &Dimension, rotate: bool, rule: Heuristic) -> Option<Rectangle> {
+        // Empty or too big dimension objects are always rejected
+        if dim.is_empty() || !self.fits_area(dim, rotate) {
+            return None;
+        }
+
+        if self.use_waste_map {
+            if let Some(new_node) = self.waste_map.insert_waste(dim, rotate) {
+                self.rects_used.push(new_node);
+                return Some(new_node);
+            }
+        }
+
+        let (index, new_node) = match self.find_shelf(dim, rotate, rule) {
+            Some(result) => result,
+            None => self.start_new_shelf(dim, rotate)?,
+        };
+
+        self.place_on_shelf(index, &new_node);
+        self.rects_used.push(new_node);
+
+        Some(new_node)
+    }
+
+    /// Attempts to insert the given list of [`Dimension`] objects into the bin.
+    ///
+    /// `nodes` specifies the list of [`Dimension`] objects to insert. All successfully inserted
+    /// objects will be removed from the list in the process.
+    ///
+    /// `rotate` indicates whether objects may be rotated by 90 degrees if the rotated
+    /// orientation fits better on a shelf. The [`Rotation`] policy of the objects takes precedence
+    /// over this parameter.
+    ///
+    /// `rule` specifies the shelf selection rule to use for the packing operations.
+    ///
+    /// Returns a list with all successfully inserted [`Rectangle`] objects.
+    ///
+    /// Objects are inserted in order of decreasing height, which usually results in more tightly
+    /// packed bins than inserting them one by one via [`insert`].
+    ///
+    /// [`insert`]: ShelfBin::insert
+    pub fn insert_list(
+        &mut self,
+        nodes: &[Dimension],
+        rotate: bool,
+        rule: Heuristic,
+    ) -> (Vec<Rectangle>, Vec<Dimension>) {
+        let nodes: Vec<_> = nodes.iter().map(|n| n.to_spaced(self.spacing)).collect();
+        let (mut inserted, mut rejected) = self.try_insert_list(&nodes, rotate, rule);
+        while !rejected.is_empty() && grow_bin(self, self.growth_policy, &rejected, rotate) {
+            let (mut new_nodes, remaining) = self.try_insert_list(&rejected, rotate, rule);
+            inserted.append(&mut new_nodes);
+            rejected = remaining;
+        }
+
+        let rejected = rejected.into_iter().map(|n| n.to_spaced(0)).collect();
+        (inserted, rejected)
+    }
+
+    /// Attempts to insert the given list of `Dimension` objects into the bin without enlarging
+    /// the bin.
+    fn try_insert_list(
+        &mut self,
+        nodes: &[Dimension],
+        rotate: bool,
+        rule: Heuristic,
+    ) -> (Vec<Rectangle>, Vec<Dimension>) {
+        let mut inserted = Vec::with_capacity(nodes.len());
+        let mut rejected = Vec::new();
+
+        // shelves are filled best if objects of similar height are placed next to each other
+        let mut sorted = nodes.to_vec();
+        sorted.sort_by_key(|dim| {
+            let height = dim
+                .orientations(rotate)
+                .map(|(dim, _)| dim.height_total())
+                .min()
+                .unwrap_or_default();
+            std::cmp::Reverse(height)
+        });
+
+        for dim in sorted {
+            if let Some(new_node) = self.try_insert(&dim, rotate, rule) {
+                inserted.push(new_node);
+            } else {
+                rejected.push(dim);
+            }
+        }
+
+        (inserted, rejected)
+    }
+
+    /// Returns the area of the bin which is available for packing rectangles.
+    fn area(&self) -> Rectangle {
+        packing_area(self.bin_width, self.bin_height, self.border, self.spacing)
+    }
+
+    /// Returns whether the given `Dimension` fits into the empty bin, optionally rotated by
+    /// 90 degrees.
+    fn fits_area(&self, dim: &Dimension, rotate: bool) -> bool {
+        let area = self.area();
+        dim.fits_into(area.width(), area.height(), rotate)
+    }
+
+    /// Creates a bin for simulating insertions, which shares the configuration, the shelves and
+    /// the waste map of this bin, but holds no mapped rectangles.
+    fn scratch_bin(&self) -> Self {
+        let mut shelves = self.shelves.to_vec();
+        let mut result = Self {
+            bin_width: self.bin_width,
+            bin_height: self.bin_height,
+            rects_used: IndexedRects::with_capacity(4),
+            shelves: TrackedVec::with_capacity(shelves.len()),
+            waste_map: self.waste_map.scratch_bin(),
+            use_waste_map: self.use_waste_map,
+            border: self.border,
+            spacing: self.spacing,
+            checkpoints: Vec::new(),
+            default_heuristic: self.default_heuristic,
+            default_rotate: self.default_rotate,
+            growth_policy: None,
+        };
+        result.shelves.append(&mut shelves);
+
+        result
+    }
+
+    /// Attempts to find the best shelf for the given `Dimension`, using the given rule.
+    ///
+    /// Returns the index of the shelf and the `Rectangle` structure where the requested
+    /// `Dimension` can be placed.
+    fn find_shelf(
+        &self,
+        dim: &Dimension,
+        rotate: bool,
+        rule: Heuristic,
+    ) -> Option<(usize, Rectangle)> {
+        let mut candidates = self
+            .shelves
+            .iter()
+            .enumerate()
+            .filter_map(|(i, _)| self.fit_to_shelf(i, dim, rotate).map(|r| (i, r)));
+
+        match rule {
+            Heuristic::NextFit => {
+                let index = self.shelves.len() - 1;
+                self.fit_to_shelf(index, dim, rotate).map(|r| (index, r))
+            }
+            Heuristic::FirstFit => candidates.next(),
+            _ => {
+                let mut result = None;
+                let mut best_score = i64::MAX;
+                let area = self.area();
+                for (i, rect) in candidates {
+                    let shelf = &self.shelves[i];
+                    let width_left = (area.right_total() - shelf.current_x) as i64;
+                    let score = match rule {
+                        Heuristic::BestAreaFit => width_left * shelf.height as i64,
+                        Heuristic::WorstAreaFit => -width_left * shelf.height as i64,
+                        Heuristic::BestHeightFit => {
+                            (shelf.height - rect.height_total()).max(0) as i64
+                        }
+                        Heuristic::BestWidthFit => width_left - rect.width_total() as i64,
+                        Heuristic::WorstWidthFit => rect.width_total() as i64 - width_left,
+                        Heuristic::NextFit | Heuristic::FirstFit => return None,
+                    };
+                    if score < best_score {
+                        best_score = score;
+                        result = Some((i, rect));
+                    }
+                }
+                result
+            }
+        }
+    }
+
+    /// Checks whether the given `Dimension` can be placed on the shelf at the given index.
+    ///
+    /// Only the last shelf may grow in height to fit the `Dimension`. If both orientations fit,
+    /// the one which makes better use of the shelf height is preferred.
+    ///
+    /// Returns the `Rectangle` structure where the `Dimension` can be placed, and `None` otherwise.
+    fn fit_to_shelf(&self, index: usize, dim: &Dimension, rotate: bool) -> Option<Rectangle> {
+        let shelf = &self.shelves[index];
+        if shelf.closed {
+            // free areas are managed by the waste map
+            return None;
+        }
+
+        let area = self.area();
+        let max_height = if index + 1 == self.shelves.len() {
+            area.bottom_total() - shelf.start_y
+        } else {
+            shelf.height
+        };
+
+        // compare against the space left to avoid overflowing near the greatest coordinate
+        let width_left = area.right_total() - shelf.current_x;
+        let mut fitting = dim
+            .orientations(rotate)
+            .filter(|(dim, _)| dim.width_total() <= width_left && dim.height_total() <= max_height);
+
+        let (mut dim, mut rotated) = fitting.next()?;
+        if let Some(flipped) = fitting.next() {
+            // both orientations fit: prefer tall objects below the shelf ceiling and avoid
+            // raising the shelf if possible
+            let (w, h) = (dim.width_total(), dim.height_total());
+            if (w > h && w <= shelf.height) || (w < h && h > shelf.height && w <= shelf.height) {
+                (dim, rotated) = flipped;
+            }
+        }
+
+        let mut rect = Rectangle::new(0, 0, dim);
+        rect.set_location_total(shelf.current_x, shelf.start_y);
+        rect.set_rotated(rotated);
+
+        Some(rect)
+    }
+
+    /// Closes the last shelf and opens a new shelf for the given `Dimension`, if there is enough
+    /// vertical space left. Objects are laid flat on a new shelf if they may be rotated.
+    ///
+    /// Returns the index of the new shelf and the `Rectangle` structure where the requested
+    /// `Dimension` can be placed.
+    fn start_new_shelf(&mut self, dim: &Dimension, rotate: bool) -> Option<(usize, Rectangle)> {
+        let rect = self.new_shelf_rect(dim, rotate)?;
+
+        if self.use_waste_map {
+            self.move_shelf_to_waste_map(self.shelves.len() - 1);
+        }
+        self.shelves
+            .push(Shelf::new(self.area().x_total(), rect.y_total(), 0));
+
+        Some((self.shelves.len() - 1, rect))
+    }
+
+    /// Determines where the given `Dimension` would be placed on a new shelf below the last shelf,
+    /// without opening the shelf.
+    ///
+    /// Returns the `Rectangle` structure where the requested `Dimension` can be placed, and `None`
+    /// if there is not enough vertical space left.
+    fn new_shelf_rect(&self, dim: &Dimension, rotate: bool) -> Option<Rectangle> {
+        let last = self.shelves.last()?;
+        if last.height == 0 {
+            // last shelf is still empty
+            return None;
+        }
+
+        let area = self.area();
+        let start_y = last.start_y.saturating_add(last.height);
+        let height_left = area.bottom_total() - start_y;
+        let (dim, rotated) = dim
+            .orientations(rotate)
+            .filter(|(dim, _)| {
+                dim.width_total() <= area.width_total() && dim.height_total() <= height_left
+            })
+            .min_by_key(|(dim, _)| dim.height_total())?;
+
+        let mut rect = Rectangle::new(0, 0, dim);
+        rect.set_location_total(area.x_total(), start_y);
+        rect.set_rotated(rotated);
+
+        Some(rect)
+    }
+
+    /// Places the given rectangle behind the last rectangle of the shelf at the given index.
+    ///
+    /// The space above the rectangle cannot be reached by the shelf anymore, and is moved to
+    /// the waste map right away. If the rectangle raises the shelf, the same applies to the
+    /// space above all previous rectangles of the shelf.
+    fn place_on_shelf(&mut self, index: usize, rect: &Rectangle) {
+        let shelf = self.shelves[index];
+        let height = rect.height_total();
+        if self.use_waste_map {
+            let left = self.area().x_total();
+            let free_rects = self.waste_map.get_free_rects();
+            if height < shelf.height {
+                free_rects.push(Rectangle::new(
+                    rect.x_total(),
+                    rect.bottom_total(),
+                    Dimension::with_id(0, rect.width_total(), shelf.height - height, 0),
+                ));
+            } else if height > shelf.height && rect.x_total() > left {
+                free_rects.push(Rectangle::new(
+                    left,
+                    shelf.start_y + shelf.height,
+                    Dimension::with_id(0, rect.x_total() - left, height - shelf.height, 0),
+                ));
+            }
+        }
+
+        self.shelves.update(index, |shelf| {
+            shelf.current_x = rect.right_total();
+            shelf.height = shelf.height.max(height);
+        });
+    }
+
+    /// Adds the unused space after the end of the shelf at the given index to the waste map.
+    fn move_shelf_to_waste_map(&mut self, index: usize) {
+        let area = self.area();
+        let shelf = &self.shelves[index];
+        let free_rects = self.waste_map.get_free_rects();
+
+        // Add the space after the shelf end
+        let width = area.right_total() - shelf.current_x;
+        if width > 0 && shelf.height > 0 {
+            free_rects.push(Rectangle::new(
+                shelf.current_x,
+                shelf.start_y,
+                Dimension::with_id(0, width, shelf.height, 0),
+            ));
+        }
+
+        self.waste_map.merge_free_list();
+        self.shelves.update(index, |shelf| shelf.closed = true);
+    }
+}
+
+impl<Idx> std::ops::Index<Idx> for ShelfBin
+where
+    Idx: std::slice::SliceIndex<[Rectangle]>,
+{
+    type Output = Idx::Output;
+
+    fn index(&self, index: Idx) -> &Self::Output {
+        &self.rects_used[index]
+    }
+}
+
+impl Display for ShelfBin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Bin(width: {}, height: {}, rectangles: {})",
+            self.bin_width,
+            self.bin_height,
+            self.rects_used.len()
+        )
+    }
+}
+
+/// A convenience function that attempts to insert a given list of `Dimension` objects into a
+/// variable number of bins.
+///
+/// New bins are created on demand, using the given shelf heuristic `rule`. Specify `true` for `rotate`
+/// to allow rotating objects by 90 degrees if it results in a better placement.
+///
+/// Specify true for `optimize` to use [`insert_list`] internally, which results in an improved
+/// bin layout but at the cost of a worse processing performance.
+///
+/// [`insert_list`]: ShelfBin::insert_list
+///
+/// Nodes with the same [`group`] key are always placed into the same bin.
+///
+/// [`group`]: Dimension::group
+///
+/// Returns a list of bins with the packed rectangle nodes as a [`Result`] value.
+///
+/// # Errors
+///
+/// A [`PackError`] is returned if any nodes could not be packed, listing each rejected node with
+/// the reason of rejection. All other nodes are still packed into the bins provided by the error.
+///
+/// # Examples
+/// ```
+/// use binpack2d::binpack::BinPacker;
+/// use binpack2d::binpack::shelf::{Heuristic, pack_bins};
+/// use binpack2d::dimension::Dimension;
+///
+/// // Defining three items of different size
+/// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
+///
+/// // Returned list of bin object contains all nodes, placed according to the given heuristic rule
+/// let bins = pack_bins(&nodes, 16, 12, false, Heuristic::BestAreaFit, true)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(1, bins.len());
+/// assert_eq!(3, bins[0].len());
+/// ```
+pub fn pack_bins(
+    nodes: &[Dimension],
+    bin_width: i32,
+    bin_height: i32,
+    rotate: bool,
+    rule: Heuristic,
+    optimized: bool,
+) -> Result<Vec<ShelfBin>, PackError<ShelfBin>> {
+    let result = pack_bins_with(nodes, bin_width, bin_height, optimized, |width, height| {
+        let mut bin = ShelfBin::new(width, height);
+        bin.set_default_rotate(rotate);
+        bin.set_default_rule(rule);
+        Box::new(bin)
+    });
+
+    result
+        .map(|bins| bins.into_iter().map(|bin| *bin).collect())
+        .map_err(|err| err.map_bins(|bin| *bin))
+}
+
+/// A convenience function that packs a given list of `Dimension` objects into a strip of the
+/// given width, and reduces the height of the strip as much as possible.
+///
+/// The strip is packed via [`insert_list`], using the given shelf heuristic `rule`. Specify `true` for `rotate`
+/// to allow rotating objects by 90 degrees if it results in a better placement.
+///
+/// [`insert_list`]: ShelfBin::insert_list
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value. The height of the bin
+/// is the final strip height.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too wide for the strip.
+///
+/// # Examples
+/// ```
+/// use binpack2d::binpack::BinPacker;
+/// use binpack2d::binpack::shelf::{Heuristic, pack_strip};
+/// use binpack2d::dimension::Dimension;
+///
+/// // Defining three items of different size
+/// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
+///
+/// // Returned strip contains all nodes, placed according to the given heuristic rule
+/// let strip = pack_strip(&nodes, 8, false, Heuristic::BestAreaFit)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(8, strip.width());
+/// assert_eq!(3, strip.len());
+/// ```
+pub fn pack_strip(
+    nodes: &[Dimension],
+    strip_width: i32,
+    rotate: bool,
+    rule: Heuristic,
+) -> Result<ShelfBin, BinError> {
+    let strip = pack_strip_with(nodes, strip_width, |width, height| {
+        let mut bin = ShelfBin::new(width, height);
+        bin.set_default_rotate(rotate);
+        bin.set_default_rule(rule);
+        Box::new(bin)
+    })?;
+
+    Ok(*strip)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,235 @@
+use super::*;
+
+const RULES: [Heuristic; 7] = [
+    Heuristic::NextFit,
+    Heuristic::FirstFit,
+    Heuristic::BestAreaFit,
+    Heuristic::WorstAreaFit,
+    Heuristic::BestHeightFit,
+    Heuristic::BestWidthFit,
+    Heuristic::WorstWidthFit,
+];
+
+#[test]
+fn bin_shrink() {
+    let mut bin = ShelfBin::new(16, 16);
+    bin.insert(&Dimension::new(6, 7), false, Heuristic::BestAreaFit);
+    bin.shrink(true);
+    assert_eq!(8, bin.width());
+    assert_eq!(8, bin.height());
+
+    bin.shrink(false);
+    assert_eq!(6, bin.width());
+    assert_eq!(7, bin.height());
+
+    let result = bin.insert(&Dimension::new(1, 1), false, Heuristic::BestAreaFit);
+    assert!(result.is_none());
+}
+
+#[test]
+fn bin_insert() {
+    let nodes = [
+        Dimension::with_padding(2, 4, 0),
+        Dimension::with_padding(6, 4, 1),
+        Dimension::with_padding(10, 3, 1),
+        Dimension::with_padding(6, 6, 0),
+        Dimension::with_padding(4, 4, 2),
+        Dimension::with_padding(3, 8, 0),
+        Dimension::with_padding(8, 3, 1),
+    ];
+
+    for rule in RULES {
+        let mut bin = ShelfBin::new(16, 16);
+        assert_eq!(16, bin.width());
+        assert_eq!(16, bin.height());
+
+        for node in nodes.iter() {
+            bin.insert(node, true, rule);
+        }
+
+        for rect1 in bin.iter() {
+            for rect2 in bin.iter() {
+                if rect1 != rect2 {
+                    assert!(!rect1.intersects(rect2));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bin_insert_list() {
+    let nodes = [
+        Dimension::with_padding(2, 4, 0),
+        Dimension::with_padding(6, 4, 1),
+        Dimension::with_padding(10, 3, 1),
+        Dimension::with_padding(6, 6, 0),
+        Dimension::with_padding(4, 4, 2),
+        Dimension::with_padding(3, 8, 0),
+        Dimension::with_padding(8, 3, 1),
+    ];
+
+    for rule in RULES {
+        let mut bin = ShelfBin::new(16, 16);
+        let (inserted, rejected) = bin.insert_list(&nodes, true, rule);
+        assert_eq!(nodes.len(), inserted.len() + rejected.len());
+
+        for rect1 in bin.iter() {
+            for rect2 in bin.iter() {
+                if rect1 != rect2 {
+                    assert!(!rect1.intersects(rect2));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bin_shelves() {
+    let mut bin = ShelfBin::new(8, 16);
+    bin.set_use_waste_map(false);
+
+    // first shelf is defined by the highest item
+    let r1 = bin.insert(&Dimension::new(4, 4), false, Heuristic::FirstFit);
+    let r2 = bin.insert(&Dimension::new(4, 2), false, Heuristic::FirstFit);
+    assert_eq!(Some((0, 0)), r1.map(|r| (r.x(), r.y())));
+    assert_eq!(Some((4, 0)), r2.map(|r| (r.x(), r.y())));
+
+    // no space left on the first shelf
+    let r3 = bin.insert(&Dimension::new(2, 2), false, Heuristic::FirstFit);
+    assert_eq!(Some((0, 4)), r3.map(|r| (r.x(), r.y())));
+
+    // rotated to fit below the ceiling of the second shelf
+    let r4 = bin.insert(&Dimension::new(2, 6), true, Heuristic::FirstFit);
+    assert_eq!(
+        Some((2, 4, true)),
+        r4.map(|r| (r.x(), r.y(), r.is_rotated()))
+    );
+}
+
+#[test]
+fn bin_waste_map() {
+    // the second item leaves a 4x4 gap on the first shelf
+    let nodes = [
+        Dimension::new(4, 8),
+        Dimension::new(4, 4),
+        Dimension::new(8, 4),
+        Dimension::new(4, 4),
+    ];
+
+    let mut bin = ShelfBin::new(8, 12);
+    for node in &nodes[..3] {
+        assert!(bin.insert(node, false, Heuristic::NextFit).is_some());
+    }
+
+    // the gap can only be reached through the waste map
+    let rect = bin.insert(&nodes[3], false, Heuristic::NextFit);
+    assert_eq!(Some((4, 4)), rect.map(|r| (r.x(), r.y())));
+
+    let mut bin = ShelfBin::new(8, 12);
+    bin.set_use_waste_map(false);
+    for node in &nodes[..3] {
+        assert!(bin.insert(node, false, Heuristic::NextFit).is_some());
+    }
+    assert!(bin.insert(&nodes[3], false, Heuristic::NextFit).is_none());
+}
+
+#[test]
+fn bin_closed_shelf() {
+    // the end of the first shelf is moved to the waste map when the second shelf is opened
+    let nodes = [
+        Dimension::new(8, 2),
+        Dimension::new(16, 4),
+        Dimension::new(2, 2),
+        Dimension::new(6, 2),
+        Dimension::new(8, 2),
+    ];
+
+    for rule in RULES {
+        let mut bin = ShelfBin::new(16, 16);
+        for node in &nodes {
+            bin.insert(node, false, rule);
+        }
+        assert_eq!(nodes.len(), bin.len());
+
+        for rect1 in bin.iter() {
+            for rect2 in bin.iter() {
+                if rect1 != rect2 {
+                    assert!(!rect1.intersects(rect2));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bin_large() {
+    // bin areas exceed the range of i32
+    let mut bin = ShelfBin::new(100_000, 100_000);
+    let result = bin.insert(&Dimension::new(50_000, 50_000), false, Heuristic::NextFit);
+    assert!(result.is_some());
+    assert_eq!(0.25, bin.occupancy());
+
+    bin.grow(u32::MAX, u32::MAX);
+    assert_eq!((i32::MAX, i32::MAX), (bin.width(), bin.height()));
+    let result = bin.insert(&Dimension::new(50_000, 50_000), false, Heuristic::NextFit);
+    assert_eq!(Some((50_000, 0)), result.map(|r| (r.x(), r.y())));
+}
+
+#[test]
+fn bin_max_coord() {
+    // edges of rectangles near the greatest coordinate do not overflow
+    let half = i32::MAX / 2 + 10;
+    let rules = [
+        Heuristic::NextFit,
+        Heuristic::FirstFit,
+        Heuristic::BestAreaFit,
+        Heuristic::WorstAreaFit,
+        Heuristic::BestHeightFit,
+        Heuristic::BestWidthFit,
+        Heuristic::WorstWidthFit,
+    ];
+    for rule in rules {
+        for use_waste_map in [false, true] {
+            let mut bin = ShelfBin::new(i32::MAX, 100);
+            bin.set_use_waste_map(use_waste_map);
+            assert!(bin.insert(&Dimension::new(half, 50), false, rule).is_some());
+            let rect = bin.insert(&Dimension::new(half, 50), false, rule);
+            assert_eq!(Some((0, 50)), rect.map(|r| (r.x(), r.y())));
+            assert!(bin.insert(&Dimension::new(half, 50), false, rule).is_none());
+            bin.shrink(false);
+            assert_eq!((half, 100), (bin.width(), bin.height()));
+
+            let mut bin = ShelfBin::new(100, i32::MAX);
+            bin.set_use_waste_map(use_waste_map);
+            bin.set_spacing(5);
+            assert!(bin.insert(&Dimension::new(45, half), false, rule).is_some());
+            let rect = bin.insert(&Dimension::new(45, half), false, rule);
+            assert_eq!(Some((50, 0)), rect.map(|r| (r.x(), r.y())));
+            assert!(bin.insert(&Dimension::new(45, half), false, rule).is_none());
+            assert!(bin.validate().is_empty());
+        }
+    }
+}
+
+#[test]
+fn bin_remove_no_waste_map() {
+    for rule in RULES {
+        let mut bin = ShelfBin::new(8, 8);
+        bin.set_use_waste_map(false);
+        let dim = Dimension::new(4, 4);
+        let first = bin.insert(&dim, false, rule).unwrap();
+        let last = bin.insert(&dim, false, rule).unwrap();
+
+        // the last rectangle of the open shelf is reclaimed by continuing the shelf
+        assert!(bin.remove_rect(&last));
+        assert_eq!(Some(last), bin.insert(&dim, false, rule));
+
+        // all other rectangles are lost without the waste map
+        assert!(bin.remove_rect(&first));
+        assert!(bin.insert(&dim, false, rule).is_some());
+        assert!(bin.insert(&dim, false, rule).is_some());
+        assert!(bin.insert(&dim, false, rule).is_none());
+        assert!(bin.validate().is_empty());
+    }
+}
@@ -0,0 +1,464 @@
+//! Provides functions for finding the smallest bin that can hold a given list of items.
+//!
+//! # Quick Start
+//!
+//! This example demonstrates how to find the smallest bin with power-of-two dimensions that holds
+//! all items, a common requirement for texture atlases.
+//!
+//! ```rust
+//! use binpack2d::{BinPacker, BinType, Dimension};
+//! use binpack2d::sizing::{find_min_bin, SizeConstraints, SizeMetric};
+//!
+//! // Create a number of items to be placed into the bin.
+//! let items_to_place = vec![
+//!     Dimension::new(188, 300),
+//!     Dimension::new(32, 32),
+//!     Dimension::new(420, 512),
+//!     Dimension::new(620, 384),
+//!     Dimension::with_id(-1, 160, 214, 0),
+//!     Dimension::with_id(300, 384, 640, 0),
+//!     Dimension::with_id(9528, 400, 200, 0),
+//! ];
+//!
+//! // Bins should have power-of-two dimensions and must not be bigger than 4096x4096.
+//! let mut constraints = SizeConstraints::new();
+//! constraints.set_power_of_two(true);
+//! constraints.set_max_width(4096);
+//! constraints.set_max_height(4096);
+//!
+//! // Find the bin with the smallest area, using the "MaxRects" bin type.
+//! let bin = find_min_bin(BinType::MaxRects, &items_to_place, &constraints, SizeMetric::Area)
+//!     .expect("Items should fit into a 4096x4096 bin");
+//!
+//! assert_eq!(items_to_place.len(), bin.len());
+//! println!("Smallest bin: {}x{}", bin.width(), bin.height());
+//! ```
+
+use super::{bin_new, BinError, BinPacker, BinType};
+use crate::dimension::Dimension;
+
+/// List of supported measures for comparing the size of bins.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SizeMetric {
+    /// Prefers the bin with the smallest area.
+    Area,
+    /// Prefers the bin with the shortest longer side. Ties are resolved by choosing the bin with
+    /// the smaller area.
+    Side,
+}
+
+/// Defines the constraints a bin must satisfy when searching for the smallest bin size.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SizeConstraints {
+    /// Whether bin dimensions must be powers of two.
+    power_of_two: bool,
+    /// Whether bins must be square.
+    square: bool,
+    /// Maximum horizontal dimension of the bin.
+    max_width: i32,
+    /// Maximum vertical dimension of the bin.
+    max_height: i32,
+    /// Optional horizontal dimension that is used for all bins.
+    fixed_width: Option<i32>,
+}
+
+impl SizeConstraints {
+    /// Creates a new set of constraints without restrictions.
+    pub fn new() -> Self {
+        Self {
+            power_of_two: false,
+            square: false,
+            max_width: i32::MAX,
+            max_height: i32::MAX,
+            fixed_width: None,
+        }
+    }
+
+    /// Returns whether bin dimensions must be powers of two.
+    pub fn power_of_two(&self) -> bool {
+        self.power_of_two
+    }
+
+    /// Specifies whether bin dimensions must be powers of two. A fixed width is exempted from this
+    /// constraint.
+    pub fn set_power_of_two(&mut self, enable: bool) {
+        self.power_of_two = enable;
+    }
+
+    /// Returns whether bins must be square.
+    pub fn square(&self) -> bool {
+        self.square
+    }
+
+    /// Specifies whether bins must be square.
+    pub fn set_square(&mut self, enable: bool) {
+        self.square = enable;
+    }
+
+    /// Returns the maximum width of the bin.
+    pub fn max_width(&self) -> i32 {
+        self.max_width
+    }
+
+    /// Sets the maximum width of the bin. Values are capped at 1.
+    pub fn set_max_width(&mut self, value: i32) {
+        self.max_width = value.max(1);
+    }
+
+    /// Returns the maximum height of the bin.
+    pub fn max_height(&self) -> i32 {
+        self.max_height
+    }
+
+    /// Sets the maximum height of the bin. Values are capped at 1.
+    pub fn set_max_height(&mut self, value: i32) {
+        self.max_height = value.max(1);
+    }
+
+    /// Returns the fixed width of the bin, if available.
+    pub fn fixed_width(&self) -> Option<i32> {
+        self.fixed_width
+    }
+
+    /// Specifies a fixed width for the bin, which takes precedence over the maximum width.
+    /// Specify `None` to let the width vary. Values are capped at 1.
+    pub fn set_fixed_width(&mut self, value: Option<i32>) {
+        self.fixed_width = value.map(|v| v.max(1));
+    }
+}
+
+impl Default for SizeConstraints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Finds the smallest bin of the given [`BinType`] that holds all `nodes`, according to the given
+/// size `constraints` and `metric`.
+///
+/// Bins are packed with the default heuristic rules for the given [`BinType`]. Use
+/// [`find_min_bin_with`] to pack bins with custom heuristic rules instead.
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too big for a bin of the
+/// maximum size, or if the nodes could not be packed into a single bin of the maximum size.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{BinPacker, BinType, Dimension};
+/// use binpack2d::sizing::{find_min_bin, SizeConstraints, SizeMetric};
+///
+/// // Defining four items of the same size
+/// let nodes = vec![Dimension::new(8, 8); 4];
+///
+/// let bin = find_min_bin(BinType::MaxRects, &nodes, &SizeConstraints::new(), SizeMetric::Area)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(256, bin.width() * bin.height());
+/// assert_eq!(4, bin.len());
+/// ```
+pub fn find_min_bin(
+    bin_type: BinType,
+    nodes: &[Dimension],
+    constraints: &SizeConstraints,
+    metric: SizeMetric,
+) -> Result<Box<dyn BinPacker>, BinError> {
+    find_min_bin_with(nodes, constraints, metric, |width, height| {
+        bin_new(bin_type, width, height)
+    })
+}
+
+/// Finds the smallest bin that holds all `nodes`, according to the given size `constraints` and
+/// `metric`.
+///
+/// Bins are created on demand by the function `create`, which is called with the width and
+/// height of the requested bin. Nodes are packed by the bin's [`insert_list`] method, which
+/// allows to choose arbitrary bin types and heuristic rules. The resulting bin is reduced to the
+/// smallest possible size by [`shrink`].
+///
+/// [`insert_list`]: BinPacker::insert_list
+/// [`shrink`]: BinPacker::shrink
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too big for a bin of the
+/// maximum size, or if the nodes could not be packed into a single bin of the maximum size.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{BinPacker, Dimension};
+/// use binpack2d::sizing::{find_min_bin_with, SizeConstraints, SizeMetric};
+/// use binpack2d::skyline::{Heuristic, SkylineBin};
+///
+/// let nodes = vec![Dimension::new(4, 12), Dimension::new(8, 4), Dimension::new(8, 8)];
+///
+/// // Bins must be exactly 12 units wide
+/// let mut constraints = SizeConstraints::new();
+/// constraints.set_fixed_width(Some(12));
+///
+/// let bin = find_min_bin_with(&nodes, &constraints, SizeMetric::Area, |width, height| {
+///     let mut bin = SkylineBin::new(width, height);
+///     bin.set_default_rule(Heuristic::MinWasteFit);
+///     Box::new(bin)
+/// })
+/// .expect("Items should not be rejected");
+///
+/// assert_eq!(12, bin.width());
+/// assert_eq!(12, bin.height());
+/// ```
+pub fn find_min_bin_with<B, F>(
+    nodes: &[Dimension],
+    constraints: &SizeConstraints,
+    metric: SizeMetric,
+    mut create: F,
+) -> Result<Box<B>, BinError>
+where
+    B: BinPacker + ?Sized,
+    F: FnMut(i32, i32) -> Box<B>,
+{
+    let pot = constraints.power_of_two;
+    let fixed_width = constraints.fixed_width;
+    let max_width = match fixed_width {
+        Some(width) => width,
+        None if pot => prev_power_of_two(constraints.max_width),
+        None => constraints.max_width,
+    };
+    let mut max_height = if pot {
+        prev_power_of_two(constraints.max_height)
+    } else {
+        constraints.max_height
+    };
+    if constraints.square {
+        max_height = max_height.min(max_width);
+    }
+
+    // determining lower and upper bounds of the bin size
+    let mut min_width = 1;
+    let mut min_height = 1;
+    let mut area = 0i64;
+    let mut extent = 0i64;
+    for node in nodes {
+        if node.is_empty() {
+            return Err(BinError::ItemTooSmall);
+        } else if !node.fits_into(max_width, max_height, true) {
+            return Err(BinError::ItemTooBig);
+        }
+
+        let (width, height) = node
+            .orientations(true)
+            .map(|(dim, _)| (dim.width_total(), dim.height_total()))
+            .fold((i32::MAX, i32::MAX), |(w1, h1), (w2, h2)| {
+                (w1.min(w2), h1.min(h2))
+            });
+        min_width = min_width.max(width);
+        min_height = min_height.max(height);
+        area += node.area_total();
+        extent += node.width_total().max(node.height_total()) as i64;
+    }
+
+    // a bin with a side length of all node sides combined holds the nodes in a single row
+    let extent = if pot {
+        (extent.min(1 << 30) as u32).next_power_of_two() as i64
+    } else {
+        extent
+    };
+    let width_limit = max_width.min(extent.min(i32::MAX as i64) as i32).max(1);
+    let height_limit = max_height.min(extent.min(i32::MAX as i64) as i32).max(1);
+
+    let mut try_pack = |width: i32, height: i32| {
+        let mut bin = create(width, height);
+        let (_, rejected) = bin.insert_list(nodes);
+        if rejected.is_empty() {
+            bin.shrink(pot);
+
+            // restoring size constraints which are not considered by the shrinking operation
+            if let Some(width) = fixed_width {
+                bin.grow((width - bin.width()).max(0) as u32, 0);
+            }
+            if constraints.square {
+                let side = bin.width().max(bin.height());
+                bin.grow((side - bin.width()) as u32, (side - bin.height()) as u32);
+            }
+
+            Some(bin)
+        } else {
+            None
+        }
+    };
+
+    let result = if constraints.square || (metric == SizeMetric::Side && fixed_width.is_none()) {
+        // searching for the shortest longer side
+        let min_side = min_width.max(min_height).max(ceil_sqrt(area));
+        let max_side = if constraints.square {
+            width_limit.min(height_limit)
+        } else {
+            width_limit.max(height_limit)
+        };
+        match fixed_width {
+            Some(width) if width <= max_height => try_pack(width, width),
+            Some(_) => None,
+            None => search_size(min_side, max_side, pot, |side| {
+                try_pack(side.min(width_limit), side.min(height_limit))
+            }),
+        }
+    } else {
+        // searching for the smallest area among a number of bin widths
+        let widths = match fixed_width {
+            Some(width) => vec![width],
+            None => bin_widths(min_width, width_limit, pot),
+        };
+
+        let mut result: Option<Box<B>> = None;
+        for width in widths {
+            let min_area = result
+                .as_ref()
+                .map_or(i64::MAX, |bin| bin.width() as i64 * bin.height() as i64);
+            let lo = min_height.max(ceil_div(area, width));
+            let hi = height_limit.min((min_area / width as i64).min(i32::MAX as i64) as i32);
+            if let Some(bin) = search_size(lo, hi, pot, |height| try_pack(width, height)) {
+                if (bin.width() as i64 * bin.height() as i64) < min_area {
+                    result = Some(bin);
+                }
+            }
+        }
+        result
+    };
+
+    result.ok_or(BinError::MaxSizeExceeded)
+}
+
+/// Packs all `nodes` into a strip of the given width, and reduces the height of the strip as much
+/// as possible.
+///
+/// Strips are created on demand by the function `create`, which is called with the width and
+/// height of the requested strip. The height of the returned bin is the final strip height.
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too wide for the strip.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{BinPacker, Dimension};
+/// use binpack2d::sizing::pack_strip_with;
+/// use binpack2d::skyline::SkylineBin;
+///
+/// let nodes = vec![Dimension::new(4, 12), Dimension::new(8, 4), Dimension::new(8, 8)];
+///
+/// let strip = pack_strip_with(&nodes, 12, |width, height| Box::new(SkylineBin::new(width, height)))
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(12, strip.width());
+/// assert_eq!(12, strip.height());
+/// ```
+pub fn pack_strip_with<B, F>(
+    nodes: &[Dimension],
+    strip_width: i32,
+    create: F,
+) -> Result<Box<B>, BinError>
+where
+    B: BinPacker + ?Sized,
+    F: FnMut(i32, i32) -> Box<B>,
+{
+    let mut constraints = SizeConstraints::new();
+    constraints.set_fixed_width(Some(strip_width));
+
+    find_min_bin_with(nodes, &constraints, SizeMetric::Area, create).map_err(|err| match err {
+        // strip height is unlimited, which only leaves the strip width as cause
+        BinError::MaxSizeExceeded => BinError::ItemTooBig,
+        err => err,
+    })
+}
+
+/// Returns the smallest size within the given range for which the given packing operation
+/// succeeds. Only powers of two are considered if `pot` is set.
+///
+/// Packing operations are assumed to succeed for all sizes greater than the returned size.
+fn search_size<B, F>(min: i32, max: i32, pot: bool, mut pack: F) -> Option<Box<B>>
+where
+    B: BinPacker + ?Sized,
+    F: FnMut(i32) -> Option<Box<B>>,
+{
+    // powers of two are searched by exponent
+    let (mut lo, mut hi) = if pot {
+        let lo = (min.max(1) as u32).next_power_of_two().trailing_zeros() as i32;
+        let hi = 31 - max.max(1).leading_zeros() as i32;
+        (lo, hi)
+    } else {
+        (min.max(1), max)
+    };
+    let size = |value: i32| if pot { 1 << value } else { value };
+
+    if lo > hi {
+        return None;
+    }
+
+    let mut result = pack(size(hi))?;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if let Some(bin) = pack(size(mid)) {
+            result = bin;
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    Some(result)
+}
+
+/// Returns the list of bin widths to consider for the smallest area search.
+fn bin_widths(min: i32, max: i32, pot: bool) -> Vec<i32> {
+    let mut widths = Vec::new();
+    if pot {
+        let mut width = (min.max(1) as u32).next_power_of_two();
+        while width <= max as u32 {
+            widths.push(width as i32);
+            width <<= 1;
+        }
+    } else {
+        // resulting bins are shrunk to the actual width, which makes it unnecessary to test
+        // every single width
+        let mut width = min.max(1);
+        while width <= max {
+            widths.push(width);
+            if width == max {
+                break;
+            }
+            width = width.saturating_add((width >> 3).max(1)).min(max);
+        }
+    }
+
+    widths
+}
+
+/// Returns the greatest power of two that is smaller than or equal to `value`.
+fn prev_power_of_two(value: i32) -> i32 {
+    1 << (31 - value.max(1).leading_zeros())
+}
+
+/// Returns the smallest integer that is greater than or equal to the square root of `value`.
+fn ceil_sqrt(value: i64) -> i32 {
+    let mut result = (value as f64).sqrt() as i64;
+    while result * result < value {
+        result += 1;
+    }
+
+    result.min(i32::MAX as i64) as i32
+}
+
+/// Returns the quotient of `value` and `divisor`, rounded up.
+fn ceil_div(value: i64, divisor: i32) -> i32 {
+    let divisor = divisor.max(1) as i64;
+    ((value + divisor - 1) / divisor).min(i32::MAX as i64) as i32
+}
+
+#[cfg(test)]
+mod tests;
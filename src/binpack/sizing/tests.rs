@@ -0,0 +1,134 @@
+use super::*;
+use crate::binpack::maxrects::{Heuristic, MaxRectsBin};
+
+const BIN_TYPES: [BinType; 4] = [
+    BinType::MaxRects,
+    BinType::Guillotine,
+    BinType::Skyline,
+    BinType::Shelf,
+];
+
+fn assert_layout(bin: &dyn BinPacker, count: usize) {
+    assert_eq!(count, bin.len());
+    for rect1 in bin.iter() {
+        assert!(rect1.x() >= 0 && rect1.x() + rect1.width() <= bin.width());
+        assert!(rect1.y() >= 0 && rect1.y() + rect1.height() <= bin.height());
+        for rect2 in bin.iter() {
+            if rect1 != rect2 {
+                assert!(!rect1.intersects(rect2));
+            }
+        }
+    }
+}
+
+#[test]
+fn min_bin_area() {
+    let nodes = vec![Dimension::new(6, 2); 3];
+
+    for bin_type in BIN_TYPES {
+        let bin = find_min_bin(bin_type, &nodes, &SizeConstraints::new(), SizeMetric::Area)
+            .expect("Items should not be rejected");
+        assert_eq!(36, bin.width() * bin.height());
+        assert_layout(bin.as_ref(), nodes.len());
+    }
+}
+
+#[test]
+fn min_bin_side() {
+    let nodes = vec![Dimension::new(6, 2); 3];
+
+    for bin_type in BIN_TYPES {
+        let bin = find_min_bin(bin_type, &nodes, &SizeConstraints::new(), SizeMetric::Side)
+            .expect("Items should not be rejected");
+        assert_eq!(6, bin.width());
+        assert_eq!(6, bin.height());
+        assert_layout(bin.as_ref(), nodes.len());
+    }
+}
+
+#[test]
+fn min_bin_power_of_two() {
+    let nodes = vec![
+        Dimension::new(8, 8),
+        Dimension::new(8, 8),
+        Dimension::new(8, 8),
+        Dimension::new(3, 5),
+    ];
+    let mut constraints = SizeConstraints::new();
+    constraints.set_power_of_two(true);
+
+    for bin_type in BIN_TYPES {
+        let bin = find_min_bin(bin_type, &nodes, &constraints, SizeMetric::Area)
+            .expect("Items should not be rejected");
+        assert_eq!(256, bin.width() * bin.height());
+        assert!(bin.width().count_ones() == 1 && bin.height().count_ones() == 1);
+        assert_layout(bin.as_ref(), nodes.len());
+    }
+}
+
+#[test]
+fn min_bin_square() {
+    let nodes = vec![Dimension::new(10, 4); 2];
+    let mut constraints = SizeConstraints::new();
+    constraints.set_square(true);
+
+    for bin_type in BIN_TYPES {
+        let bin = find_min_bin(bin_type, &nodes, &constraints, SizeMetric::Area)
+            .expect("Items should not be rejected");
+        assert_eq!(10, bin.width());
+        assert_eq!(10, bin.height());
+        assert_layout(bin.as_ref(), nodes.len());
+    }
+}
+
+#[test]
+fn min_bin_fixed_width() {
+    let nodes = vec![Dimension::new(4, 4); 3];
+    let mut constraints = SizeConstraints::new();
+    constraints.set_fixed_width(Some(6));
+
+    for bin_type in BIN_TYPES {
+        let bin = find_min_bin(bin_type, &nodes, &constraints, SizeMetric::Area)
+            .expect("Items should not be rejected");
+        assert_eq!(6, bin.width());
+        assert_eq!(12, bin.height());
+        assert_layout(bin.as_ref(), nodes.len());
+    }
+}
+
+#[test]
+fn min_bin_with() {
+    let nodes = vec![Dimension::with_padding(4, 6, 1); 5];
+    let mut constraints = SizeConstraints::new();
+    constraints.set_max_width(20);
+
+    let bin = find_min_bin_with(&nodes, &constraints, SizeMetric::Area, |width, height| {
+        let mut bin = MaxRectsBin::new(width, height);
+        bin.set_default_rule(Heuristic::BottomLeftRule);
+        Box::new(bin)
+    })
+    .expect("Items should not be rejected");
+
+    assert!(bin.width() <= 20);
+    assert_eq!(Heuristic::BottomLeftRule, bin.default_rule());
+    assert_layout(bin.as_ref(), nodes.len());
+}
+
+#[test]
+fn min_bin_failure() {
+    let mut constraints = SizeConstraints::new();
+    constraints.set_max_width(8);
+    constraints.set_max_height(8);
+
+    let nodes = [Dimension::new(4, 4), Dimension::new(0, 4)];
+    let result = find_min_bin(BinType::MaxRects, &nodes, &constraints, SizeMetric::Area);
+    assert_eq!(BinError::ItemTooSmall, result.err().unwrap());
+
+    let nodes = [Dimension::new(4, 4), Dimension::new(9, 4)];
+    let result = find_min_bin(BinType::MaxRects, &nodes, &constraints, SizeMetric::Area);
+    assert_eq!(BinError::ItemTooBig, result.err().unwrap());
+
+    let nodes = [Dimension::new(8, 8), Dimension::new(4, 4)];
+    let result = find_min_bin(BinType::MaxRects, &nodes, &constraints, SizeMetric::Area);
+    assert_eq!(BinError::MaxSizeExceeded, result.err().unwrap());
+}
@@ -0,0 +1,1090 @@
+//! A two-dimensional rectangle bin packer using the *SKYLINE* data structure to keep track of
+//! the upper edge of the packed rectangles. A good choice for packing large numbers of
+//! rectangles quickly.
+//!
+//! # Quick Start
+//!
+//! This example demonstrates the usage of the "Skyline" bin-packing algorithm. A comparable code
+//! sample for a high-level implementation can be looked up in the [`binpack`] module description.
+//!
+//! [`binpack`]: crate::binpack
+//!
+//! ```rust
+//! use binpack2d::{BinPacker, Dimension};
+//! use binpack2d::skyline::{Heuristic, SkylineBin};
+//!
+//! // Create a number of items to be placed into the bin.
+//! let items_to_place = vec![
+//!     // Items with autogenerated identifiers.
+//!     // Identifiers start at 1 and increment by 1 per call.
+//!     Dimension::new(188, 300),
+//!     Dimension::new(32, 32),
+//!     Dimension::new(420, 512),
+//!     Dimension::new(620, 384),
+//!     // Three more items with explicit identifiers: -1, 300, and 9528 respectively
+//!     Dimension::with_id(-1, 160, 214, 0),
+//!     Dimension::with_id(300, 384, 640, 0),
+//!     Dimension::with_id(9528, 400, 200, 0),
+//! ];
+//!
+//! // Create a bin with the dimensions 1024x1024
+//! let mut bin = SkylineBin::new(1024, 1024);
+//!
+//! // Perform the bin packing operation on the list of items, using the min-waste placement rule.
+//! // Passing `true` to the "rotate" parameter allows the method to rotate items by 90 degrees
+//! // if it results in a better placement.
+//! let (inserted, rejected) = bin.insert_list(&items_to_place, true, Heuristic::MinWasteFit);
+//!
+//! // Let's see if our item with id=9528 was successfully inserted...
+//! if let Some(rect) = &bin.find_by_id(9528) {
+//!     println!("Item with id {} was placed into the bin at position (x: {}, y: {})",
+//!              rect.id(), rect.x(), rect.y());
+//! } else {
+//!     println!("Item with id 9528 could not be placed into the bin.");
+//! }
+//!
+//! // List all successfully inserted rectangles.
+//! if !inserted.is_empty() {
+//!     inserted.iter().for_each(|rect| println!("Inserted: {}", rect));
+//! } else {
+//!     println!("No rectangles were added to the bin.");
+//! }
+//!
+//! // List all items which could not be inserted into the bin.
+//! if !rejected.is_empty() {
+//!     rejected.iter().for_each(|item| println!("Rejected: {}", item));
+//! } else {
+//!     println!("No items were rejected.");
+//! }
+//!
+//! println!("Occupancy of the bin: {:.1} %", bin.occupancy() * 100.0);
+//! ```
+
+use crate::binpack::{BinError, PackError};
+use std::fmt::{Display, Formatter};
+use std::slice;
+use std::slice::Iter;
+
+use super::growth::{grow_bin, GrowthPolicy};
+use super::guillotine::GuillotineBin;
+use super::index::IndexedRects;
+use super::journal::TrackedVec;
+use super::sizing::pack_strip_with;
+use super::{pack_bins_with, packing_area, rect_at, visualize_bin, BinPacker};
+use crate::coord::Coord;
+use crate::dimension::Dimension;
+use crate::rectangle::Rectangle;
+
+/// List of supported heuristic rules for *SKYLINE* data structures that can be used when deciding
+/// where to place a new rectangle.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Heuristic {
+    /// Positions the rectangle at the lowest possible position of the skyline. Ties are resolved
+    /// by choosing the narrowest skyline segment. Does the Tetris placement.
+    BottomLeft,
+    /// Positions the rectangle where it leaves the least amount of unusable space below it.
+    MinWasteFit,
+}
+
+/// A single horizontal segment of the skyline.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct SkylineNode {
+    /// Left side of the skyline segment.
+    x: i32,
+    /// Vertical position of the skyline segment.
+    y: i32,
+    /// Horizontal dimension of the skyline segment.
+    width: i32,
+}
+
+impl SkylineNode {
+    fn new(x: i32, y: i32, width: i32) -> Self {
+        Self { x, y, width }
+    }
+}
+
+/// A two-dimensional rectangle bin packer using the *SKYLINE* data structure to keep track of
+/// the upper edge of the packed rectangles.
+///
+/// Space below the skyline which cannot be reached by the skyline anymore is lost, unless the
+/// optional waste map is enabled. The waste map keeps track of these areas and tries to fill
+/// them first when placing new rectangles.
+///
+/// It can be used to pack multiple rectangles of arbitrary size into a "bin" of rectangular shape
+/// with the goal to add as many rectangles as possible into the bin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SkylineBin {
+    /// Horizontal dimension of the bin.
+    bin_width: i32,
+    /// Vertical dimension of the bin.
+    bin_height: i32,
+    /// Keeps track of used areas within the bin.
+    rects_used: IndexedRects,
+    /// Keeps track of the upper edge of the used areas, ordered from left to right.
+    skyline: TrackedVec<SkylineNode>,
+
+    /// Keeps track of free areas below the skyline.
+    waste_map: GuillotineBin,
+    /// Indicates whether the waste map is used to fill free areas below the skyline.
+    use_waste_map: bool,
+    /// Margin along the bin boundaries.
+    border: i32,
+    /// Gap between neighbouring rectangles.
+    spacing: i32,
+    /// Bin size at the time of each active checkpoint.
+    checkpoints: Vec<(i32, i32)>,
+
+    /// Optionally enlarges the bin if objects do not fit.
+    growth_policy: Option<GrowthPolicy>,
+
+    /// Implicitly used for the methods defined by the `BinPacker` trait.
+    default_heuristic: Heuristic,
+    /// Implicitly used for the methods defined by the `BinPacker` trait.
+    default_rotate: bool,
+}
+
+impl BinPacker for SkylineBin {
+    fn width(&self) -> i32 {
+        self.bin_width
+    }
+
+    fn height(&self) -> i32 {
+        self.bin_height
+    }
+
+    fn clear_with(&mut self, capacity: usize) {
+        self.rects_used.clear();
+        self.rects_used.shrink_to(capacity.max(4));
+        self.skyline.clear();
+        self.skyline.shrink_to(capacity.max(4));
+        let area = self.area();
+        self.skyline.push(SkylineNode::new(
+            area.x_total(),
+            area.y_total(),
+            area.width_total(),
+        ));
+        self.waste_map.get_free_rects().clear();
+    }
+
+    fn grow(&mut self, dw: u32, dh: u32) {
+        if dw > 0 {
+            let old_right = self.area().right_total();
+            self.bin_width = self.bin_width.saturating_add(i32::from_u32(dw));
+            let area = self.area();
+            let dw = area.right_total() - old_right;
+            if dw > 0 {
+                self.skyline
+                    .push(SkylineNode::new(old_right, area.y_total(), dw));
+                self.merge_skylines();
+            }
+        }
+
+        if dh > 0 {
+            self.bin_height = self.bin_height.saturating_add(i32::from_u32(dh));
+        }
+
+        self.waste_map.set_bin_size(self.bin_width, self.bin_height);
+    }
+
+    fn shrink(&mut self, binary: bool) {
+        if self.rects_used.is_empty() {
+            return;
+        }
+
+        let mut min_x = i32::MAX;
+        let mut min_y = i32::MAX;
+        let mut max_x = i32::MIN;
+        let mut max_y = i32::MIN;
+
+        // finding borders, the spacing of rectangles is not needed at the bin boundary
+        for rect in &self.rects_used {
+            min_x = min_x.min(rect.x_total());
+            min_y = min_y.min(rect.y_total());
+            max_x = max_x.max(rect.right_total() - rect.dim().spacing);
+            max_y = max_y.max(rect.bottom_total() - rect.dim().spacing);
+        }
+
+        let shift_x = min_x - self.border;
+        let shift_y = min_y - self.border;
+        let mut new_width = max_x - shift_x + self.border;
+        let mut new_height = max_y - shift_y + self.border;
+
+        if binary {
+            // attempt to shrink to the next lower power of two
+            let mut cur_width = self.bin_width;
+            while new_width <= (cur_width >> 1) {
+                cur_width >>= 1;
+            }
+            new_width = cur_width;
+
+            let mut cur_height = self.bin_height;
+            while new_height <= (cur_height >> 1) {
+                cur_height >>= 1;
+            }
+            new_height = cur_height;
+        }
+
+        // adjusting rectangle positions
+        if new_width != self.bin_width || new_height != self.bin_height {
+            if shift_x > 0 || shift_y > 0 {
+                self.rects_used.update_all(|rect| {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
+                });
+                for rect in self.waste_map.get_free_rects().as_mut_vec() {
+                    rect.set_x_total(rect.x_total() - shift_x);
+                    rect.set_y_total(rect.y_total() - shift_y);
+                }
+            }
+
+            self.bin_width = new_width;
+            self.bin_height = new_height;
+            self.waste_map.set_bin_size(new_width, new_height);
+
+            // waste areas are always located below the skyline, i.e. within the used area
+            let area = self.area();
+            self.waste_map
+                .get_free_rects()
+                .retain(|r| area.contains_total(r));
+
+            // adjusting skyline to the new packing area
+            let (area_left, area_top) = (area.x_total(), area.y_total());
+            let area_right = area.right_total();
+            let mut skyline = Vec::with_capacity(self.skyline.len() + 1);
+            for node in &self.skyline {
+                let left = (node.x - shift_x).max(area_left);
+                let right = (node.x + node.width - shift_x).min(area_right);
+                if right > left {
+                    skyline.push(SkylineNode::new(
+                        left,
+                        (node.y - shift_y).max(area_top),
+                        right - left,
+                    ));
+                }
+            }
+            let right = skyline.last().map_or(area_left, |node| node.x + node.width);
+            if right < area_right {
+                skyline.push(SkylineNode::new(right, area_top, area_right - right));
+            }
+            *self.skyline.as_mut_vec() = skyline;
+            self.merge_skylines();
+        }
+    }
+
+    fn border(&self) -> i32 {
+        self.border
+    }
+
+    fn set_border(&mut self, border: i32) {
+        self.border = border.max(0);
+        self.waste_map.set_border(self.border);
+        self.clear();
+    }
+
+    fn spacing(&self) -> i32 {
+        self.spacing
+    }
+
+    fn set_spacing(&mut self, spacing: i32) {
+        self.spacing = spacing.max(0);
+        self.waste_map.set_spacing(self.spacing);
+        self.clear();
+    }
+
+    fn growth_policy(&self) -> Option<GrowthPolicy> {
+        self.growth_policy
+    }
+
+    fn set_growth_policy(&mut self, policy: Option<GrowthPolicy>) {
+        self.growth_policy = policy;
+    }
+
+    fn insert(&mut self, dim: &Dimension) -> Option<Rectangle> {
+        self.insert(dim, self.default_rotate, self.default_heuristic)
+    }
+
+    fn insert_list(&mut self, nodes: &[Dimension]) -> (Vec<Rectangle>, Vec<Dimension>) {
+        self.insert_list(nodes, self.default_rotate, self.default_heuristic)
+    }
+
+    /// The requested area must be located either above the skyline or in a free area of the
+    /// waste map.
+    fn insert_at(&mut self, x: i32, y: i32, dim: &Dimension) -> Option<Rectangle> {
+        let rect = rect_at(x, y, &dim.to_spaced(self.spacing), &self.area(), None)?;
+
+        if self.use_waste_map && self.waste_map.is_free_area(&rect) {
+            self.waste_map.carve_free_list(&rect);
+            self.rects_used.push(rect);
+            return Some(rect);
+        }
+
+        let left = rect.x_total();
+        let right = rect.right_total();
+        if self
+            .skyline
+            .iter()
+            .any(|node| node.x < right && node.x + node.width > left && node.y > rect.y_total())
+        {
+            return None;
+        }
+
+        let index = self.split_skyline(left);
+        self.add_skyline_level(index, &rect);
+        self.rects_used.push(rect);
+
+        Some(rect)
+    }
+
+    fn preview_insert(&self, dim: &Dimension) -> Option<Rectangle> {
+        let dim = &dim.to_spaced(self.spacing);
+        let rotate = self.default_rotate;
+        if dim.is_empty() || !self.fits_area(dim, rotate) {
+            return None;
+        }
+
+        if self.use_waste_map {
+            if let Some(result) = self.waste_map.preview_waste(dim, rotate) {
+                return Some(result);
+            }
+        }
+
+        let (_, _, result) = self.score_rect(dim, rotate, self.default_heuristic);
+        result.map(|(_, rect)| rect)
+    }
+
+    fn count_fit(&self, dim: &Dimension) -> usize {
+        let dim = &dim.to_spaced(self.spacing);
+        let mut bin = self.scratch_bin();
+        let mut count = 0;
+        while bin
+            .try_insert(dim, self.default_rotate, self.default_heuristic)
+            .is_some()
+        {
+            count += 1;
+        }
+        count
+    }
+
+    fn occupancy(&self) -> f32 {
+        if self.bin_width == 0 || self.bin_height == 0 {
+            return 0.0;
+        }
+
+        let area: i64 = self.rects_used.iter().map(|r| r.dim().area()).sum();
+
+        (area as f64 / self.bin_width.mul_wide(self.bin_height) as f64) as f32
+    }
+
+    fn as_slice(&self) -> &[Rectangle] {
+        &self.rects_used
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rects_used.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.rects_used.len()
+    }
+
+    fn iter(&self) -> Iter<'_, Rectangle> {
+        self.rects_used.iter()
+    }
+
+    fn find_by_id(&self, id: isize) -> Option<Rectangle> {
+        self.rects_used.find_by_id(id).copied()
+    }
+
+    /// Removed areas at the top of the skyline are reclaimed by lowering the skyline. All other
+    /// areas are reclaimed by the waste map, and are lost if the waste map is disabled.
+    fn remove_rect(&mut self, rect: &Rectangle) -> bool {
+        if let Some(index) = self.rects_used.position(rect) {
+            let rect = self.rects_used.remove(index);
+            if !self.lower_skyline(&rect) && self.use_waste_map {
+                self.waste_map.reclaim_rect(&rect);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        self.checkpoints.push((self.bin_width, self.bin_height));
+        self.rects_used.checkpoint();
+        self.skyline.checkpoint();
+        self.waste_map.checkpoint();
+    }
+
+    fn rollback(&mut self) -> bool {
+        if let Some((width, height)) = self.checkpoints.pop() {
+            self.bin_width = width;
+            self.bin_height = height;
+            self.rects_used.rollback();
+            self.skyline.rollback();
+            self.waste_map.rollback();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn commit(&mut self) -> bool {
+        if self.checkpoints.pop().is_some() {
+            self.rects_used.commit();
+            self.skyline.commit();
+            self.waste_map.commit();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn visualize(&self) -> String {
+        if let Some(output) = visualize_bin(self.bin_width, self.bin_height, &self.rects_used) {
+            output
+        } else {
+            format!("{self}")
+        }
+    }
+}
+
+impl SkylineBin {
+    /// Creates an empty bin of the given size.
+    ///
+    /// Minimum width and height of a bin is 1.
+    pub fn new(width: i32, height: i32) -> Self {
+        Self::with_capacity(width, height, 4)
+    }
+
+    /// Creates an empty bin of the given size and reserves space for at least `capacity` number
+    /// of mapped rectangle to improve performance.
+    ///
+    /// Minimum width and height of a bin is 1.
+    pub fn with_capacity(width: i32, height: i32, capacity: usize) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        let mut result = Self {
+            bin_width: width,
+            bin_height: height,
+            rects_used: IndexedRects::with_capacity(capacity.max(4)),
+            skyline: TrackedVec::with_capacity(capacity.max(4)),
+            waste_map: GuillotineBin::new(width, height),
+            use_waste_map: true,
+            border: 0,
+            spacing: 0,
+            checkpoints: Vec::new(),
+            default_heuristic: Heuristic::BottomLeft,
+            default_rotate: false,
+            growth_policy: None,
+        };
+        result
+            .skyline
+            .push(SkylineNode::new(0, 0, result.bin_width));
+        result.waste_map.get_free_rects().clear();
+
+        result
+    }
+
+    /// Returns the default [`Heuristic`] rule, which is used by the [`BinPacker`] trait's
+    /// [`insert`] and [`insert_list`] methods.
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn default_rule(&self) -> Heuristic {
+        self.default_heuristic
+    }
+
+    /// Can be used to override the default [`Heuristic`] rule, which is used by the [`BinPacker`]
+    /// trait's [`insert`] and [`insert_list`] methods.
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn set_default_rule(&mut self, rule: Heuristic) {
+        self.default_heuristic = rule;
+    }
+
+    /// Returns whether objects may be rotated by 90 degrees by the [`BinPacker`] trait's
+    /// [`insert`] and [`insert_list`] methods.
+    ///
+    /// [`insert`]: BinPacker::insert
+    /// [`insert_list`]: BinPacker::insert_list
+    pub fn default_rotate(&self) -> bool {
+        self.default_rotate
+    }
+
+    /// Can be used to override whether objects may be rotated by 90 degrees by the [`BinPacker`]
+    /// trait's [`insert`] and [`insert_list`] methods. Rotation is disabled by default.
+    ///
+    /// The [`Rotation`] policy of the individual objects takes precedence over this setting.
+    pub fn set_default_rotate(&mut self, rotate: bool) {
+        self.default_rotate = rotate;
+    }
+
+    /// Returns whether the waste map is used to keep track of free areas below the skyline.
+    pub fn use_waste_map(&self) -> bool {
+        self.use_waste_map
+    }
+
+    /// Specifies whether the waste map should be used to keep track of free areas below the
+    /// skyline, which would otherwise be lost. The waste map is enabled by default.
+    ///
+    /// Disabling the waste map discards all free areas that have been collected so far.
+    pub fn set_use_waste_map(&mut self, enable: bool) {
+        self.use_waste_map = enable;
+        if !enable {
+            self.waste_map.get_free_rects().clear();
+        }
+    }
+
+    /// Inserts a single [`Dimension`] object into the bin.
+    ///
+    /// `dim` refers to the object to be packed into the bin.
+    ///
+    /// `rotate` indicates whether the object may be rotated by 90 degrees if the rotated
+    /// placement scores better than the upright placement. The [`Rotation`] policy of the object
+    /// takes precedence over this parameter.
+    ///
+    /// `rule` specifies the rectangle placement rule to use for the packing operation.
+    ///
+    /// Free areas of the waste map are considered first if the waste map is enabled.
+    ///
+    /// Returns a copy of the packed [`Rectangle`] if the object was inserted successful,
+    /// or `None` otherwise.
+    pub fn insert(&mut self, dim: &Dimension, rotate: bool, rule: Heuristic) -> Option<Rectangle> {
+        let dim = &dim.to_spaced(self.spacing);
+        loop {
+            let result = self.try_insert(dim, rotate, rule);
+            if result.is_some() || !grow_bin(self, self.growth_policy, slice::from_ref(dim), rotate)
+            {
+                return result;
+            }
+        }
+    }
+
+    /// Inserts a single `Dimension` object into the bin without enlarging the bin.
+    fn try_insert(&mut self, dim: &Dimension, rotate: bool, rule: Heuristic) -> Option<Rectangle> {
+        // Empty or too big dimension objects are always rejected
+        if dim.is_empty() || !self.fits_area(dim, rotate) {
+            return None;
+        }
+
+        if self.use_waste_map {
+            if let Some(new_node) = self.waste_map.insert_waste(dim, rotate) {
+                self.rects_used.push(new_node);
+                return Some(new_node);
+            }
+        }
+
+        let (_, _, result) = self.score_rect(dim, rotate, rule);
+
+        if let Some((index, new_node)) = result {
+            self.add_skyline_level(index, &new_node);
+            self.rects_used.push(new_node);
+
+            Some(new_node)
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to insert the given list of [`Dimension`] objects into the bin.
+    ///
+    /// `nodes` specifies the list of [`Dimension`] objects to insert. All successfully inserted
+    /// objects will be removed from the list in the process.
+    ///
+    /// `rotate` indicates whether objects may be rotated by 90 degrees if the rotated
+    /// placement scores better than the upright placement. The [`Rotation`] policy of the objects
+    /// takes precedence over this parameter.
+    ///
+    /// `rule` specifies the rectangle placement rule to use for the packing operations.
+    ///
+    /// Returns a list with all successfully inserted [`Rectangle`] objects.
+    ///
+    /// This method performs slower than [`insert`], but may result in more tightly
+    /// packed bins for greater numbers of dimension objects.
+    ///
+    /// [`insert`]: SkylineBin::insert
+    pub fn insert_list(
+        &mut self,
+        nodes: &[Dimension],
+        rotate: bool,
+        rule: Heuristic,
+    ) -> (Vec<Rectangle>, Vec<Dimension>) {
+        let nodes: Vec<_> = nodes.iter().map(|n| n.to_spaced(self.spacing)).collect();
+        let (mut inserted, mut rejected) = self.try_insert_list(&nodes, rotate, rule);
+        while !rejected.is_empty() && grow_bin(self, self.growth_policy, &rejected, rotate) {
+            let (mut new_nodes, remaining) = self.try_insert_list(&rejected, rotate, rule);
+            inserted.append(&mut new_nodes);
+            rejected = remaining;
+        }
+
+        let rejected = rejected.into_iter().map(|n| n.to_spaced(0)).collect();
+        (inserted, rejected)
+    }
+
+    /// Attempts to insert the given list of `Dimension` objects into the bin without enlarging
+    /// the bin.
+    fn try_insert_list(
+        &mut self,
+        nodes: &[Dimension],
+        rotate: bool,
+        rule: Heuristic,
+    ) -> (Vec<Rectangle>, Vec<Dimension>) {
+        let mut inserted = Vec::with_capacity(nodes.len());
+        let mut rejected = nodes.to_vec();
+
+        while !rejected.is_empty() {
+            let mut best_score1 = i64::MAX;
+            let mut best_score2 = i64::MAX;
+            let mut best_index = None;
+            let mut best_node = None;
+
+            for (i, dim) in rejected.iter().enumerate() {
+                // Empty dimension objects would result in degenerate skyline nodes
+                if dim.is_empty() {
+                    continue;
+                }
+
+                if let (score1, score2, Some(new_node)) = self.score_rect(dim, rotate, rule) {
+                    if score1 < best_score1 || (score1 == best_score1 && score2 < best_score2) {
+                        best_score1 = score1;
+                        best_score2 = score2;
+                        best_index = Some(i);
+                        best_node = Some(new_node);
+                    }
+                }
+            }
+
+            if best_index.is_none() {
+                break;
+            }
+
+            debug_assert!(best_node.is_some());
+
+            let (skyline_index, new_node) = best_node.unwrap();
+            self.add_skyline_level(skyline_index, &new_node);
+            self.rects_used.push(new_node);
+            inserted.push(new_node);
+            rejected.swap_remove(best_index.unwrap());
+        }
+
+        (inserted, rejected)
+    }
+
+    /// Computes the placement score for placing the given `Dimension` with the given rule.
+    ///
+    /// Returns a tuple consisting of the primary and secondary placement scores, as well as
+    /// the index of the skyline node and the `Rectangle` structure where the requested
+    /// `Dimension` can be placed.
+    fn score_rect(
+        &self,
+        dim: &Dimension,
+        rotate: bool,
+        rule: Heuristic,
+    ) -> (i64, i64, Option<(usize, Rectangle)>) {
+        match rule {
+            Heuristic::BottomLeft => self.find_bl(dim, rotate),
+            Heuristic::MinWasteFit => self.find_mwf(dim, rotate),
+        }
+    }
+
+    /// Attempts to find the best rectangle position in the bin, using the [`Heuristic::BottomLeft`] rule.
+    fn find_bl(&self, dim: &Dimension, rotate: bool) -> (i64, i64, Option<(usize, Rectangle)>) {
+        let mut result = None;
+
+        let mut best_height = i64::MAX;
+        let mut best_width = i64::MAX;
+        for (i, node) in self.skyline.iter().enumerate() {
+            for (dim, rotated) in dim.orientations(rotate) {
+                if let Some(y) = self.rectangle_fits(i, dim.width_total(), dim.height_total()) {
+                    let top_y = y as i64 + dim.height_total() as i64;
+                    let width = node.width as i64;
+                    if top_y < best_height || (top_y == best_height && width < best_width) {
+                        let mut best_node = Rectangle::new(0, 0, dim);
+                        best_node.set_location_total(node.x, y);
+                        best_node.set_rotated(rotated);
+                        result = Some((i, best_node));
+                        best_height = top_y;
+                        best_width = width;
+                    }
+                }
+            }
+        }
+
+        (best_height, best_width, result)
+    }
+
+    /// Attempts to find the best rectangle position in the bin, using the [`Heuristic::MinWasteFit`] rule.
+    fn find_mwf(&self, dim: &Dimension, rotate: bool) -> (i64, i64, Option<(usize, Rectangle)>) {
+        let mut result = None;
+
+        let mut best_wasted_area = i64::MAX;
+        let mut best_height = i64::MAX;
+        for (i, node) in self.skyline.iter().enumerate() {
+            for (dim, rotated) in dim.orientations(rotate) {
+                if let Some(y) = self.rectangle_fits(i, dim.width_total(), dim.height_total()) {
+                    let wasted_area = self.compute_wasted_area(i, dim.width_total(), y);
+                    let top_y = y as i64 + dim.height_total() as i64;
+                    if wasted_area < best_wasted_area
+                        || (wasted_area == best_wasted_area && top_y < best_height)
+                    {
+                        let mut best_node = Rectangle::new(0, 0, dim);
+                        best_node.set_location_total(node.x, y);
+                        best_node.set_rotated(rotated);
+                        result = Some((i, best_node));
+                        best_wasted_area = wasted_area;
+                        best_height = top_y;
+                    }
+                }
+            }
+        }
+
+        (best_wasted_area, best_height, result)
+    }
+
+    /// Checks whether a rectangle of the given size can be placed at the left side of the
+    /// skyline node at the given index.
+    ///
+    /// Returns the vertical position of the rectangle if it fits, and `None` otherwise.
+    fn rectangle_fits(&self, index: usize, width: i32, height: i32) -> Option<i32> {
+        let area = self.area();
+        let x = self.skyline[index].x;
+        // compare against the space left to avoid overflowing near the greatest coordinate
+        if width > area.right_total() - x {
+            return None;
+        }
+
+        let mut width_left = width;
+        let mut y = self.skyline[index].y;
+        for node in &self.skyline[index..] {
+            if width_left <= 0 {
+                break;
+            }
+            y = y.max(node.y);
+            width_left -= node.width;
+        }
+
+        if width_left > 0 || height > area.bottom_total() - y {
+            None
+        } else {
+            Some(y)
+        }
+    }
+
+    /// Returns the area of the bin which is available for packing rectangles.
+    fn area(&self) -> Rectangle {
+        packing_area(self.bin_width, self.bin_height, self.border, self.spacing)
+    }
+
+    /// Returns whether the given `Dimension` fits into the empty bin, optionally rotated by
+    /// 90 degrees.
+    fn fits_area(&self, dim: &Dimension, rotate: bool) -> bool {
+        let area = self.area();
+        dim.fits_into(area.width(), area.height(), rotate)
+    }
+
+    /// Creates a bin for simulating insertions, which shares the configuration, the skyline and
+    /// the waste map of this bin, but holds no mapped rectangles.
+    fn scratch_bin(&self) -> Self {
+        let mut skyline = self.skyline.to_vec();
+        let mut result = Self {
+            bin_width: self.bin_width,
+            bin_height: self.bin_height,
+            rects_used: IndexedRects::with_capacity(4),
+            skyline: TrackedVec::with_capacity(skyline.len()),
+            waste_map: self.waste_map.scratch_bin(),
+            use_waste_map: self.use_waste_map,
+            border: self.border,
+            spacing: self.spacing,
+            checkpoints: Vec::new(),
+            default_heuristic: self.default_heuristic,
+            default_rotate: self.default_rotate,
+            growth_policy: None,
+        };
+        result.skyline.append(&mut skyline);
+
+        result
+    }
+
+    /// Computes the area below a rectangle of the given width at the given vertical position,
+    /// which is lost if the rectangle is placed at the skyline node of the given index.
+    fn compute_wasted_area(&self, index: usize, width: i32, y: i32) -> i64 {
+        let rect_left = self.skyline[index].x;
+        let rect_right = rect_left.saturating_add(width);
+
+        let mut wasted_area = 0i64;
+        for node in &self.skyline[index..] {
+            if node.x >= rect_right || node.x + node.width <= rect_left {
+                break;
+            }
+
+            let left_side = node.x;
+            let right_side = rect_right.min(left_side + node.width);
+            debug_assert!(y >= node.y);
+            wasted_area += (right_side - left_side) as i64 * (y - node.y) as i64;
+        }
+
+        wasted_area
+    }
+
+    /// Adds the area below a rectangle of the given width at the given vertical position to the
+    /// waste map, if the rectangle is placed at the skyline node of the given index.
+    fn add_waste_map_area(&mut self, index: usize, width: i32, y: i32) {
+        let rect_left = self.skyline[index].x;
+        let rect_right = rect_left.saturating_add(width);
+
+        for i in index..self.skyline.len() {
+            let node = self.skyline[i];
+            if node.x >= rect_right || node.x + node.width <= rect_left {
+                break;
+            }
+
+            let left_side = node.x;
+            let right_side = rect_right.min(left_side + node.width);
+            if y > node.y {
+                let waste = Rectangle::new(
+                    left_side,
+                    node.y,
+                    Dimension::with_id(0, right_side - left_side, y - node.y, 0),
+                );
+                self.waste_map.get_free_rects().push(waste);
+            }
+        }
+    }
+
+    /// Raises the skyline by the given rectangle, which is placed at the skyline node of the
+    /// given index.
+    fn add_skyline_level(&mut self, index: usize, rect: &Rectangle) {
+        // First track all wasted areas and mark them into the waste map if we're using one
+        if self.use_waste_map {
+            self.add_waste_map_area(index, rect.width_total(), rect.y_total());
+        }
+
+        let new_node = SkylineNode::new(rect.x_total(), rect.bottom_total(), rect.width_total());
+        self.skyline.insert(index, new_node);
+
+        // Shrink or remove all skyline nodes which are covered by the new node
+        let i = index + 1;
+        while i < self.skyline.len() {
+            let prev = self.skyline[i - 1];
+            let mut node = self.skyline[i];
+            if node.x < prev.x + prev.width {
+                let shrink = prev.x + prev.width - node.x;
+                node.x += shrink;
+                node.width -= shrink;
+
+                if node.width <= 0 {
+                    self.skyline.remove(i);
+                } else {
+                    self.skyline.set(i, node);
+                    break;
+                }
+            } else {
+                break;
+            }
+        }
+
+        self.merge_skylines();
+    }
+
+    /// Lowers the skyline to the top of the given removed rectangle, if its bottom side lies on the
+    /// skyline.
+    ///
+    /// Returns `true` if the skyline has been lowered, and `false` otherwise.
+    fn lower_skyline(&mut self, rect: &Rectangle) -> bool {
+        let (left, right) = (rect.x_total(), rect.right_total());
+        let mut covered = self
+            .skyline
+            .iter()
+            .filter(|node| node.x < right && node.x + node.width > left);
+        if !covered.all(|node| node.y == rect.bottom_total()) {
+            return false;
+        }
+
+        // neighboring nodes are merged, so the rectangle is covered by a single node
+        let index = self.split_skyline(left);
+        let node = self.skyline[index];
+        if node.x + node.width > right {
+            self.skyline.insert(
+                index + 1,
+                SkylineNode::new(right, node.y, node.x + node.width - right),
+            );
+        }
+        self.skyline.set(
+            index,
+            SkylineNode::new(left, rect.y_total(), rect.width_total()),
+        );
+        self.merge_skylines();
+
+        true
+    }
+
+    /// Splits the skyline node which covers the given horizontal position, so that a skyline node
+    /// starts at this position.
+    ///
+    /// Returns the index of the skyline node starting at the given position.
+    fn split_skyline(&mut self, x: i32) -> usize {
+        let index = self
+            .skyline
+            .iter()
+            .position(|node| node.x + node.width > x)
+            .unwrap_or(self.skyline.len() - 1);
+
+        let node = self.skyline[index];
+        if node.x >= x {
+            return index;
+        }
+
+        self.skyline.update(index, |node| node.width = x - node.x);
+        self.skyline.insert(
+            index + 1,
+            SkylineNode::new(x, node.y, node.x + node.width - x),
+        );
+        index + 1
+    }
+
+    /// Merges all neighboring skyline nodes of the same vertical position.
+    fn merge_skylines(&mut self) {
+        let mut i = 0usize;
+        while i + 1 < self.skyline.len() {
+            if self.skyline[i].y == self.skyline[i + 1].y {
+                let width = self.skyline[i + 1].width;
+                self.skyline.update(i, |node| node.width += width);
+                self.skyline.remove(i + 1);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+impl<Idx> std::ops::Index<Idx> for SkylineBin
+where
+    Idx: std::slice::SliceIndex<[Rectangle]>,
+{
+    type Output = Idx::Output;
+
+    fn index(&self, index: Idx) -> &Self::Output {
+        &self.rects_used[index]
+    }
+}
+
+impl Display for SkylineBin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Bin(width: {}, height: {}, rectangles: {})",
+            self.bin_width,
+            self.bin_height,
+            self.rects_used.len()
+        )
+    }
+}
+
+/// A convenience function that attempts to insert a given list of `Dimension` objects into a
+/// variable number of bins.
+///
+/// New bins are created on demand, using the given heuristic `rule`. Specify `true` for `rotate`
+/// to allow rotating objects by 90 degrees if it results in a better placement.
+///
+/// Specify true for `optimize` to use [`insert_list`] internally, which results in an improved
+/// bin layout but at the cost of a worse processing performance.
+///
+/// [`insert_list`]: SkylineBin::insert_list
+///
+/// Nodes with the same [`group`] key are always placed into the same bin.
+///
+/// [`group`]: Dimension::group
+///
+/// Returns a list of bins with the packed rectangle nodes as a [`Result`] value.
+///
+/// # Errors
+///
+/// A [`PackError`] is returned if any nodes could not be packed, listing each rejected node with
+/// the reason of rejection. All other nodes are still packed into the bins provided by the error.
+///
+/// # Examples
+/// ```
+/// use binpack2d::binpack::BinPacker;
+/// use binpack2d::binpack::skyline::{Heuristic, pack_bins};
+/// use binpack2d::dimension::Dimension;
+///
+/// // Defining three items of different size
+/// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
+///
+/// // Returned list of bin object contains all nodes, placed according to the given heuristic rule
+/// let bins = pack_bins(&nodes, 16, 12, false, Heuristic::BottomLeft, true)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(1, bins.len());
+/// assert_eq!(3, bins[0].len());
+/// ```
+pub fn pack_bins(
+    nodes: &[Dimension],
+    bin_width: i32,
+    bin_height: i32,
+    rotate: bool,
+    rule: Heuristic,
+    optimized: bool,
+) -> Result<Vec<SkylineBin>, PackError<SkylineBin>> {
+    let result = pack_bins_with(nodes, bin_width, bin_height, optimized, |width, height| {
+        let mut bin = SkylineBin::new(width, height);
+        bin.set_default_rotate(rotate);
+        bin.set_default_rule(rule);
+        Box::new(bin)
+    });
+
+    result
+        .map(|bins| bins.into_iter().map(|bin| *bin).collect())
+        .map_err(|err| err.map_bins(|bin| *bin))
+}
+
+/// A convenience function that packs a given list of `Dimension` objects into a strip of the
+/// given width, and reduces the height of the strip as much as possible.
+///
+/// The strip is packed via [`insert_list`], using the given heuristic `rule`. Specify `true` for `rotate`
+/// to allow rotating objects by 90 degrees if it results in a better placement.
+///
+/// [`insert_list`]: SkylineBin::insert_list
+///
+/// Returns the bin with the packed rectangle nodes as a [`Result`] value. The height of the bin
+/// is the final strip height.
+///
+/// # Errors
+///
+/// A [`BinError`] is returned for nodes which are either empty or too wide for the strip.
+///
+/// # Examples
+/// ```
+/// use binpack2d::binpack::BinPacker;
+/// use binpack2d::binpack::skyline::{Heuristic, pack_strip};
+/// use binpack2d::dimension::Dimension;
+///
+/// // Defining three items of different size
+/// let nodes = vec![Dimension::new(2, 4), Dimension::new(8, 6), Dimension::new(6, 6)];
+///
+/// // Returned strip contains all nodes, placed according to the given heuristic rule
+/// let strip = pack_strip(&nodes, 8, false, Heuristic::BottomLeft)
+///     .expect("Items should not be rejected");
+///
+/// assert_eq!(8, strip.width());
+/// assert_eq!(3, strip.len());
+/// ```
+pub fn pack_strip(
+    nodes: &[Dimension],
+    strip_width: i32,
+    rotate: bool,
+    rule: Heuristic,
+) -> Result<SkylineBin, BinError> {
+    let strip = pack_strip_with(nodes, strip_width, |width, height| {
+        let mut bin = SkylineBin::new(width, height);
+        bin.set_default_rotate(rotate);
+        bin.set_default_rule(rule);
+        Box::new(bin)
+    })?;
+
+    Ok(*strip)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,185 @@
+use super::*;
+
+#[test]
+fn bin_shrink() {
+    let mut bin = SkylineBin::new(16, 16);
+    bin.insert(&Dimension::new(6, 7), false, Heuristic::BottomLeft);
+    bin.shrink(true);
+    assert_eq!(8, bin.width());
+    assert_eq!(8, bin.height());
+
+    bin.shrink(false);
+    assert_eq!(6, bin.width());
+    assert_eq!(7, bin.height());
+
+    let result = bin.insert(&Dimension::new(1, 1), false, Heuristic::BottomLeft);
+    assert!(result.is_none());
+}
+
+#[test]
+fn bin_insert() {
+    let nodes = [
+        Dimension::with_padding(2, 4, 0),
+        Dimension::with_padding(6, 4, 1),
+        Dimension::with_padding(10, 3, 1),
+        Dimension::with_padding(6, 6, 0),
+        Dimension::with_padding(4, 4, 2),
+        Dimension::with_padding(3, 8, 0),
+        Dimension::with_padding(8, 3, 1),
+    ];
+
+    for rule in [Heuristic::BottomLeft, Heuristic::MinWasteFit] {
+        let mut bin = SkylineBin::new(16, 16);
+        assert_eq!(16, bin.width());
+        assert_eq!(16, bin.height());
+
+        for node in nodes.iter() {
+            bin.insert(node, true, rule);
+        }
+
+        for rect1 in bin.iter() {
+            for rect2 in bin.iter() {
+                if rect1 != rect2 {
+                    assert!(!rect1.intersects(rect2));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bin_insert_list() {
+    let nodes = [
+        Dimension::with_padding(2, 4, 0),
+        Dimension::with_padding(6, 4, 1),
+        Dimension::with_padding(10, 3, 1),
+        Dimension::with_padding(6, 6, 0),
+        Dimension::with_padding(4, 4, 2),
+        Dimension::with_padding(3, 8, 0),
+        Dimension::with_padding(8, 3, 1),
+    ];
+
+    for rule in [Heuristic::BottomLeft, Heuristic::MinWasteFit] {
+        let mut bin = SkylineBin::new(16, 16);
+        let (inserted, rejected) = bin.insert_list(&nodes, true, rule);
+        assert_eq!(nodes.len(), inserted.len() + rejected.len());
+
+        for rect1 in bin.iter() {
+            for rect2 in bin.iter() {
+                if rect1 != rect2 {
+                    assert!(!rect1.intersects(rect2));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn bin_waste_map() {
+    // the second item leaves a 4x4 gap below the third item
+    let nodes = [
+        Dimension::new(4, 8),
+        Dimension::new(4, 4),
+        Dimension::new(8, 4),
+        Dimension::new(4, 4),
+    ];
+
+    let mut bin = SkylineBin::new(8, 12);
+    for node in &nodes[..3] {
+        assert!(bin.insert(node, false, Heuristic::BottomLeft).is_some());
+    }
+
+    // the gap can only be reached through the waste map
+    let rect = bin.insert(&nodes[3], false, Heuristic::BottomLeft);
+    assert_eq!(Some((4, 4)), rect.map(|r| (r.x(), r.y())));
+
+    let mut bin = SkylineBin::new(8, 12);
+    bin.set_use_waste_map(false);
+    for node in &nodes[..3] {
+        assert!(bin.insert(node, false, Heuristic::BottomLeft).is_some());
+    }
+    assert!(bin
+        .insert(&nodes[3], false, Heuristic::BottomLeft)
+        .is_none());
+}
+
+#[test]
+fn bin_large() {
+    // bin areas exceed the range of i32
+    let mut bin = SkylineBin::new(100_000, 100_000);
+    let result = bin.insert(
+        &Dimension::new(50_000, 50_000),
+        false,
+        Heuristic::BottomLeft,
+    );
+    assert!(result.is_some());
+    assert_eq!(0.25, bin.occupancy());
+
+    bin.grow(u32::MAX, u32::MAX);
+    assert_eq!((i32::MAX, i32::MAX), (bin.width(), bin.height()));
+    let result = bin.insert(
+        &Dimension::new(50_000, 50_000),
+        false,
+        Heuristic::BottomLeft,
+    );
+    assert_eq!(Some((50_000, 0)), result.map(|r| (r.x(), r.y())));
+}
+
+#[test]
+fn bin_max_coord() {
+    // edges of rectangles near the greatest coordinate do not overflow
+    let half = i32::MAX / 2 + 10;
+    for rule in [Heuristic::BottomLeft, Heuristic::MinWasteFit] {
+        for use_waste_map in [false, true] {
+            let mut bin = SkylineBin::new(i32::MAX, 100);
+            bin.set_use_waste_map(use_waste_map);
+            assert!(bin.insert(&Dimension::new(half, 50), false, rule).is_some());
+            let rect = bin.insert(&Dimension::new(half, 50), false, rule);
+            assert_eq!(Some((0, 50)), rect.map(|r| (r.x(), r.y())));
+            assert!(bin.insert(&Dimension::new(half, 50), false, rule).is_none());
+            bin.shrink(false);
+            assert_eq!((half, 100), (bin.width(), bin.height()));
+
+            let mut bin = SkylineBin::new(100, i32::MAX);
+            bin.set_use_waste_map(use_waste_map);
+            bin.set_spacing(5);
+            assert!(bin.insert(&Dimension::new(45, half), false, rule).is_some());
+            let rect = bin.insert(&Dimension::new(45, half), false, rule);
+            assert_eq!(Some((50, 0)), rect.map(|r| (r.x(), r.y())));
+            assert!(bin.insert(&Dimension::new(45, half), false, rule).is_none());
+            assert!(bin.validate().is_empty());
+        }
+    }
+}
+
+#[test]
+fn bin_remove_no_waste_map() {
+    for rule in [Heuristic::BottomLeft, Heuristic::MinWasteFit] {
+        let mut bin = SkylineBin::new(8, 8);
+        bin.set_use_waste_map(false);
+        let dim = Dimension::new(8, 4);
+        let bottom = bin.insert(&dim, false, rule).unwrap();
+        let top = bin.insert(&dim, false, rule).unwrap();
+
+        // the topmost rectangle is reclaimed by lowering the skyline
+        assert!(bin.remove_rect(&top));
+        assert_eq!(Some(top), bin.insert(&dim, false, rule));
+
+        // covered rectangles are lost without the waste map
+        assert!(bin.remove_rect(&bottom));
+        assert!(bin.insert(&dim, false, rule).is_none());
+        assert!(bin.remove_rect(&top));
+        assert_eq!(Some(top), bin.insert(&dim, false, rule));
+        assert!(bin.insert(&dim, false, rule).is_none());
+
+        // partially covered rectangles are lost as well
+        let mut bin = SkylineBin::new(8, 8);
+        bin.set_use_waste_map(false);
+        let left = bin.insert(&Dimension::new(4, 4), false, rule).unwrap();
+        bin.insert(&Dimension::new(4, 2), false, rule).unwrap();
+        bin.insert(&Dimension::new(8, 2), false, rule).unwrap();
+        assert!(bin.remove_rect(&left));
+        assert!(bin.insert(&Dimension::new(4, 4), false, rule).is_none());
+        assert!(bin.validate().is_empty());
+    }
+}
@@ -1,4 +1,6 @@
 use super::*;
+use crate::binpack::growth::GrowthStrategy;
+use crate::dimension::{Padding, Rotation};
 
 fn bin_shrink(bin_type: BinType) {
     let mut bin = bin_new(bin_type, 16, 16);
@@ -91,7 +93,7 @@ fn bin_find_by_id(bin_type: BinType) {
 
     let result = bin.find_by_id(2);
     assert!(result.is_some());
-    assert_eq!(1, result.unwrap().dim().padding());
+    assert_eq!(Padding::uniform(1), result.unwrap().dim().padding());
 }
 
 fn bin_iter_slice(bin_type: BinType) {
@@ -111,6 +113,392 @@ fn bin_iter_slice(bin_type: BinType) {
     assert_eq!(2, rects.len());
 }
 
+fn bin_remove(bin_type: BinType) {
+    let mut bin = bin_new(bin_type, 8, 8);
+    assert!(bin.insert(&Dimension::with_id(1, 8, 4, 0)).is_some());
+    let rect2 = bin.insert(&Dimension::with_id(2, 8, 4, 0));
+    assert!(rect2.is_some());
+    assert!(bin.insert(&Dimension::with_id(3, 8, 4, 0)).is_none());
+
+    let rect1 = bin.remove(1);
+    assert_eq!(Some(1), rect1.map(|r| r.id()));
+    assert!(bin.remove(1).is_none());
+    assert_eq!(1, bin.len());
+
+    // space of the removed rectangle can be reused
+    assert!(bin.insert(&Dimension::with_id(3, 8, 4, 0)).is_some());
+
+    let rect2 = rect2.unwrap();
+    assert!(bin.remove_rect(&rect2));
+    assert!(!bin.remove_rect(&rect2));
+    assert_eq!(1, bin.len());
+    assert!(bin.find_by_id(2).is_none());
+}
+
+fn bin_pack_strip(bin_type: BinType) {
+    let nodes = vec![
+        Dimension::new(2, 4),
+        Dimension::new(8, 6),
+        Dimension::new(6, 6),
+    ];
+
+    let strip = pack_strip(bin_type, &nodes, 8).expect("Items should not be rejected");
+    assert_eq!(8, strip.width());
+    assert_eq!(12, strip.height());
+    assert_eq!(nodes.len(), strip.len());
+
+    for rect1 in strip.iter() {
+        for rect2 in strip.iter() {
+            if rect1 != rect2 {
+                assert!(!rect1.intersects(rect2));
+            }
+        }
+    }
+
+    let result = pack_strip(bin_type, &[Dimension::new(9, 9)], 8);
+    assert_eq!(BinError::ItemTooBig, result.err().unwrap());
+}
+
+fn bin_growth_policy(bin_type: BinType) {
+    let mut bin = bin_new(bin_type, 8, 8);
+    bin.set_growth_policy(Some(GrowthPolicy::new(
+        GrowthStrategy::NextPowerOfTwo,
+        32,
+        32,
+    )));
+    assert!(bin.insert(&Dimension::new(8, 8)).is_some());
+    assert!(bin.insert(&Dimension::new(8, 8)).is_some());
+    assert_eq!(16, bin.width());
+    assert_eq!(8, bin.height());
+
+    // items which do not fit into a bin of maximum size do not enlarge the bin
+    assert!(bin.insert(&Dimension::new(33, 1)).is_none());
+    assert_eq!(16, bin.width());
+    assert_eq!(8, bin.height());
+
+    let nodes = vec![Dimension::new(4, 4); 4];
+    let mut bin = bin_new(bin_type, 4, 4);
+    let (inserted, rejected) = bin.insert_list(&nodes);
+    assert_eq!(1, inserted.len());
+    assert_eq!(3, rejected.len());
+
+    let mut bin = bin_new(bin_type, 4, 4);
+    bin.set_growth_policy(Some(GrowthPolicy::new(
+        GrowthStrategy::DoubleShorterSide,
+        16,
+        16,
+    )));
+    let (inserted, rejected) = bin.insert_list(&nodes);
+    assert_eq!(4, inserted.len());
+    assert!(rejected.is_empty());
+    assert_eq!(8, bin.width());
+    assert_eq!(8, bin.height());
+
+    for rect1 in bin.iter() {
+        for rect2 in bin.iter() {
+            if rect1 != rect2 {
+                assert!(!rect1.intersects(rect2));
+            }
+        }
+    }
+}
+
+fn bin_insert_at(bin_type: BinType) {
+    let mut bin = bin_new(bin_type, 16, 16);
+    let rect = bin.insert_at(4, 4, &Dimension::with_id(1, 8, 8, 0));
+    assert_eq!(Some((4, 4)), rect.map(|r| (r.x(), r.y())));
+
+    // occupied area or outside of the bin
+    assert!(bin.insert_at(8, 8, &Dimension::new(8, 8)).is_none());
+    assert!(bin.insert_at(12, 0, &Dimension::new(8, 4)).is_none());
+    assert!(bin
+        .insert_at(0, 0, &Dimension::with_padding(2, 2, 1))
+        .is_none());
+    assert!(bin.insert_at(0, 0, &Dimension::new(0, 2)).is_none());
+
+    let mut forced = Dimension::new(4, 2);
+    forced.set_rotation(Rotation::Forced);
+    let rect = bin.insert_at(14, 12, &forced).expect("Area should be free");
+    assert!(rect.is_rotated());
+    assert_eq!((2, 4), (rect.width(), rect.height()));
+
+    // subsequent insertions respect the placed rectangles
+    let nodes = vec![Dimension::new(2, 2); 60];
+    let (inserted, _) = bin.insert_list(&nodes);
+    assert!(!inserted.is_empty());
+    for rect1 in bin.iter() {
+        assert!(rect1.x() >= 0 && rect1.x() + rect1.width() <= bin.width());
+        assert!(rect1.y() >= 0 && rect1.y() + rect1.height() <= bin.height());
+        for rect2 in bin.iter() {
+            if rect1 != rect2 {
+                assert!(!rect1.intersects(rect2));
+            }
+        }
+    }
+}
+
+fn bin_preview_insert(bin_type: BinType) {
+    let mut bin = bin_new(bin_type, 8, 8);
+    let node = Dimension::new(4, 4);
+    assert!(bin.can_fit(&node));
+    assert_eq!(4, bin.count_fit(&node));
+    assert!(!bin.can_fit(&Dimension::new(0, 4)));
+    assert!(!bin.can_fit(&Dimension::new(9, 4)));
+
+    for count in (0..4).rev() {
+        let preview = bin.preview_insert(&node);
+        assert!(preview.is_some());
+        assert_eq!(3 - count, bin.len());
+        assert_eq!(preview, bin.insert(&node));
+        assert_eq!(count, bin.count_fit(&node));
+    }
+    assert!(!bin.can_fit(&node));
+    assert!(bin.preview_insert(&node).is_none());
+
+    // simulated insertions match subsequent insertions into a partially filled bin
+    let mut bin = bin_new(bin_type, 40, 30);
+    bin.set_spacing(1);
+    bin.insert_list(&[
+        Dimension::new(13, 7),
+        Dimension::new(5, 11),
+        Dimension::new(9, 3),
+    ]);
+    let small = Dimension::new(6, 4);
+    let count = bin.count_fit(&small);
+    assert!(count > 0);
+    assert_eq!(count, std::iter::from_fn(|| bin.insert(&small)).count());
+
+    // growth policy is not considered
+    let mut bin = bin_new(bin_type, 4, 4);
+    bin.set_growth_policy(Some(GrowthPolicy::new(
+        GrowthStrategy::DoubleShorterSide,
+        16,
+        16,
+    )));
+    bin.insert(&node);
+    assert!(!bin.can_fit(&node));
+    assert_eq!(0, bin.count_fit(&node));
+    assert_eq!((4, 4), (bin.width(), bin.height()));
+}
+
+fn bin_checkpoint(bin_type: BinType) {
+    let nodes: Vec<_> = (1..=6).map(|id| Dimension::with_id(id, 4, 6, 0)).collect();
+    let prepare = || {
+        let mut bin = bin_new(bin_type, 16, 16);
+        bin.set_growth_policy(Some(GrowthPolicy::new(
+            GrowthStrategy::DoubleShorterSide,
+            64,
+            64,
+        )));
+        bin.insert_list(&nodes[..3]);
+        bin
+    };
+    let mut bin = prepare();
+    let mut reference = prepare();
+    assert!(!bin.rollback());
+    assert!(!bin.commit());
+
+    bin.checkpoint();
+    bin.insert(&Dimension::new(16, 12));
+    bin.checkpoint();
+    bin.remove(2);
+    bin.insert_list(&vec![Dimension::new(3, 3); 20]);
+    assert!(bin.commit());
+    bin.shrink(false);
+    assert!(bin.rollback());
+
+    // the bin behaves exactly like a bin without the discarded operations
+    assert_eq!((16, 16), (bin.width(), bin.height()));
+    assert_eq!(reference.as_slice(), bin.as_slice());
+    let mut more = nodes[3..].to_vec();
+    more.extend((8..20).map(|id| Dimension::with_id(id, 1, 1, 0)));
+    assert_eq!(reference.insert_list(&more), bin.insert_list(&more));
+    assert_eq!(reference.as_slice(), bin.as_slice());
+
+    bin.checkpoint();
+    let rect = bin.insert(&Dimension::with_id(7, 1, 1, 0));
+    assert!(bin.commit());
+    assert_eq!(rect, bin.find_by_id(7));
+    assert!(!bin.rollback());
+}
+
+fn bin_pack_groups(bin_type: BinType) {
+    let grouped = |id: isize, width: i32, height: i32, group: isize| {
+        let mut dim = Dimension::with_id(id, width, height, 0);
+        dim.set_group(Some(group));
+        dim
+    };
+
+    // a greedy packing would split the groups over the bins
+    let mut nodes = vec![
+        Dimension::with_id(1, 8, 8, 0),
+        Dimension::with_id(2, 8, 8, 0),
+    ];
+    nodes.extend((3..7).map(|id| grouped(id, 8, 8, 1)));
+    nodes.extend((7..9).map(|id| grouped(id, 8, 8, 2)));
+    for optimized in [false, true] {
+        let bins = pack_bins(bin_type, &nodes, 16, 16, optimized).expect("Items should fit");
+        assert_eq!(nodes.len(), bins.iter().map(|bin| bin.len()).sum());
+        for group in [1, 2] {
+            let count = bins
+                .iter()
+                .filter(|bin| bin.iter().any(|r| r.dim().group() == Some(group)))
+                .count();
+            assert_eq!(1, count);
+        }
+    }
+
+    // group does not fit into a single bin, but all other nodes are still packed
+    nodes.push(grouped(9, 8, 8, 1));
+    for optimized in [false, true] {
+        let err = pack_bins(bin_type, &nodes, 16, 16, optimized)
+            .err()
+            .unwrap();
+        let ids: Vec<isize> = err.rejections().iter().map(|r| r.id()).collect();
+        assert_eq!(vec![3, 4, 5, 6, 9], ids);
+        assert!(err
+            .rejections()
+            .iter()
+            .all(|r| r.reason() == BinError::GroupTooBig));
+        assert_eq!(4, err.bins().iter().map(|bin| bin.len()).sum::<usize>());
+    }
+}
+
+fn bin_pack_rejections(bin_type: BinType) {
+    let mut upright = Dimension::with_id(4, 20, 8, 0);
+    upright.set_rotation(Rotation::Forbidden);
+    let mut grouped = [
+        Dimension::with_id(5, 4, 4, 0),
+        Dimension::with_id(6, 4, 32, 0),
+    ];
+    grouped.iter_mut().for_each(|node| node.set_group(Some(1)));
+
+    let mut nodes = vec![
+        Dimension::with_id(1, 8, 8, 0),
+        Dimension::with_id(2, 16, 20, 1),
+        Dimension::with_id(3, 0, 8, 0),
+        upright,
+    ];
+    nodes.extend(grouped);
+    nodes.push(Dimension::with_id(7, 8, 8, 0));
+
+    for optimized in [false, true] {
+        let err = pack_bins(bin_type, &nodes, 16, 24, optimized)
+            .err()
+            .unwrap();
+        let expected = vec![
+            Rejection::new(2, BinError::ItemTooBig),
+            Rejection::new(3, BinError::ItemTooSmall),
+            Rejection::new(4, BinError::RotationForbidden),
+            Rejection::new(5, BinError::GroupTooBig),
+            Rejection::new(6, BinError::ItemTooBig),
+        ];
+        assert_eq!(expected, err.rejections());
+
+        // remaining nodes are still packed
+        let bins = err.into_bins();
+        assert_eq!(1, bins.len());
+        assert!(bins[0].find_by_id(1).is_some());
+        assert!(bins[0].find_by_id(7).is_some());
+    }
+
+    // reasons consider the border and spacing of the bins
+    let mut nodes = [
+        Dimension::with_id(1, 14, 14, 0),
+        Dimension::with_id(2, 14, 8, 0),
+        Dimension::with_id(3, 8, 14, 0),
+    ];
+    nodes[1].set_rotation(Rotation::Forbidden);
+    let err = pack_bins_with(&nodes, 16, 24, false, |width, height| {
+        let mut bin = bin_new(bin_type, width, height);
+        bin.set_border(2);
+        bin.set_spacing(1);
+        bin
+    })
+    .err()
+    .unwrap();
+    let expected = vec![
+        Rejection::new(1, BinError::ItemTooBig),
+        Rejection::new(2, BinError::RotationForbidden),
+    ];
+    assert_eq!(expected, err.rejections());
+}
+
+fn bin_rotation_policy(bin_type: BinType) {
+    let mut forbidden = Dimension::new(8, 2);
+    forbidden.set_rotation(Rotation::Forbidden);
+    let mut forced = Dimension::new(8, 2);
+    forced.set_rotation(Rotation::Forced);
+
+    let mut bin = bin_new(bin_type, 4, 8);
+    assert!(bin.insert(&forbidden).is_none());
+    let rect = bin.insert(&forced).expect("Object should fit into the bin");
+    assert!(rect.is_rotated());
+    assert_eq!(2, rect.width());
+    assert_eq!(8, rect.height());
+
+    let mut bin = bin_new(bin_type, 10, 10);
+    let (inserted, rejected) = bin.insert_list(&[forbidden, forced]);
+    assert!(rejected.is_empty());
+    for rect in inserted {
+        assert_eq!(rect.id() == forced.id(), rect.is_rotated());
+    }
+}
+
+fn bin_border_spacing(bin_type: BinType) {
+    let mut bin = bin_new(bin_type, 20, 20);
+    bin.set_border(2);
+    bin.set_spacing(2);
+    assert_eq!((2, 2), (bin.border(), bin.spacing()));
+    assert_eq!(4, bin.count_fit(&Dimension::new(7, 7)));
+    assert_eq!(1, bin.count_fit(&Dimension::new(8, 8)));
+
+    // neither the border nor the gap to other objects may be occupied
+    assert!(bin.insert_at(0, 0, &Dimension::new(7, 7)).is_none());
+    assert!(bin.insert_at(2, 2, &Dimension::new(7, 7)).is_some());
+    assert!(bin.insert_at(10, 2, &Dimension::new(7, 7)).is_none());
+
+    let nodes = vec![Dimension::new(7, 7); 4];
+    let (inserted, rejected) = bin.insert_list(&nodes);
+    assert_eq!(3, inserted.len());
+    assert_eq!(vec![nodes[0]], rejected);
+    for rect1 in bin.iter() {
+        assert!(rect1.x() >= 2 && rect1.x() + rect1.width() <= 18);
+        assert!(rect1.y() >= 2 && rect1.y() + rect1.height() <= 18);
+        for rect2 in bin.iter() {
+            if rect1 != rect2 {
+                assert!(
+                    rect1.x() + rect1.width() + 2 <= rect2.x()
+                        || rect2.x() + rect2.width() + 2 <= rect1.x()
+                        || rect1.y() + rect1.height() + 2 <= rect2.y()
+                        || rect2.y() + rect2.height() + 2 <= rect1.y()
+                );
+            }
+        }
+    }
+
+    // the border is preserved when the bin is resized
+    let mut bin = bin_new(bin_type, 10, 10);
+    bin.set_border(1);
+    bin.set_spacing(2);
+    assert!(bin.insert(&Dimension::new(8, 8)).is_some());
+    assert!(bin.insert(&Dimension::new(8, 8)).is_none());
+    bin.grow(10, 0);
+    let dim = Dimension::new(8, 8);
+    let rect = bin.insert(&dim).expect("Bin should have grown");
+    assert_eq!((11, 1), (rect.x(), rect.y()));
+
+    // the spacing assigned by the bin does not affect comparisons with the original object
+    assert_eq!(&dim, rect.dim());
+    assert!(bin.remove_rect(&Rectangle::new(rect.x(), rect.y(), dim)));
+    bin.shrink(false);
+    assert_eq!((10, 10), (bin.width(), bin.height()));
+
+    bin.set_border(-1);
+    assert_eq!(0, bin.border());
+    assert!(bin.is_empty());
+}
+
 #[test]
 fn bin_shrink_maxrects() {
     bin_shrink(BinType::MaxRects);
@@ -121,6 +509,16 @@ fn bin_shrink_guillotine() {
     bin_shrink(BinType::Guillotine);
 }
 
+#[test]
+fn bin_shrink_skyline() {
+    bin_shrink(BinType::Skyline);
+}
+
+#[test]
+fn bin_shrink_shelf() {
+    bin_shrink(BinType::Shelf);
+}
+
 #[test]
 fn bin_grow_maxrects() {
     bin_grow(BinType::MaxRects);
@@ -131,6 +529,16 @@ fn bin_grow_guillotine() {
     bin_grow(BinType::Guillotine);
 }
 
+#[test]
+fn bin_grow_skyline() {
+    bin_grow(BinType::Skyline);
+}
+
+#[test]
+fn bin_grow_shelf() {
+    bin_grow(BinType::Shelf);
+}
+
 #[test]
 fn bin_insert_maxrects() {
     bin_insert(BinType::MaxRects);
@@ -141,6 +549,16 @@ fn bin_insert_guillotine() {
     bin_insert(BinType::Guillotine);
 }
 
+#[test]
+fn bin_insert_skyline() {
+    bin_insert(BinType::Skyline);
+}
+
+#[test]
+fn bin_insert_shelf() {
+    bin_insert(BinType::Shelf);
+}
+
 #[test]
 fn bin_insert_list_maxrects() {
     bin_insert_list(BinType::MaxRects);
@@ -151,6 +569,16 @@ fn bin_insert_list_guillotine() {
     bin_insert_list(BinType::Guillotine);
 }
 
+#[test]
+fn bin_insert_list_skyline() {
+    bin_insert_list(BinType::Skyline);
+}
+
+#[test]
+fn bin_insert_list_shelf() {
+    bin_insert_list(BinType::Shelf);
+}
+
 #[test]
 fn bin_find_by_id_maxrects() {
     bin_find_by_id(BinType::MaxRects);
@@ -161,6 +589,16 @@ fn bin_find_by_id_guillotine() {
     bin_find_by_id(BinType::Guillotine);
 }
 
+#[test]
+fn bin_find_by_id_skyline() {
+    bin_find_by_id(BinType::Skyline);
+}
+
+#[test]
+fn bin_find_by_id_shelf() {
+    bin_find_by_id(BinType::Shelf);
+}
+
 #[test]
 fn bin_iter_slice_maxrects() {
     bin_iter_slice(BinType::MaxRects);
@@ -170,3 +608,213 @@ fn bin_iter_slice_maxrects() {
 fn bin_iter_slice_guillotine() {
     bin_iter_slice(BinType::Guillotine);
 }
+
+#[test]
+fn bin_iter_slice_skyline() {
+    bin_iter_slice(BinType::Skyline);
+}
+
+#[test]
+fn bin_iter_slice_shelf() {
+    bin_iter_slice(BinType::Shelf);
+}
+
+#[test]
+fn bin_rotation_policy_maxrects() {
+    bin_rotation_policy(BinType::MaxRects);
+}
+
+#[test]
+fn bin_rotation_policy_guillotine() {
+    bin_rotation_policy(BinType::Guillotine);
+}
+
+#[test]
+fn bin_rotation_policy_skyline() {
+    bin_rotation_policy(BinType::Skyline);
+}
+
+#[test]
+fn bin_rotation_policy_shelf() {
+    bin_rotation_policy(BinType::Shelf);
+}
+
+#[test]
+fn bin_remove_maxrects() {
+    bin_remove(BinType::MaxRects);
+}
+
+#[test]
+fn bin_remove_guillotine() {
+    bin_remove(BinType::Guillotine);
+}
+
+#[test]
+fn bin_remove_skyline() {
+    bin_remove(BinType::Skyline);
+}
+
+#[test]
+fn bin_remove_shelf() {
+    bin_remove(BinType::Shelf);
+}
+
+#[test]
+fn bin_pack_strip_maxrects() {
+    bin_pack_strip(BinType::MaxRects);
+}
+
+#[test]
+fn bin_pack_strip_guillotine() {
+    bin_pack_strip(BinType::Guillotine);
+}
+
+#[test]
+fn bin_pack_strip_skyline() {
+    bin_pack_strip(BinType::Skyline);
+}
+
+#[test]
+fn bin_pack_strip_shelf() {
+    bin_pack_strip(BinType::Shelf);
+}
+
+#[test]
+fn bin_growth_policy_maxrects() {
+    bin_growth_policy(BinType::MaxRects);
+}
+
+#[test]
+fn bin_growth_policy_guillotine() {
+    bin_growth_policy(BinType::Guillotine);
+}
+
+#[test]
+fn bin_growth_policy_skyline() {
+    bin_growth_policy(BinType::Skyline);
+}
+
+#[test]
+fn bin_growth_policy_shelf() {
+    bin_growth_policy(BinType::Shelf);
+}
+
+#[test]
+fn bin_preview_insert_maxrects() {
+    bin_preview_insert(BinType::MaxRects);
+}
+
+#[test]
+fn bin_preview_insert_guillotine() {
+    bin_preview_insert(BinType::Guillotine);
+}
+
+#[test]
+fn bin_preview_insert_skyline() {
+    bin_preview_insert(BinType::Skyline);
+}
+
+#[test]
+fn bin_preview_insert_shelf() {
+    bin_preview_insert(BinType::Shelf);
+}
+
+#[test]
+fn bin_checkpoint_maxrects() {
+    bin_checkpoint(BinType::MaxRects);
+}
+
+#[test]
+fn bin_checkpoint_guillotine() {
+    bin_checkpoint(BinType::Guillotine);
+}
+
+#[test]
+fn bin_checkpoint_skyline() {
+    bin_checkpoint(BinType::Skyline);
+}
+
+#[test]
+fn bin_checkpoint_shelf() {
+    bin_checkpoint(BinType::Shelf);
+}
+
+#[test]
+fn bin_pack_groups_maxrects() {
+    bin_pack_groups(BinType::MaxRects);
+}
+
+#[test]
+fn bin_pack_groups_guillotine() {
+    bin_pack_groups(BinType::Guillotine);
+}
+
+#[test]
+fn bin_pack_groups_skyline() {
+    bin_pack_groups(BinType::Skyline);
+}
+
+#[test]
+fn bin_pack_groups_shelf() {
+    bin_pack_groups(BinType::Shelf);
+}
+
+#[test]
+fn bin_pack_rejections_maxrects() {
+    bin_pack_rejections(BinType::MaxRects);
+}
+
+#[test]
+fn bin_pack_rejections_guillotine() {
+    bin_pack_rejections(BinType::Guillotine);
+}
+
+#[test]
+fn bin_pack_rejections_skyline() {
+    bin_pack_rejections(BinType::Skyline);
+}
+
+#[test]
+fn bin_pack_rejections_shelf() {
+    bin_pack_rejections(BinType::Shelf);
+}
+
+#[test]
+fn bin_insert_at_maxrects() {
+    bin_insert_at(BinType::MaxRects);
+}
+
+#[test]
+fn bin_insert_at_guillotine() {
+    bin_insert_at(BinType::Guillotine);
+}
+
+#[test]
+fn bin_insert_at_skyline() {
+    bin_insert_at(BinType::Skyline);
+}
+
+#[test]
+fn bin_insert_at_shelf() {
+    bin_insert_at(BinType::Shelf);
+}
+
+#[test]
+fn bin_border_spacing_maxrects() {
+    bin_border_spacing(BinType::MaxRects);
+}
+
+#[test]
+fn bin_border_spacing_guillotine() {
+    bin_border_spacing(BinType::Guillotine);
+}
+
+#[test]
+fn bin_border_spacing_skyline() {
+    bin_border_spacing(BinType::Skyline);
+}
+
+#[test]
+fn bin_border_spacing_shelf() {
+    bin_border_spacing(BinType::Shelf);
+}
@@ -0,0 +1,185 @@
+//! Provides functions for validating the layout of packed rectangles.
+//!
+//! Validation reports all [`Violation`]s which are found in a layout, such as overlapping
+//! rectangles or rectangles exceeding the bin bounds, instead of panicking. Bin packers can be
+//! validated by [`BinPacker::validate`], which additionally verifies the lists of free rectangles
+//! maintained by the MaxRects and Guillotine bin packers. Layouts from other sources can be
+//! validated by [`validate_layout`].
+//!
+//! # Quick Start
+//!
+//! ```rust
+//! use binpack2d::{BinPacker, Dimension, Rectangle};
+//! use binpack2d::maxrects::{Heuristic, MaxRectsBin};
+//! use binpack2d::validate::{validate_layout, Violation};
+//!
+//! let mut bin = MaxRectsBin::new(64, 64);
+//! let nodes = vec![Dimension::new(32, 32), Dimension::with_padding(16, 16, 2)];
+//! bin.insert_list(&nodes, true, Heuristic::BestShortSideFit);
+//! assert!(bin.validate().is_empty());
+//!
+//! // Padding is included in the checks.
+//! let a = Rectangle::new(0, 0, Dimension::with_id(1, 40, 40, 0));
+//! let b = Rectangle::new(42, 20, Dimension::with_id(2, 24, 24, 4));
+//! let violations = validate_layout(64, 64, &[a, b]);
+//! assert_eq!(vec![Violation::OutOfBounds(b), Violation::Overlap(a, b)], violations);
+//! ```
+//!
+//! [`BinPacker::validate`]: crate::BinPacker::validate
+
+use super::packing_area;
+use crate::coord::Coord;
+use crate::dimension::Dimension;
+use crate::rectangle::Rectangle;
+use std::fmt::{Display, Formatter};
+
+/// List of possible violations found by validating a layout.
+///
+/// Violations which involve more than one rectangle refer to all of them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Violation<C: Coord = i32> {
+    /// Two placed rectangles overlap, or a placed rectangle overlaps an obstacle. Padding and
+    /// spacing are included in the check.
+    Overlap(Rectangle<C>, Rectangle<C>),
+    /// A placed rectangle exceeds the packing area of the bin, i.e. the bin without its border,
+    /// or an obstacle exceeds the bin.
+    OutOfBounds(Rectangle<C>),
+    /// A free rectangle exceeds the packing area of the bin.
+    FreeOutOfBounds(Rectangle<C>),
+    /// A free rectangle overlaps the given placed rectangle or obstacle.
+    FreeOccupied(Rectangle<C>, Rectangle<C>),
+    /// Two free rectangles overlap, although the bin packer requires disjoint free rectangles.
+    FreeOverlap(Rectangle<C>, Rectangle<C>),
+    /// A free rectangle contains another free rectangle, although the bin packer requires
+    /// maximal free rectangles.
+    FreeRedundant(Rectangle<C>, Rectangle<C>),
+}
+
+impl<C: Coord> Display for Violation<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overlap(a, b) => write!(f, "{a} overlaps {b}"),
+            Self::OutOfBounds(r) => write!(f, "{r} exceeds the bin"),
+            Self::FreeOutOfBounds(r) => write!(f, "free {r} exceeds the bin"),
+            Self::FreeOccupied(r, o) => write!(f, "free {r} overlaps {o}"),
+            Self::FreeOverlap(a, b) => write!(f, "free {a} overlaps free {b}"),
+            Self::FreeRedundant(a, b) => write!(f, "free {a} contains free {b}"),
+        }
+    }
+}
+
+/// Validates the layout of the given rectangles in a bin of the given size.
+///
+/// Verifies that no two rectangles overlap, padding and spacing included, and that every
+/// rectangle lies within the bin. Running time is Theta(|rects|^2).
+///
+/// Returns all violations found, or an empty list if the layout is valid.
+pub fn validate_layout<C: Coord>(width: C, height: C, rects: &[Rectangle<C>]) -> Vec<Violation<C>> {
+    check_layout(width, height, C::ZERO, rects, &[])
+}
+
+/// Validates the layout of placed rectangles and obstacles in a bin of the given size and border.
+///
+/// Placed rectangles must lie within the packing area of the bin, obstacles within the bin.
+/// Obstacles may overlap each other.
+pub(crate) fn check_layout<C: Coord>(
+    width: C,
+    height: C,
+    border: C,
+    rects: &[Rectangle<C>],
+    obstacles: &[Rectangle<C>],
+) -> Vec<Violation<C>> {
+    let mut violations = Vec::new();
+
+    for rect in rects {
+        let area = packing_area(width, height, border, rect.dim().spacing);
+        if !rect.is_total_valid() || !area.contains_total(rect) {
+            violations.push(Violation::OutOfBounds(*rect));
+        }
+    }
+
+    let bin = Rectangle::new(
+        C::ZERO,
+        C::ZERO,
+        Dimension::with_id(0, width, height, C::ZERO),
+    );
+    for obstacle in obstacles {
+        if !obstacle.is_total_valid() || !bin.contains_total(obstacle) {
+            violations.push(Violation::OutOfBounds(*obstacle));
+        }
+    }
+
+    // invalid rectangles have already been reported and cannot be compared safely
+    for (i, rect) in rects.iter().enumerate() {
+        if !rect.is_total_valid() {
+            continue;
+        }
+        for other in rects[i + 1..].iter().chain(obstacles) {
+            if other.is_total_valid() && rect.intersects_total(other) {
+                violations.push(Violation::Overlap(*rect, *other));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Validates that the given free rectangles lie within the packing area and do not overlap any
+/// placed rectangles or obstacles.
+pub(crate) fn check_free_bounds<C: Coord>(
+    area: &Rectangle<C>,
+    rects_free: &[Rectangle<C>],
+    rects: &[Rectangle<C>],
+    obstacles: &[Rectangle<C>],
+) -> Vec<Violation<C>> {
+    let mut violations = Vec::new();
+
+    for free in rects_free {
+        if !free.is_total_valid() || !area.contains_total(free) {
+            violations.push(Violation::FreeOutOfBounds(*free));
+            continue;
+        }
+        for rect in rects.iter().chain(obstacles) {
+            if rect.is_total_valid() && free.intersects_total(rect) {
+                violations.push(Violation::FreeOccupied(*free, *rect));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Validates that no free rectangle is contained in another free rectangle.
+pub(crate) fn check_free_maximal<C: Coord>(rects_free: &[Rectangle<C>]) -> Vec<Violation<C>> {
+    let mut violations = Vec::new();
+
+    for (i, free) in rects_free.iter().enumerate() {
+        for other in &rects_free[i + 1..] {
+            if free.contains_total(other) {
+                violations.push(Violation::FreeRedundant(*free, *other));
+            } else if other.contains_total(free) {
+                violations.push(Violation::FreeRedundant(*other, *free));
+            }
+        }
+    }
+
+    violations
+}
+
+/// Validates that no two free rectangles overlap.
+pub(crate) fn check_free_disjoint<C: Coord>(rects_free: &[Rectangle<C>]) -> Vec<Violation<C>> {
+    let mut violations = Vec::new();
+
+    for (i, free) in rects_free.iter().enumerate() {
+        for other in &rects_free[i + 1..] {
+            if free.intersects_total(other) {
+                violations.push(Violation::FreeOverlap(*free, *other));
+            }
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests;
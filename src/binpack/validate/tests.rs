@@ -0,0 +1,76 @@
+use super::*;
+use crate::binpack::{bin_new, BinType};
+use crate::dimension::Padding;
+
+#[test]
+fn validate_layout_rects() {
+    let a = Rectangle::new(0, 0, Dimension::with_id(1, 8, 8, 0));
+    let b = Rectangle::new(8, 0, Dimension::with_id(2, 8, 8, 0));
+    assert!(validate_layout(16, 8, &[a, b]).is_empty());
+
+    // touching rectangles overlap once padding is included
+    let c = Rectangle::new(8, 1, Dimension::with_id(3, 7, 6, 1));
+    assert_eq!(
+        vec![Violation::Overlap(a, c)],
+        validate_layout(16, 8, &[a, c])
+    );
+
+    let d = Rectangle::new(-1, 0, Dimension::with_id(4, 8, 8, 0));
+    let e = Rectangle::new(10, 4, Dimension::with_id(5, 6, 5, 0));
+    assert_eq!(
+        vec![
+            Violation::OutOfBounds(d),
+            Violation::OutOfBounds(e),
+            Violation::Overlap(d, a),
+        ],
+        validate_layout(16, 8, &[d, e, a])
+    );
+
+    // rectangles exceeding the coordinate range are reported without overflowing
+    let f = Rectangle::new(
+        1,
+        1,
+        Dimension::with_id(6, i32::MAX, 1, Padding::new(0, 0, 1, 0)),
+    );
+    assert_eq!(
+        vec![Violation::OutOfBounds(f)],
+        validate_layout(16, 8, &[f, a])
+    );
+    assert!(Violation::OutOfBounds(f)
+        .to_string()
+        .ends_with("exceeds the bin"));
+}
+
+#[test]
+fn validate_bins() {
+    let types = [
+        BinType::MaxRects,
+        BinType::Guillotine,
+        BinType::Skyline,
+        BinType::Shelf,
+    ];
+
+    for bin_type in types {
+        let mut bin = bin_new(bin_type, 128, 128);
+        bin.set_border(3);
+        bin.set_spacing(2);
+        let nodes: Vec<_> = (1..40)
+            .map(|i| Dimension::with_padding(4 + i % 7 * 3, 4 + i % 5 * 4, i % 3))
+            .collect();
+        let (inserted, _) = bin.insert_list(&nodes);
+        assert!(bin.validate().is_empty(), "{bin_type:?}");
+
+        for rect in inserted.iter().step_by(3) {
+            bin.remove(rect.dim().id());
+        }
+        bin.insert_list(&nodes);
+        assert!(bin.validate().is_empty(), "{bin_type:?}");
+
+        bin.grow(32, 16);
+        bin.insert_list(&nodes);
+        assert!(bin.validate().is_empty(), "{bin_type:?}");
+
+        bin.shrink(false);
+        assert!(bin.validate().is_empty(), "{bin_type:?}");
+    }
+}
@@ -0,0 +1,344 @@
+//! Provides the [`Coord`] trait for numeric types which can be used as coordinates and extents
+//! of objects and bins.
+//!
+//! All structures of this crate use `i32` coordinates by default. Other coordinate types can be
+//! chosen by specifying the type parameter explicitly, e.g. `u16` to reduce the memory footprint
+//! of packed layouts, or `i64` for layouts which exceed the range of `i32`.
+//!
+//! Real-valued extents are supported by the floating-point types `f32` and `f64`. Comparisons of
+//! floating-point coordinates tolerate rounding errors up to [`Coord::EPSILON`], so that objects
+//! which touch each other are neither considered overlapping nor separated by a gap.
+//!
+//! # Quick Start
+//!
+//! ```rust
+//! use binpack2d::{BinPacker, Dimension};
+//! use binpack2d::maxrects::{Heuristic, MaxRectsBin};
+//!
+//! // A bin with a side length of 10 metres in micrometres exceeds the range of `i32`.
+//! let mut bin: MaxRectsBin<i64> = MaxRectsBin::new(10_000_000, 10_000_000);
+//! let rect = bin.insert(&Dimension::new(6_000_000, 4_000_000), false, Heuristic::BestAreaFit);
+//! assert!(rect.is_some());
+//!
+//! // Areas are computed with a wider type, which does not overflow.
+//! assert_eq!(24_000_000_000_000, rect.unwrap().dim().area());
+//!
+//! // Sizes in millimetres which are not representable as integers
+//! let mut bin: MaxRectsBin<f64> = MaxRectsBin::new(297.0, 210.0);
+//! let nodes = vec![Dimension::new(99.0, 52.5); 12];
+//! let (inserted, rejected) = bin.insert_list(&nodes, false, Heuristic::BestShortSideFit);
+//! assert_eq!((12, 0), (inserted.len(), rejected.len()));
+//! ```
+
+use std::fmt::{Debug, Display};
+use std::iter::Sum;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// A numeric type which can be used for coordinates and extents of objects and bins.
+///
+/// Values are added and subtracted by the regular operators only if the result is known to be
+/// within range. Checked and saturating variants are provided for all other cases.
+///
+/// Areas and placement scores are computed with the associated [`Wide`] type, which covers the
+/// product of any two coordinate values.
+///
+/// [`Wide`]: Coord::Wide
+pub trait Coord:
+    Copy
+    + Debug
+    + Display
+    + Default
+    + PartialEq
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Send
+    + Sync
+    + 'static
+{
+    /// A signed numeric type which is used for areas and placement scores.
+    type Wide: Copy
+        + Debug
+        + Display
+        + Default
+        + PartialEq
+        + PartialOrd
+        + Add<Output = Self::Wide>
+        + Sub<Output = Self::Wide>
+        + Mul<Output = Self::Wide>
+        + Neg<Output = Self::Wide>
+        + Sum
+        + Send
+        + Sync
+        + 'static;
+
+    /// The value 0.
+    const ZERO: Self;
+    /// The value 1.
+    const ONE: Self;
+    /// The smallest value of the type.
+    const MIN: Self;
+    /// The greatest value of the type.
+    const MAX: Self;
+    /// The smallest value of the [`Wide`] type.
+    ///
+    /// [`Wide`]: Coord::Wide
+    const WIDE_MIN: Self::Wide;
+    /// The greatest value of the [`Wide`] type.
+    ///
+    /// [`Wide`]: Coord::Wide
+    const WIDE_MAX: Self::Wide;
+    /// The tolerance of comparisons between coordinates, which is 0 for integer types.
+    ///
+    /// Coordinates are considered equal if they differ by no more than `EPSILON`.
+    const EPSILON: Self;
+
+    /// Converts the value to the [`Wide`] type.
+    ///
+    /// [`Wide`]: Coord::Wide
+    fn widen(self) -> Self::Wide;
+
+    /// Multiplies the value by `rhs` in the [`Wide`] type, saturating at the bounds of the
+    /// [`Wide`] type.
+    ///
+    /// [`Wide`]: Coord::Wide
+    fn mul_wide(self, rhs: Self) -> Self::Wide;
+
+    /// Converts the value to `f64`, possibly with a loss of precision.
+    fn to_f64(self) -> f64;
+
+    /// Converts a value of the [`Wide`] type to `f64`, possibly with a loss of precision.
+    ///
+    /// [`Wide`]: Coord::Wide
+    fn wide_to_f64(value: Self::Wide) -> f64;
+
+    /// Converts the given `u32` value, saturating at [`MAX`].
+    ///
+    /// [`MAX`]: Coord::MAX
+    fn from_u32(value: u32) -> Self;
+
+    /// Converts the value to `u32`, saturating at the bounds of `u32`.
+    fn to_u32(self) -> u32;
+
+    /// Adds `rhs` to the value. Returns `None` if the result is out of range.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Subtracts `rhs` from the value. Returns `None` if the result is out of range.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Adds `rhs` to the value, saturating at the bounds of the type.
+    fn saturating_add(self, rhs: Self) -> Self;
+
+    /// Subtracts `rhs` from the value, saturating at the bounds of the type.
+    fn saturating_sub(self, rhs: Self) -> Self;
+
+    /// Returns the non-negative remainder of the division by `rhs`.
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty => $wide:ty),*) => {
+        $(
+            impl Coord for $t {
+                type Wide = $wide;
+
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+                const WIDE_MIN: Self::Wide = <$wide>::MIN;
+                const WIDE_MAX: Self::Wide = <$wide>::MAX;
+                const EPSILON: Self = 0;
+
+                #[inline]
+                fn widen(self) -> Self::Wide {
+                    self as $wide
+                }
+
+                #[inline]
+                fn mul_wide(self, rhs: Self) -> Self::Wide {
+                    (self as $wide).saturating_mul(rhs as $wide)
+                }
+
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline]
+                fn wide_to_f64(value: Self::Wide) -> f64 {
+                    value as f64
+                }
+
+                #[inline]
+                fn from_u32(value: u32) -> Self {
+                    <$t>::try_from(value).unwrap_or(<$t>::MAX)
+                }
+
+                #[inline]
+                fn to_u32(self) -> u32 {
+                    u32::try_from(self).unwrap_or(if self > 0 { u32::MAX } else { 0 })
+                }
+
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$t>::saturating_add(self, rhs)
+                }
+
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$t>::saturating_sub(self, rhs)
+                }
+
+                #[inline]
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i16 => i64, i32 => i64, i64 => i128, u16 => i64, u32 => i64, u64 => i128);
+
+macro_rules! impl_coord_float {
+    ($($t:ty => $eps:expr),*) => {
+        $(
+            impl Coord for $t {
+                type Wide = f64;
+
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+                const WIDE_MIN: Self::Wide = f64::MIN;
+                const WIDE_MAX: Self::Wide = f64::MAX;
+                const EPSILON: Self = $eps;
+
+                #[inline]
+                fn widen(self) -> Self::Wide {
+                    self as f64
+                }
+
+                #[inline]
+                fn mul_wide(self, rhs: Self) -> Self::Wide {
+                    (self as f64 * rhs as f64).clamp(f64::MIN, f64::MAX)
+                }
+
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                #[inline]
+                fn wide_to_f64(value: Self::Wide) -> f64 {
+                    value
+                }
+
+                #[inline]
+                fn from_u32(value: u32) -> Self {
+                    value as $t
+                }
+
+                #[inline]
+                fn to_u32(self) -> u32 {
+                    self as u32
+                }
+
+                #[inline]
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    Some(self + rhs).filter(|v| v.is_finite())
+                }
+
+                #[inline]
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    Some(self - rhs).filter(|v| v.is_finite())
+                }
+
+                #[inline]
+                fn saturating_add(self, rhs: Self) -> Self {
+                    (self + rhs).clamp(<$t>::MIN, <$t>::MAX)
+                }
+
+                #[inline]
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    (self - rhs).clamp(<$t>::MIN, <$t>::MAX)
+                }
+
+                #[inline]
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coord_float!(f32 => 1e-3, f64 => 1e-9);
+
+/// Returns the smaller of the two values, or `a` if they are equal.
+///
+/// Returns `b` if `a` is not comparable, e.g. NaN.
+pub(crate) fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if a <= b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Returns the greater of the two values, or `a` if they are equal.
+///
+/// Returns `b` if `a` is not comparable, e.g. NaN.
+pub(crate) fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if a >= b {
+        a
+    } else {
+        b
+    }
+}
+
+/// Returns `true` if the two values differ by no more than [`Coord::EPSILON`].
+#[inline]
+pub(crate) fn approx_eq<C: Coord>(a: C, b: C) -> bool {
+    a == b || (C::EPSILON > C::ZERO && abs_diff(a, b) <= C::EPSILON.widen())
+}
+
+/// Returns `true` if `a` is less than or approximately equal to `b`.
+#[inline]
+pub(crate) fn approx_le<C: Coord>(a: C, b: C) -> bool {
+    a <= b || approx_eq(a, b)
+}
+
+/// Returns `true` if `a` is less than `b` and not approximately equal to `b`.
+#[inline]
+pub(crate) fn approx_lt<C: Coord>(a: C, b: C) -> bool {
+    a < b && !approx_eq(a, b)
+}
+
+/// Returns the absolute difference of the two values in the [`Wide`] type.
+///
+/// [`Wide`]: Coord::Wide
+pub(crate) fn abs_diff<C: Coord>(a: C, b: C) -> C::Wide {
+    if a > b {
+        a.widen() - b.widen()
+    } else {
+        b.widen() - a.widen()
+    }
+}
+
+#[cfg(test)]
+mod tests;
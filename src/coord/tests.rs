@@ -0,0 +1,49 @@
+use super::*;
+
+#[test]
+fn coord_conversion() {
+    assert_eq!(u16::MAX, u16::from_u32(100_000));
+    assert_eq!(100_000, i64::from_u32(100_000));
+    assert_eq!(0, (-5i32).to_u32());
+    assert_eq!(u32::MAX, i64::MAX.to_u32());
+    assert_eq!(70_000, 70_000u64.to_u32());
+}
+
+#[test]
+fn coord_wide() {
+    assert_eq!(65_535 * 65_535, u16::MAX.mul_wide(u16::MAX));
+    assert_eq!(i64::MAX as i128 * 2, i64::MAX.mul_wide(2));
+    assert_eq!(i128::MAX, u64::MAX.mul_wide(u64::MAX));
+    assert_eq!(u32::MAX as i64, abs_diff(0u32, u32::MAX));
+    assert_eq!(3, abs_diff(-1i16, 2));
+}
+
+#[test]
+fn coord_min_max() {
+    assert_eq!(3, min(3u16, 7));
+    assert_eq!(7, max(3u16, 7));
+    assert_eq!(-2, min(-2i64, -2));
+}
+
+#[test]
+fn coord_float() {
+    assert_eq!(None, f32::MAX.checked_add(f32::MAX));
+    assert_eq!(Some(0.75), 0.5f64.checked_add(0.25));
+    assert_eq!(f64::MAX, f64::MAX.saturating_add(f64::MAX));
+    assert_eq!(f64::MAX, f64::MAX.mul_wide(2.0));
+    assert_eq!(12, 12.7f32.to_u32());
+    assert_eq!(0, f64::NAN.to_u32());
+    assert_eq!(0.0, max(f64::NAN, 0.0));
+}
+
+#[test]
+fn coord_approx() {
+    assert!(approx_eq(0.1 + 0.2, 0.3f64));
+    assert!(approx_le(0.1 + 0.2, 0.3f64));
+    assert!(!approx_lt(0.3, 0.1 + 0.2f64));
+    assert!(approx_lt(0.3, 0.3 + 1e-6f64));
+    assert!(!approx_eq(0.3, 0.3 + 1e-6f64));
+    assert!(!approx_eq(1, 2));
+    assert!(approx_lt(1, 2));
+    assert!(!approx_lt(2u16, 2));
+}
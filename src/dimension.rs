@@ -2,6 +2,7 @@
 //!
 //! The `Dimension` structure is primarily used to request placement of an object in a bin.
 
+use crate::coord::{self, Coord};
 use std::fmt::{Display, Formatter};
 use std::mem;
 use std::sync::atomic::{AtomicIsize, Ordering};
@@ -14,39 +15,319 @@ pub(crate) fn get_unique_id() -> isize {
     UNIQUE_ID.fetch_add(1, Ordering::Relaxed)
 }
 
+/// Generates a sequence of identifiers, independently of the process-wide identifier counter.
+///
+/// Identifiers start at a given seed value and increment by 1 per call. Unlike auto-generated
+/// identifiers, the same sequence of calls always results in the same identifiers.
+///
+/// # Examples
+/// ```
+/// use binpack2d::{Dimension, IdAllocator};
+///
+/// let mut ids = IdAllocator::with_seed(100);
+/// let nodes = vec![
+///     Dimension::with_allocator(&mut ids, 8, 8, 0),
+///     Dimension::with_allocator(&mut ids, 4, 4, 0),
+/// ];
+/// assert_eq!(100, nodes[0].id());
+/// assert_eq!(101, nodes[1].id());
+///
+/// // Identifiers start over at the seed value
+/// ids.reset();
+/// assert_eq!(100, ids.next_id());
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IdAllocator {
+    /// Identifier after reset.
+    seed: isize,
+    /// Identifier returned by the next call.
+    next: isize,
+}
+
+impl IdAllocator {
+    /// Creates a new allocator with identifiers starting at 1.
+    pub fn new() -> Self {
+        Self::with_seed(1)
+    }
+
+    /// Creates a new allocator with identifiers starting at the given `seed`.
+    pub fn with_seed(seed: isize) -> Self {
+        Self { seed, next: seed }
+    }
+
+    /// Returns the seed value of the allocator.
+    pub fn seed(&self) -> isize {
+        self.seed
+    }
+
+    /// Returns the identifier which is returned by the next call of [`next_id`] without
+    /// consuming it.
+    ///
+    /// [`next_id`]: Self::next_id
+    pub fn peek(&self) -> isize {
+        self.next
+    }
+
+    /// Returns a new identifier.
+    pub fn next_id(&mut self) -> isize {
+        let id = self.next;
+        self.next = self.next.wrapping_add(1);
+        id
+    }
+
+    /// Restarts identifiers at the seed value.
+    pub fn reset(&mut self) {
+        self.next = self.seed;
+    }
+}
+
+impl Default for IdAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Specifies whether an object may be rotated by 90 degrees when it is placed into a bin.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub enum Rotation {
+    /// The object must never be rotated.
+    Forbidden,
+    /// The object may be rotated if the bin packer considers rotation.
+    ///
+    /// This is the default rotation policy.
+    #[default]
+    Allowed,
+    /// The object must always be rotated by 90 degrees, regardless of whether the bin packer
+    /// considers rotation.
+    Forced,
+}
+
+/// Specifies the padding on each side of an object.
+///
+/// Padding is reserved space around the object, which is kept free of other objects.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub struct Padding<C: Coord = i32> {
+    left: C,
+    top: C,
+    right: C,
+    bottom: C,
+}
+
+impl<C: Coord> Padding<C> {
+    /// Creates a new `Padding` with individual values for each side.
+    ///
+    /// Negative values are capped at 0.
+    pub fn new(left: C, top: C, right: C, bottom: C) -> Self {
+        Self {
+            left: coord::max(left, C::ZERO),
+            top: coord::max(top, C::ZERO),
+            right: coord::max(right, C::ZERO),
+            bottom: coord::max(bottom, C::ZERO),
+        }
+    }
+
+    /// Creates a new `Padding` with the same value for all sides.
+    ///
+    /// A negative value is capped at 0.
+    pub fn uniform(value: C) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    /// Returns the padding on the left side.
+    pub fn left(&self) -> C {
+        self.left
+    }
+
+    /// Returns the padding on the top side.
+    pub fn top(&self) -> C {
+        self.top
+    }
+
+    /// Returns the padding on the right side.
+    pub fn right(&self) -> C {
+        self.right
+    }
+
+    /// Returns the padding on the bottom side.
+    pub fn bottom(&self) -> C {
+        self.bottom
+    }
+
+    /// Returns `true` if all sides have the same padding, and `false` otherwise.
+    pub fn is_uniform(&self) -> bool {
+        self.left == self.top && self.left == self.right && self.left == self.bottom
+    }
+
+    /// Returns the padding of an object which is flipped by 90 degrees, i.e. the padding of the
+    /// left and top side as well as the right and bottom side are swapped.
+    pub(crate) fn to_flipped(self) -> Self {
+        Self::new(self.top, self.left, self.bottom, self.right)
+    }
+}
+
+impl<C: Coord> From<C> for Padding<C> {
+    fn from(value: C) -> Self {
+        Self::uniform(value)
+    }
+}
+
+impl<C: Coord> Display for Padding<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_uniform() {
+            write!(f, "{}", self.left)
+        } else {
+            write!(
+                f,
+                "Padding(left: {}, top: {}, right: {}, bottom: {})",
+                self.left, self.top, self.right, self.bottom
+            )
+        }
+    }
+}
+
+/// Specifies the alignment of object positions within a bin, e.g. to place textures on the block
+/// boundaries required by GPU block compression formats.
+///
+/// Objects are placed only at horizontal positions which are multiples of `x`, and at vertical
+/// positions which are multiples of `y`. Optionally, the size of objects is rounded up to
+/// multiples of the alignment as well.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+pub struct Alignment<C: Coord = i32> {
+    x: C,
+    y: C,
+    round_size: bool,
+}
+
+impl<C: Coord> Alignment<C> {
+    /// Creates a new `Alignment` with the specified horizontal and vertical alignment.
+    ///
+    /// `round_size` indicates whether the space occupied by objects is rounded up to multiples
+    /// of the alignment.
+    ///
+    /// Values which are not positive are replaced by 1.
+    pub fn new(x: C, y: C, round_size: bool) -> Self {
+        let positive_or_one = |value: C| if value > C::ZERO { value } else { C::ONE };
+        Self {
+            x: positive_or_one(x),
+            y: positive_or_one(y),
+            round_size,
+        }
+    }
+
+    /// Returns the horizontal alignment.
+    pub fn x(&self) -> C {
+        self.x
+    }
+
+    /// Returns the vertical alignment.
+    pub fn y(&self) -> C {
+        self.y
+    }
+
+    /// Returns whether the size of objects is rounded up to multiples of the alignment.
+    pub fn round_size(&self) -> bool {
+        self.round_size
+    }
+
+    /// Returns `true` if the given position is aligned, and `false` otherwise.
+    pub fn is_aligned(&self, x: C, y: C) -> bool {
+        x.rem_euclid(self.x) == C::ZERO && y.rem_euclid(self.y) == C::ZERO
+    }
+
+    /// Returns the smallest aligned horizontal position which is not less than `x`, or `None` if
+    /// it is out of range.
+    pub(crate) fn align_x(&self, x: C) -> Option<C> {
+        x.checked_add(align_gap(x, self.x))
+    }
+
+    /// Returns the smallest aligned vertical position which is not less than `y`, or `None` if
+    /// it is out of range.
+    pub(crate) fn align_y(&self, y: C) -> Option<C> {
+        y.checked_add(align_gap(y, self.y))
+    }
+}
+
+impl<C: Coord> Display for Alignment<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Alignment(x: {}, y: {}, round_size: {})",
+            self.x, self.y, self.round_size
+        )
+    }
+}
+
+/// Returns the distance from `value` to the next multiple of `step`.
+fn align_gap<C: Coord>(value: C, step: C) -> C {
+    (step - value.rem_euclid(step)) % step
+}
+
 /// The `Dimension` struct stores information about width, height and optional padding of an object.
 ///
 /// Padding can be used to ensure that rectangles are placed into bins with spacing to each other.
+/// It can be specified for each side individually by the [`Padding`] type. Padding is also applied
+/// against the bin boundaries, whereas the [`spacing`] of a bin only separates neighbouring
+/// objects.
+///
+/// [`spacing`]: crate::BinPacker::set_spacing
 ///
 /// An identifier is provided for custom identification purposes.
 ///
+/// The [`Rotation`] policy determines whether the object may be rotated by 90 degrees when
+/// it is placed into a bin.
+///
+/// An optional group key can be assigned to objects which must be placed into the same bin by
+/// functions that pack objects into multiple bins.
+///
+/// An optional [`Alignment`] restricts the positions where the object may be placed. It takes
+/// precedence over the alignment of the bin.
+///
+/// Extents are of the [`Coord`] type `C`, which is `i32` by default.
+///
 /// [`id`]: Dimension::id
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
-pub struct Dimension {
+#[derive(Clone, Copy, Debug)]
+pub struct Dimension<C: Coord = i32> {
     id: isize,
-    pub(crate) width: i32,
-    pub(crate) height: i32,
-    pub(crate) padding: i32,
+    pub(crate) width: C,
+    pub(crate) height: C,
+    pub(crate) padding: Padding<C>,
+    /// Gap to neighbouring objects on the right and bottom side, which is assigned by the bin
+    /// packer.
+    pub(crate) spacing: C,
+    /// Additional width and height to round the size up to multiples of the alignment, which is
+    /// assigned by the bin packer.
+    pub(crate) rounding: (C, C),
+    /// Index of the object in a list of payload items, which is assigned by the payload API to
+    /// match packed objects with their payload.
+    pub(crate) tag: usize,
+    rotation: Rotation,
+    group: Option<isize>,
+    alignment: Option<Alignment<C>>,
 }
 
-impl Dimension {
+impl<C: Coord> Dimension<C> {
     /// Creates a new `Dimension` object with the specified dimensions, an auto-generated
     /// identifier and no padding.
     ///
     /// Negative values for width and height are capped at 0.
     ///
     /// **Note:** Auto-generated identifiers can be considered unique, as long as they are not
-    /// duplicated by [`with_id`], [`set_id`] or cloning.
+    /// duplicated by [`with_id`], [`set_id`] or cloning. They depend on all previously created
+    /// objects of the process. Use [`with_allocator`] for reproducible identifiers.
     ///
     /// [`with_id`]: Self::with_id
     /// [`set_id`]: Self::set_id
-    pub fn new(width: i32, height: i32) -> Self {
-        Self::with_id(get_unique_id(), width, height, 0)
+    /// [`with_allocator`]: Self::with_allocator
+    pub fn new(width: C, height: C) -> Self {
+        Self::with_id(get_unique_id(), width, height, C::ZERO)
     }
 
     /// Creates a new `Dimension` object with the specified dimensions, an auto-generated
     /// identifier and optional padding.
     ///
+    /// Padding can be specified either as a single value for all sides, or as [`Padding`] with
+    /// individual values for each side.
+    ///
     /// Negative values for width, height and padding are capped at 0.
     ///
     /// **Note:** Auto-generated identifiers can be considered unique, as long as they are not
@@ -54,20 +335,45 @@ impl Dimension {
     ///
     /// [`with_id`]: Self::with_id
     /// [`set_id`]: Self::set_id
-    pub fn with_padding(width: i32, height: i32, padding: i32) -> Self {
+    pub fn with_padding<P: Into<Padding<C>>>(width: C, height: C, padding: P) -> Self {
         Self::with_id(get_unique_id(), width, height, padding)
     }
 
+    /// Creates a new `Dimension` object with the specified dimensions, an identifier provided by
+    /// the given [`IdAllocator`] and optional padding.
+    ///
+    /// Padding can be specified either as a single value for all sides, or as [`Padding`] with
+    /// individual values for each side.
+    ///
+    /// Negative values for width, height and padding are capped at 0.
+    pub fn with_allocator<P: Into<Padding<C>>>(
+        ids: &mut IdAllocator,
+        width: C,
+        height: C,
+        padding: P,
+    ) -> Self {
+        Self::with_id(ids.next_id(), width, height, padding)
+    }
+
     /// Creates a new `Dimension` object with the specified dimension, identifier and
     /// optional padding.
     ///
+    /// Padding can be specified either as a single value for all sides, or as [`Padding`] with
+    /// individual values for each side.
+    ///
     /// Negative values for width, height and padding are capped at 0.
-    pub fn with_id(id: isize, width: i32, height: i32, padding: i32) -> Self {
+    pub fn with_id<P: Into<Padding<C>>>(id: isize, width: C, height: C, padding: P) -> Self {
         Self {
             id,
-            width: width.max(0),
-            height: height.max(0),
-            padding: padding.max(0),
+            width: coord::max(width, C::ZERO),
+            height: coord::max(height, C::ZERO),
+            padding: padding.into(),
+            spacing: C::ZERO,
+            rounding: (C::ZERO, C::ZERO),
+            tag: 0,
+            rotation: Rotation::Allowed,
+            group: None,
+            alignment: None,
         }
     }
 
@@ -77,30 +383,81 @@ impl Dimension {
     }
 
     /// Returns width of the `Dimension`.
-    pub fn width(&self) -> i32 {
+    pub fn width(&self) -> C {
         self.width
     }
 
-    /// Returns width of the `Dimension` with padding.
-    pub(crate) fn width_total(&self) -> i32 {
-        self.width + (self.padding << 1)
+    /// Returns width of the `Dimension` with padding, spacing and rounding.
+    ///
+    /// The result must be within range of the coordinate type, which is verified by
+    /// [`is_total_valid`] before objects are placed into a bin.
+    ///
+    /// [`is_total_valid`]: Self::is_total_valid
+    pub(crate) fn width_total(&self) -> C {
+        self.width + self.rounding.0 + self.padding.left + self.padding.right + self.spacing
+    }
+
+    /// Returns width of the `Dimension` with padding, spacing and rounding, or `None` if the
+    /// result is out of range.
+    fn checked_width_total(&self) -> Option<C> {
+        self.width
+            .checked_add(self.rounding.0)?
+            .checked_add(self.padding.left)?
+            .checked_add(self.padding.right)?
+            .checked_add(self.spacing)
     }
 
     /// Returns height of the `Dimension`.
-    pub fn height(&self) -> i32 {
+    pub fn height(&self) -> C {
+        self.height
+    }
+
+    /// Returns height of the `Dimension` with padding, spacing and rounding.
+    ///
+    /// The result must be within range of the coordinate type, which is verified by
+    /// [`is_total_valid`] before objects are placed into a bin.
+    ///
+    /// [`is_total_valid`]: Self::is_total_valid
+    pub(crate) fn height_total(&self) -> C {
+        self.height + self.rounding.1 + self.padding.top + self.padding.bottom + self.spacing
+    }
+
+    /// Returns height of the `Dimension` with padding, spacing and rounding, or `None` if the
+    /// result is out of range.
+    fn checked_height_total(&self) -> Option<C> {
         self.height
+            .checked_add(self.rounding.1)?
+            .checked_add(self.padding.top)?
+            .checked_add(self.padding.bottom)?
+            .checked_add(self.spacing)
     }
 
-    /// Returns height of the `Dimension` with padding.
-    pub(crate) fn height_total(&self) -> i32 {
-        self.height + (self.padding << 1)
+    /// Returns `true` if width and height of the `Dimension` with padding, spacing and rounding
+    /// are within range of the coordinate type, and `false` otherwise.
+    pub(crate) fn is_total_valid(&self) -> bool {
+        self.checked_width_total().is_some() && self.checked_height_total().is_some()
     }
 
-    /// Returns the padding of the `Dimension`.
-    pub fn padding(&self) -> i32 {
+    /// Returns the [`Padding`] of the `Dimension`.
+    pub fn padding(&self) -> Padding<C> {
         self.padding
     }
 
+    /// Returns the [`Rotation`] policy of the `Dimension`.
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    /// Returns the group key of the `Dimension`, if available.
+    pub fn group(&self) -> Option<isize> {
+        self.group
+    }
+
+    /// Returns the [`Alignment`] of the `Dimension`, if available.
+    pub fn alignment(&self) -> Option<Alignment<C>> {
+        self.alignment
+    }
+
     /// Assigns a new identifier to `Dimension`.
     pub fn set_id(&mut self, value: isize) {
         self.id = value;
@@ -109,74 +466,217 @@ impl Dimension {
     /// Sets width of the `Dimension` to the specified `value`.
     ///
     /// A negative value for width is capped at 0.
-    pub fn set_width(&mut self, value: i32) {
-        self.width = value.max(0);
+    pub fn set_width(&mut self, value: C) {
+        self.width = coord::max(value, C::ZERO);
     }
 
     /// Sets height of the `Dimension` to the specified `value`.
     ///
     /// A negative value for height is capped at 0.
-    pub fn set_height(&mut self, value: i32) {
-        self.height = value.max(0);
+    pub fn set_height(&mut self, value: C) {
+        self.height = coord::max(value, C::ZERO);
     }
 
     /// Sets both `width` and `height` of the `Dimension`.
     ///
     /// Negative values for width and height are capped at 0.
-    pub fn set_dimension(&mut self, width: i32, height: i32) {
+    pub fn set_dimension(&mut self, width: C, height: C) {
         self.width = width;
         self.height = height;
     }
 
-    /// Sets padding of the `Dimension` to the specified `value`.
+    /// Sets padding of the `Dimension` to the specified `value`, which is either a single value
+    /// for all sides, or [`Padding`] with individual values for each side.
     ///
     /// A negative value for padding is capped at 0.
-    pub fn set_padding(&mut self, value: i32) {
-        self.padding = value.max(0);
+    pub fn set_padding<P: Into<Padding<C>>>(&mut self, value: P) {
+        self.padding = value.into();
+    }
+
+    /// Sets the [`Rotation`] policy of the `Dimension`, which determines whether the object may
+    /// be rotated by 90 degrees when it is placed into a bin.
+    pub fn set_rotation(&mut self, rotation: Rotation) {
+        self.rotation = rotation;
+    }
+
+    /// Assigns the `Dimension` to the group with the specified key. Specify `None` to remove the
+    /// `Dimension` from its group.
+    ///
+    /// Functions which pack objects into multiple bins place all objects of the same group into
+    /// the same bin.
+    pub fn set_group(&mut self, group: Option<isize>) {
+        self.group = group;
+    }
+
+    /// Specifies the [`Alignment`] of the `Dimension`, which overrides the alignment of the bin.
+    /// Specify `None` to use the alignment of the bin.
+    ///
+    /// Alignment is only considered by the *MAXRECTS* and *GUILLOTINE* bin packers.
+    pub fn set_alignment(&mut self, alignment: Option<Alignment<C>>) {
+        self.alignment = alignment;
     }
 
     /// Flips the `Dimension` by 90 degrees.
+    ///
+    /// Width and height are swapped, as well as the padding of the left and top side, and the
+    /// padding of the right and bottom side.
     pub fn flip(&mut self) {
         mem::swap(&mut self.width, &mut self.height);
+        mem::swap(&mut self.rounding.0, &mut self.rounding.1);
+        self.padding = self.padding.to_flipped();
     }
 
     /// Creates a new `Dimension` from the current instance, which is flipped by 90 degrees.
     pub fn to_flipped(&self) -> Self {
-        Self::with_id(self.id, self.height, self.width, self.padding)
+        let mut dim = *self;
+        dim.flip();
+        dim
+    }
+
+    /// Creates a copy of the `Dimension` with the given gap to neighbouring objects on the right
+    /// and bottom side.
+    pub(crate) fn to_spaced(self, spacing: C) -> Self {
+        let mut dim = self;
+        dim.spacing = spacing;
+        dim
+    }
+
+    /// Creates a copy of the `Dimension` whose size is rounded up to multiples of the given
+    /// [`Alignment`], if requested by the alignment.
+    pub(crate) fn to_aligned(self, alignment: &Alignment<C>) -> Self {
+        let mut dim = self;
+        dim.rounding = if alignment.round_size {
+            (
+                align_gap(dim.width, alignment.x),
+                align_gap(dim.height, alignment.y),
+            )
+        } else {
+            (C::ZERO, C::ZERO)
+        };
+        dim
+    }
+
+    /// Returns the orientations of the `Dimension` which should be considered by a bin packer,
+    /// according to the [`Rotation`] policy.
+    ///
+    /// `rotate` indicates whether the bin packer considers rotation of objects with the
+    /// [`Rotation::Allowed`] policy.
+    ///
+    /// Each orientation is returned as a tuple of the oriented `Dimension` and a flag that
+    /// indicates whether it was rotated. The upright orientation is always returned first.
+    pub(crate) fn orientations(&self, rotate: bool) -> impl Iterator<Item = (Self, bool)> {
+        let (upright, flipped) = match self.rotation {
+            Rotation::Forbidden => (true, false),
+            // rotating a square object would not make any difference
+            Rotation::Allowed => (true, rotate && self.width != self.height),
+            Rotation::Forced => (false, true),
+        };
+        let upright = upright.then_some((*self, false));
+        let flipped = flipped.then(|| (self.to_flipped(), true));
+        upright.into_iter().chain(flipped)
+    }
+
+    /// Returns whether the `Dimension` fits into an area of the specified size in any of the
+    /// orientations permitted by the [`Rotation`] policy.
+    ///
+    /// Padding and spacing are included in the check. Objects whose size including padding and
+    /// spacing is out of range of the coordinate type never fit.
+    pub(crate) fn fits_into(&self, width: C, height: C, rotate: bool) -> bool {
+        self.is_total_valid()
+            && self.orientations(rotate).any(|(dim, _)| {
+                coord::approx_le(dim.width_total(), width)
+                    && coord::approx_le(dim.height_total(), height)
+            })
     }
 
     /// Returns `true` if `width` or `height` of the `Dimension` is 0, and `false` otherwise.
     pub fn is_empty(&self) -> bool {
-        self.width == 0 || self.height == 0
+        self.width == C::ZERO || self.height == C::ZERO
     }
 
     /// Returns `true` if `width` or `height` of the `Dimension` is 0, and `false` otherwise.
     ///
-    /// Padding is included in the check.
+    /// Padding and spacing are included in the check. Extents of up to [`Coord::EPSILON`] are
+    /// considered to be 0.
     pub(crate) fn is_empty_total(&self) -> bool {
-        (self.width + self.padding) == 0 || (self.height + self.padding) == 0
+        !coord::approx_lt(C::ZERO, self.width_total())
+            || !coord::approx_lt(C::ZERO, self.height_total())
     }
 
     /// Computes the area of this `Dimension`.
-    pub fn area(&self) -> i64 {
-        self.width as i64 * self.height as i64
+    ///
+    /// The area is computed with the [`Wide`] type of the coordinate type, e.g. `i64` for `i32`
+    /// coordinates.
+    ///
+    /// [`Wide`]: Coord::Wide
+    pub fn area(&self) -> C::Wide {
+        self.width.mul_wide(self.height)
     }
 
     /// Computes the area of this `Dimension`.
     ///
-    /// Padding is included in the calculation.
-    pub(crate) fn area_total(&self) -> i64 {
-        self.width_total() as i64 * self.height_total() as i64
+    /// Padding and spacing are included in the calculation.
+    pub(crate) fn area_total(&self) -> C::Wide {
+        self.width_total().mul_wide(self.height_total())
+    }
+}
+
+/// Fields of a `Dimension` which take part in comparisons.
+type DimensionKey<C> = (
+    isize,
+    C,
+    C,
+    Padding<C>,
+    Rotation,
+    Option<isize>,
+    Option<Alignment<C>>,
+);
+
+impl<C: Coord> Dimension<C> {
+    /// Returns the fields which take part in comparisons.
+    ///
+    /// The spacing, rounding and payload tag are assigned internally and excluded, so that
+    /// packed objects compare equal to the objects they were created from.
+    fn key(&self) -> DimensionKey<C> {
+        (
+            self.id,
+            self.width,
+            self.height,
+            self.padding,
+            self.rotation,
+            self.group,
+            self.alignment,
+        )
+    }
+}
+
+impl<C: Coord> PartialEq for Dimension<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl<C: Coord + Eq> Eq for Dimension<C> {}
+
+impl<C: Coord> PartialOrd for Dimension<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.key().partial_cmp(&other.key())
+    }
+}
+
+impl<C: Coord + Ord> Ord for Dimension<C> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
     }
 }
 
-impl Default for Dimension {
+impl<C: Coord> Default for Dimension<C> {
     fn default() -> Self {
-        Dimension::new(0, 0)
+        Dimension::new(C::ZERO, C::ZERO)
     }
 }
 
-impl Display for Dimension {
+impl<C: Coord> Display for Dimension<C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
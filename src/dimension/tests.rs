@@ -1,4 +1,4 @@
-use super::Dimension;
+use super::{Alignment, Dimension, IdAllocator, Padding, Rotation};
 
 #[test]
 fn dimension_get() {
@@ -6,7 +6,7 @@ fn dimension_get() {
     assert_eq!(12, dim.id());
     assert_eq!(50, dim.width());
     assert_eq!(30, dim.height());
-    assert_eq!(3, dim.padding());
+    assert_eq!(Padding::uniform(3), dim.padding());
     assert_eq!(56, dim.width_total());
     assert_eq!(36, dim.height_total());
 }
@@ -25,11 +25,16 @@ fn dimension_set() {
     dim.set_padding(3);
     assert_eq!(39, dim.width_total());
     assert_eq!(50, dim.height_total());
+
+    assert!(dim.group().is_none());
+    dim.set_group(Some(4));
+    assert_eq!(Some(4), dim.group());
+    assert_eq!(Some(4), dim.to_flipped().group());
 }
 
 #[test]
 fn dimension_area() {
-    let mut dim = Dimension::with_padding(5, 8, 3);
+    let mut dim: Dimension = Dimension::with_padding(5, 8, 3);
     assert_eq!(5 * 8, dim.area());
     assert_eq!(11 * 14, dim.area_total());
 
@@ -51,6 +56,27 @@ fn dimension_flip() {
     assert_eq!(5, dim2.height());
 }
 
+#[test]
+fn dimension_compare() {
+    let dim = Dimension::with_id(1, 5, 3, 1);
+    assert!(dim < Dimension::with_id(2, 5, 3, 1));
+    assert_ne!(dim, Dimension::with_id(1, 5, 4, 1));
+
+    // fields assigned by the bin packer are ignored
+    let spaced = dim.to_spaced(2);
+    assert_eq!(9, spaced.width_total());
+    assert_eq!(dim, spaced);
+    assert_eq!(std::cmp::Ordering::Equal, dim.cmp(&spaced));
+
+    let rounded = dim.to_aligned(&Alignment::new(4, 4, true));
+    assert_eq!(10, rounded.width_total());
+    assert_eq!(dim, rounded);
+
+    let mut tagged = dim;
+    tagged.tag = 3;
+    assert_eq!(dim, tagged);
+}
+
 #[test]
 fn dimension_empty() {
     let dim1 = Dimension::new(0, 0);
@@ -80,3 +106,90 @@ fn dimension_default() {
     assert!(dim.is_empty());
     assert!(dim.is_empty_total());
 }
+
+#[test]
+fn dimension_rotation() {
+    let mut dim = Dimension::with_padding(3, 5, 1);
+    assert_eq!(Rotation::Allowed, dim.rotation());
+    assert!(dim.fits_into(7, 5, true));
+    assert!(!dim.fits_into(7, 5, false));
+    assert_eq!(2, dim.orientations(true).count());
+    assert_eq!(1, dim.orientations(false).count());
+
+    dim.set_rotation(Rotation::Forbidden);
+    assert_eq!(Rotation::Forbidden, dim.to_flipped().rotation());
+    assert!(!dim.fits_into(7, 5, true));
+    assert!(dim.orientations(true).all(|(_, rotated)| !rotated));
+
+    dim.set_rotation(Rotation::Forced);
+    assert!(dim.fits_into(7, 5, false));
+    assert!(!dim.fits_into(5, 7, true));
+    assert!(dim
+        .orientations(false)
+        .all(|(d, rotated)| rotated && d.width() == 5));
+}
+
+#[test]
+fn dimension_padding() {
+    let padding = Padding::new(1, 2, 3, -4);
+    assert_eq!(0, padding.bottom());
+    assert!(!padding.is_uniform());
+    assert!(Padding::from(2).is_uniform());
+
+    let mut dim = Dimension::with_padding(10, 20, padding);
+    assert_eq!(14, dim.width_total());
+    assert_eq!(22, dim.height_total());
+
+    dim.flip();
+    assert_eq!(Padding::new(2, 1, 0, 3), dim.padding());
+    assert_eq!(22, dim.width_total());
+    assert_eq!(14, dim.height_total());
+
+    let dim = dim.to_spaced(2);
+    assert_eq!(24, dim.width_total());
+    assert_eq!(16, dim.height_total());
+}
+
+#[test]
+fn dimension_alignment() {
+    let alignment = Alignment::new(4, 0, true);
+    assert_eq!((4, 1), (alignment.x(), alignment.y()));
+    assert!(alignment.is_aligned(8, 3));
+    assert!(!alignment.is_aligned(6, 0));
+    assert_eq!(Some(8), alignment.align_x(5));
+    assert_eq!(Some(8), alignment.align_x(8));
+
+    let mut dim = Dimension::new(5, 3);
+    assert!(dim.alignment().is_none());
+    dim.set_alignment(Some(alignment));
+    assert_eq!(Some(alignment), dim.alignment());
+
+    let dim = dim.to_aligned(&alignment);
+    assert_eq!((8, 3), (dim.width_total(), dim.height_total()));
+    assert_eq!(
+        (3, 8),
+        (
+            dim.to_flipped().width_total(),
+            dim.to_flipped().height_total()
+        )
+    );
+    let dim = dim.to_aligned(&Alignment::new(4, 4, false));
+    assert_eq!((5, 3), (dim.width_total(), dim.height_total()));
+}
+
+#[test]
+fn dimension_allocator() {
+    let mut ids = IdAllocator::default();
+    assert_eq!(1, ids.peek());
+    assert_eq!(1, Dimension::with_allocator(&mut ids, 4, 4, 0).id());
+    assert_eq!(2, Dimension::with_allocator(&mut ids, 4, 4, 1).id());
+    assert_eq!(3, ids.peek());
+
+    let mut ids = IdAllocator::with_seed(-5);
+    let first: Vec<isize> = (0..3).map(|_| ids.next_id()).collect();
+    assert_eq!(vec![-5, -4, -3], first);
+    ids.reset();
+    let second: Vec<isize> = (0..3).map(|_| ids.next_id()).collect();
+    assert_eq!(first, second);
+    assert_eq!(-5, ids.seed());
+}
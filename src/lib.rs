@@ -4,16 +4,34 @@
 pub use crate::binpack::bin_new;
 pub use crate::binpack::bin_with_capacity;
 pub use crate::binpack::pack_bins;
+pub use crate::binpack::pack_strip;
 pub use crate::binpack::BinError;
 pub use crate::binpack::BinPacker;
 pub use crate::binpack::BinType;
+pub use crate::binpack::PackError;
+pub use crate::binpack::Rejection;
 
+pub use crate::binpack::binset;
+pub use crate::binpack::catalog;
+pub use crate::binpack::growth;
 pub use crate::binpack::guillotine;
+pub use crate::binpack::keyed;
 pub use crate::binpack::maxrects;
+pub use crate::binpack::payload;
+pub use crate::binpack::shelf;
+pub use crate::binpack::sizing;
+pub use crate::binpack::skyline;
+pub use crate::binpack::validate;
 
+pub use crate::coord::Coord;
+pub use crate::dimension::Alignment;
 pub use crate::dimension::Dimension;
+pub use crate::dimension::IdAllocator;
+pub use crate::dimension::Padding;
+pub use crate::dimension::Rotation;
 pub use crate::rectangle::Rectangle;
 
 pub mod binpack;
+pub mod coord;
 pub mod dimension;
 pub mod rectangle;
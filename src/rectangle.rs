@@ -1,70 +1,114 @@
 //! A structure that represents the placement of a single object in a bin.
 
-use super::dimension::{self, Dimension};
+use super::dimension::{self, Dimension, Padding};
+use crate::coord::{self, Coord};
 use std::fmt::{Display, Formatter};
 
 /// `Rectangle` specifies an area in a coordinate space that is defined an upper-left point,
 /// as defined by `x` and `y`, and the dimensions, defined by the [`Dimension`] object.
+///
+/// Rectangles returned by the bin packers additionally keep track of whether the original
+/// [`Dimension`] has been rotated by 90 degrees to be placed into the bin.
+///
+/// Coordinates and extents are of the [`Coord`] type `C`, which is `i32` by default.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
-pub struct Rectangle {
-    x: i32,
-    y: i32,
-    dim: Dimension,
+pub struct Rectangle<C: Coord = i32> {
+    x: C,
+    y: C,
+    dim: Dimension<C>,
+    rotated: bool,
 }
 
-impl Rectangle {
+impl<C: Coord> Rectangle<C> {
     /// Creates a new `Rect` whose upper-left corner is defined by `x` and `y`, and whose `width`
     /// and `height` are defined by the [`Dimension`] type.
-    pub fn new(x: i32, y: i32, dim: Dimension) -> Rectangle {
-        Rectangle { x, y, dim }
+    pub fn new(x: C, y: C, dim: Dimension<C>) -> Self {
+        Self {
+            x,
+            y,
+            dim,
+            rotated: false,
+        }
     }
 
     /// Returns the x coordinate of the bounding `Rectangle`.
-    pub fn x(&self) -> i32 {
+    pub fn x(&self) -> C {
         self.x
     }
 
     /// Returns the x coordinate of the bounding `Rectangle`, including padding.
-    pub(crate) fn x_total(&self) -> i32 {
-        self.x - self.dim.padding
+    pub(crate) fn x_total(&self) -> C {
+        self.x - self.dim.padding.left()
     }
 
     /// Returns the y coordinate of the bounding `Rectangle`.
-    pub fn y(&self) -> i32 {
+    pub fn y(&self) -> C {
         self.y
     }
 
     /// Returns the y coordinate of the bounding `Rectangle`, including padding.
-    pub(crate) fn y_total(&self) -> i32 {
-        self.y - self.dim.padding
+    pub(crate) fn y_total(&self) -> C {
+        self.y - self.dim.padding.top()
+    }
+
+    /// Returns the x coordinate of the right edge of the bounding `Rectangle`, including padding
+    /// and spacing.
+    ///
+    /// The result saturates at the greatest value of the coordinate type.
+    pub(crate) fn right_total(&self) -> C {
+        self.x_total().saturating_add(self.width_total())
+    }
+
+    /// Returns the y coordinate of the bottom edge of the bounding `Rectangle`, including padding
+    /// and spacing.
+    ///
+    /// The result saturates at the greatest value of the coordinate type.
+    pub(crate) fn bottom_total(&self) -> C {
+        self.y_total().saturating_add(self.height_total())
+    }
+
+    /// Returns `true` if all edges of the bounding `Rectangle` including padding and spacing are
+    /// within range of the coordinate type, and `false` otherwise.
+    pub(crate) fn is_total_valid(&self) -> bool {
+        self.dim.is_total_valid()
+            && self
+                .x
+                .checked_sub(self.dim.padding.left())
+                .and_then(|x| x.checked_add(self.width_total()))
+                .is_some()
+            && self
+                .y
+                .checked_sub(self.dim.padding.top())
+                .and_then(|y| y.checked_add(self.height_total()))
+                .is_some()
     }
 
     /// Moves this `Rectangle` horizontally to the location specified by x.
-    pub fn set_x(&mut self, x: i32) {
+    pub fn set_x(&mut self, x: C) {
         self.x = x;
     }
 
     /// Moves this `Rectangle` horizontally to the location specified by x.
     ///
     /// Includes padding in the calculation.
-    pub(crate) fn set_x_total(&mut self, x: i32) {
-        self.x = x + self.dim.padding;
+    pub(crate) fn set_x_total(&mut self, x: C) {
+        self.x = x + self.dim.padding.left();
     }
 
     /// Moves this `Rectangle` vertically to the location specified by y.
-    pub fn set_y(&mut self, y: i32) {
+    pub fn set_y(&mut self, y: C) {
         self.y = y;
     }
 
     /// Moves this `Rectangle` vertically to the location specified by y.
     ///
     /// Includes padding in the calculation.
-    pub(crate) fn set_y_total(&mut self, y: i32) {
-        self.y = y + self.dim.padding;
+    pub(crate) fn set_y_total(&mut self, y: C) {
+        self.y = y + self.dim.padding.top();
     }
 
     /// Moves this `Rectangle` to the location specified by x and y.
-    pub fn set_location(&mut self, x: i32, y: i32) {
+    pub fn set_location(&mut self, x: C, y: C) {
         self.x = x;
         self.y = y;
     }
@@ -72,20 +116,21 @@ impl Rectangle {
     /// Moves this `Rectangle` to the location specified by x and y.
     ///
     /// Includes padding in the calculation.
-    pub(crate) fn set_location_total(&mut self, x: i32, y: i32) {
-        self.x = x + self.dim.padding;
-        self.y = y + self.dim.padding;
+    pub(crate) fn set_location_total(&mut self, x: C, y: C) {
+        self.x = x + self.dim.padding.left();
+        self.y = y + self.dim.padding.top();
     }
 
     /// Translates this `Rectangle` the indicated distance, to the right along the X axis,
     /// and downward along the Y axis.
     ///
-    /// **Note:** Underflow and overflow are bound by [`i32::MIN`] and [`i32::MAX`] respectively.
-    pub fn translate(&mut self, dx: i32, dy: i32) {
-        if dx != 0 {
+    /// **Note:** Underflow and overflow are bound by [`Coord::MIN`] and [`Coord::MAX`]
+    /// respectively.
+    pub fn translate(&mut self, dx: C, dy: C) {
+        if dx != C::ZERO {
             self.x = self.x.saturating_add(dx);
         }
-        if dy != 0 {
+        if dy != C::ZERO {
             self.y = self.y.saturating_add(dy);
         }
     }
@@ -96,35 +141,63 @@ impl Rectangle {
     }
 
     /// Returns the width of the bounding `Rectangle` without padding.
-    pub fn width(&self) -> i32 {
+    pub fn width(&self) -> C {
         self.dim.width()
     }
 
-    /// Returns the width of the bounding `Rectangle` with padding.
-    pub(crate) fn width_total(&self) -> i32 {
+    /// Returns the width of the bounding `Rectangle` with padding and spacing.
+    pub(crate) fn width_total(&self) -> C {
         self.dim.width_total()
     }
 
     /// Returns the height of the bounding `Rectangle` without padding.
-    pub fn height(&self) -> i32 {
+    pub fn height(&self) -> C {
         self.dim.height()
     }
 
-    /// Returns the height of the bounding `Rectangle` with padding.
-    pub(crate) fn height_total(&self) -> i32 {
+    /// Returns the height of the bounding `Rectangle` with padding and spacing.
+    pub(crate) fn height_total(&self) -> C {
         self.dim.height_total()
     }
 
     /// Returns an immutable reference to the associated [`Dimension`] object.
-    pub fn dim(&self) -> &Dimension {
+    pub fn dim(&self) -> &Dimension<C> {
         &self.dim
     }
 
     /// Returns a mutable reference to the associated [`Dimension`] object.
-    pub fn dim_mut(&mut self) -> &mut Dimension {
+    pub fn dim_mut(&mut self) -> &mut Dimension<C> {
         &mut self.dim
     }
 
+    /// Returns `true` if the associated [`Dimension`] has been rotated by 90 degrees when it was
+    /// placed into the bin, and `false` otherwise.
+    ///
+    /// Width and height of a rotated `Rectangle` are swapped in relation to the original
+    /// [`Dimension`] object.
+    pub fn is_rotated(&self) -> bool {
+        self.rotated
+    }
+
+    /// Specifies whether the associated [`Dimension`] has been rotated by 90 degrees.
+    ///
+    /// This is only a marker. Width and height of the `Rectangle` are not affected by this method.
+    pub fn set_rotated(&mut self, rotated: bool) {
+        self.rotated = rotated;
+    }
+
+    /// Returns the [`Dimension`] object in its original orientation, i.e. before it was rotated
+    /// to be placed into the bin.
+    ///
+    /// Returns a copy of the associated [`Dimension`] object if the `Rectangle` is not rotated.
+    pub fn original_dim(&self) -> Dimension<C> {
+        if self.rotated {
+            self.dim.to_flipped()
+        } else {
+            self.dim
+        }
+    }
+
     /// Returns `true` if `width` or `height` of the `Rectangle` is 0, and `false` otherwise.
     ///
     /// Padding is not included in the check.
@@ -134,46 +207,57 @@ impl Rectangle {
 
     /// Checks whether or not this `Rectangle` entirely contains the specified `Rectangle`.
     ///
-    /// Padding is not included in the check.
-    pub fn contains(&self, rect: &Rectangle) -> bool {
-        rect.x >= self.x
-            && rect.y >= self.y
-            && rect.x + rect.width() <= self.x + self.width()
-            && rect.y + rect.height() <= self.y + self.height()
+    /// Padding is not included in the check. Edges may exceed this `Rectangle` by up to
+    /// [`Coord::EPSILON`].
+    pub fn contains(&self, rect: &Self) -> bool {
+        coord::approx_le(self.x, rect.x)
+            && coord::approx_le(self.y, rect.y)
+            && coord::approx_le(
+                rect.x.saturating_add(rect.width()),
+                self.x.saturating_add(self.width()),
+            )
+            && coord::approx_le(
+                rect.y.saturating_add(rect.height()),
+                self.y.saturating_add(self.height()),
+            )
     }
 
     /// Checks whether or not this `Rectangle` entirely contains the specified `Rectangle`.
     ///
-    /// Padding is included in the check.
-    pub(crate) fn contains_total(&self, rect: &Rectangle) -> bool {
-        rect.x_total() >= self.x_total()
-            && rect.y_total() >= self.y_total()
-            && rect.x_total() + rect.width_total() <= self.x_total() + self.width_total()
-            && rect.y_total() + rect.height_total() <= self.y_total() + self.height_total()
+    /// Padding is included in the check. Edges may exceed this `Rectangle` by up to
+    /// [`Coord::EPSILON`].
+    pub(crate) fn contains_total(&self, rect: &Self) -> bool {
+        coord::approx_le(self.x_total(), rect.x_total())
+            && coord::approx_le(self.y_total(), rect.y_total())
+            && coord::approx_le(rect.right_total(), self.right_total())
+            && coord::approx_le(rect.bottom_total(), self.bottom_total())
     }
 
     /// Checks whether or not this `Rectangle` and the specified `Rectangle` intersect.
     ///
-    /// Padding is not included in the check.
-    pub fn intersects(&self, rect: &Rectangle) -> bool {
-        let mut tw = self.width();
-        let mut th = self.height();
-        let mut rw = rect.width();
-        let mut rh = rect.height();
-        if rw == 0 || rh == 0 || tw == 0 || th == 0 {
+    /// Padding is not included in the check. Overlaps of up to [`Coord::EPSILON`] are ignored.
+    pub fn intersects(&self, rect: &Self) -> bool {
+        if self.is_empty() || rect.is_empty() {
             return false;
         }
 
-        let tx = self.x;
-        let ty = self.y;
-        let rx = rect.x;
-        let ry = rect.y;
-        rw += rx;
-        rh += ry;
-        tw += tx;
-        th += ty;
-        // overflow || intersect
-        (rw < rx || rw > tx) && (rh < ry || rh > ty) && (tw < tx || tw > rx) && (th < ty || th > ry)
+        // right and bottom edges saturate instead of overflowing
+        coord::approx_lt(rect.x, self.x.saturating_add(self.width()))
+            && coord::approx_lt(self.x, rect.x.saturating_add(rect.width()))
+            && coord::approx_lt(rect.y, self.y.saturating_add(self.height()))
+            && coord::approx_lt(self.y, rect.y.saturating_add(rect.height()))
+    }
+
+    /// Checks whether or not this `Rectangle` and the specified `Rectangle` intersect.
+    ///
+    /// Padding is included in the check. Overlaps of up to [`Coord::EPSILON`] are ignored.
+    pub(crate) fn intersects_total(&self, rect: &Self) -> bool {
+        !self.dim.is_empty_total()
+            && !rect.dim.is_empty_total()
+            && coord::approx_lt(rect.x_total(), self.right_total())
+            && coord::approx_lt(self.x_total(), rect.right_total())
+            && coord::approx_lt(rect.y_total(), self.bottom_total())
+            && coord::approx_lt(self.y_total(), rect.bottom_total())
     }
 
     /// Computes the union of this `Rectangle` with the specified `Rectangle`.
@@ -181,45 +265,59 @@ impl Rectangle {
     /// `rect` specifies the second rectangle to use for the union.
     ///
     /// `id` will be used as new identifier for the dimension included the returned `Rectangle`.
-    /// An identifier is autogenerated if if `None` is specified.
+    /// An identifier is autogenerated if if `None` is specified. Reproducible identifiers can be
+    /// provided by an [`IdAllocator`].
     ///
-    /// The greater padding of the source `Rectangle`s is applied to the returned `Rectangle`.
+    /// [`IdAllocator`]: crate::IdAllocator
+    ///
+    /// The greater padding of the source `Rectangle`s is applied to each side of the returned
+    /// `Rectangle`.
     ///
     /// Returns a new `Rectangle` that represents the union of the two rectangles.
-    pub fn union(&self, rect: &Rectangle, id: Option<isize>) -> Self {
-        let min_x = self.x.min(rect.x);
-        let min_y = self.y.min(rect.y);
-
-        let max_x = (self.x + self.width()).max(rect.x + rect.width());
-        let width = max_x - min_x;
-
-        let max_y = (self.y + self.height()).max(rect.y + rect.height());
-        let height = max_y - min_y;
+    pub fn union(&self, rect: &Self, id: Option<isize>) -> Self {
+        let min_x = coord::min(self.x, rect.x);
+        let min_y = coord::min(self.y, rect.y);
+
+        let max_x = coord::max(
+            self.x.saturating_add(self.width()),
+            rect.x.saturating_add(rect.width()),
+        );
+        let width = max_x.saturating_sub(min_x);
+
+        let max_y = coord::max(
+            self.y.saturating_add(self.height()),
+            rect.y.saturating_add(rect.height()),
+        );
+        let height = max_y.saturating_sub(min_y);
 
         let id = id.unwrap_or_else(dimension::get_unique_id);
 
-        let padding = self.dim.padding().max(rect.dim.padding());
-
-        Self {
-            x: min_x,
-            y: min_y,
-            dim: Dimension::with_id(id, width, height, padding),
-        }
+        let (p1, p2) = (self.dim.padding(), rect.dim.padding());
+        let padding = Padding::new(
+            coord::max(p1.left(), p2.left()),
+            coord::max(p1.top(), p2.top()),
+            coord::max(p1.right(), p2.right()),
+            coord::max(p1.bottom(), p2.bottom()),
+        );
+
+        let mut dim = Dimension::with_id(id, width, height, padding);
+        dim.spacing = coord::max(self.dim.spacing, rect.dim.spacing);
+        Self::new(min_x, min_y, dim)
     }
 }
 
-impl From<Dimension> for Rectangle {
-    fn from(value: Dimension) -> Self {
-        Rectangle::new(0, 0, value)
+impl<C: Coord> From<Dimension<C>> for Rectangle<C> {
+    fn from(value: Dimension<C>) -> Self {
+        Rectangle::new(C::ZERO, C::ZERO, value)
     }
 }
 
-impl Display for Rectangle {
+impl<C: Coord> Display for Rectangle<C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Rectangle(x: {}, y: {}, dim: {})",
-            self.x, self.y, self.dim
+            "Rectangle(x: {}, y: {}, dim: {}, rotated: {})",
+            self.x, self.y, self.dim, self.rotated
         )
     }
 }
@@ -1,9 +1,9 @@
 use super::Rectangle;
-use crate::dimension::Dimension;
+use crate::dimension::{Dimension, Padding};
 
 #[test]
 fn rectangle_get() {
-    let rect = Rectangle::new(5, 8, Dimension::with_id(1, 6, 7, 2));
+    let rect: Rectangle = Rectangle::new(5, 8, Dimension::with_id(1, 6, 7, 2));
     assert_eq!(1, rect.id());
     assert_eq!(5, rect.x());
     assert_eq!(3, rect.x_total());
@@ -43,6 +43,16 @@ fn rectangle_set() {
     rect.dim_mut().set_padding(2);
     assert_eq!(0, rect.x_total());
     assert_eq!(0, rect.y_total());
+
+    rect.dim_mut().set_padding(Padding::new(1, 2, 3, 4));
+    assert_eq!(1, rect.x_total());
+    assert_eq!(0, rect.y_total());
+    assert_eq!(11, rect.width_total());
+    assert_eq!(14, rect.height_total());
+
+    rect.set_location_total(4, 4);
+    assert_eq!(5, rect.x());
+    assert_eq!(6, rect.y());
 }
 
 #[test]
@@ -91,3 +101,17 @@ fn rectangle_union() {
         r1.union(&r3, Some(2))
     );
 }
+
+#[test]
+fn rectangle_rotated() {
+    let dim = Dimension::with_id(1, 3, 5, 1);
+    let mut rect = Rectangle::new(0, 0, dim.to_flipped());
+    assert!(!rect.is_rotated());
+    assert_eq!(dim.to_flipped(), rect.original_dim());
+
+    rect.set_rotated(true);
+    assert!(rect.is_rotated());
+    assert_eq!(5, rect.width());
+    assert_eq!(3, rect.height());
+    assert_eq!(dim, rect.original_dim());
+}
@@ -4,7 +4,12 @@ use std::time::Instant;
 
 #[test]
 fn bin_performance_binpack() {
-    let bin_types = vec![BinType::MaxRects, BinType::Guillotine];
+    let bin_types = vec![
+        BinType::MaxRects,
+        BinType::Guillotine,
+        BinType::Skyline,
+        BinType::Shelf,
+    ];
 
     const DIM: i32 = 512;
     const SIZE: usize = 1_000;
@@ -58,17 +63,29 @@ fn bin_failure() {
     nodes.push(Dimension::with_padding(20, 12, 0));
 
     let result1 = pack_bins(BinType::MaxRects, &nodes, 16, 16, true);
-    assert_eq!(BinError::ItemTooBig, result1.err().unwrap());
+    assert_eq!(
+        BinError::ItemTooBig,
+        result1.err().unwrap().rejections()[0].reason()
+    );
 
     let result2 = pack_bins(BinType::MaxRects, &nodes, 16, 16, false);
-    assert_eq!(BinError::ItemTooBig, result2.err().unwrap());
+    assert_eq!(
+        BinError::ItemTooBig,
+        result2.err().unwrap().rejections()[0].reason()
+    );
 
     nodes.pop();
     nodes.push(Dimension::with_padding(0, 64, 0));
 
     let result3 = pack_bins(BinType::MaxRects, &nodes, 16, 16, true);
-    assert_eq!(BinError::ItemTooSmall, result3.err().unwrap());
+    assert_eq!(
+        BinError::ItemTooSmall,
+        result3.err().unwrap().rejections()[0].reason()
+    );
 
     let result4 = pack_bins(BinType::MaxRects, &nodes, 16, 16, false);
-    assert_eq!(BinError::ItemTooSmall, result4.err().unwrap());
+    assert_eq!(
+        BinError::ItemTooSmall,
+        result4.err().unwrap().rejections()[0].reason()
+    );
 }
@@ -30,7 +30,7 @@ fn bin_performance_maxrect() {
     for rule in rules {
         // running benchmark
         let now = Instant::now();
-        let bins_result = pack_bins(&nodes, DIM, DIM, rule, false);
+        let bins_result = pack_bins(&nodes, DIM, DIM, false, rule, false);
         let elapsed = now.elapsed();
 
         if let Ok(bins) = bins_result {
@@ -64,18 +64,30 @@ fn bin_failure() {
 
     nodes.push(Dimension::with_padding(20, 12, 0));
 
-    let result1 = pack_bins(&nodes, 16, 16, Heuristic::BestShortSideFit, true);
-    assert_eq!(BinError::ItemTooBig, result1.err().unwrap());
+    let result1 = pack_bins(&nodes, 16, 16, false, Heuristic::BestShortSideFit, true);
+    assert_eq!(
+        BinError::ItemTooBig,
+        result1.err().unwrap().rejections()[0].reason()
+    );
 
-    let result2 = pack_bins(&nodes, 16, 16, Heuristic::BestShortSideFit, false);
-    assert_eq!(BinError::ItemTooBig, result2.err().unwrap());
+    let result2 = pack_bins(&nodes, 16, 16, false, Heuristic::BestShortSideFit, false);
+    assert_eq!(
+        BinError::ItemTooBig,
+        result2.err().unwrap().rejections()[0].reason()
+    );
 
     nodes.pop();
     nodes.push(Dimension::with_padding(0, 64, 0));
 
-    let result3 = pack_bins(&nodes, 16, 16, Heuristic::BestShortSideFit, true);
-    assert_eq!(BinError::ItemTooSmall, result3.err().unwrap());
+    let result3 = pack_bins(&nodes, 16, 16, false, Heuristic::BestShortSideFit, true);
+    assert_eq!(
+        BinError::ItemTooSmall,
+        result3.err().unwrap().rejections()[0].reason()
+    );
 
-    let result4 = pack_bins(&nodes, 16, 16, Heuristic::BestShortSideFit, false);
-    assert_eq!(BinError::ItemTooSmall, result4.err().unwrap());
+    let result4 = pack_bins(&nodes, 16, 16, false, Heuristic::BestShortSideFit, false);
+    assert_eq!(
+        BinError::ItemTooSmall,
+        result4.err().unwrap().rejections()[0].reason()
+    );
 }